@@ -16,6 +16,16 @@ pub enum Action {
     DisconnectFromDatabase { 
         database_name: String 
     },
+    AttachDatabase {
+        database_name: String,
+        path: String,
+        alias: String,
+        read_only: bool
+    },
+    DetachDatabase {
+        database_name: String,
+        alias: String
+    },
     CreateNewDatabase { 
         database_name: String,
         database_type: DatabaseType
@@ -33,6 +43,90 @@ pub enum Action {
         table_name: String 
     },
     RefreshTables,
+    CreateTable {
+        table_name: String,
+        statement: String
+    },
+    
+    // Data editing
+    UpdateCell {
+        table_name: String,
+        column_name: String,
+        row_key: String,
+        new_value: Option<String>
+    },
+    InsertRow {
+        table_name: String,
+        columns: Vec<String>
+    },
+    DeleteRows {
+        table_name: String,
+        row_keys: Vec<String>
+    },
+    BulkUpdate {
+        table_name: String,
+        column_name: String,
+        expression: String,
+        filter: String,
+        row_count: usize
+    },
+    BulkDelete {
+        table_name: String,
+        filter: String,
+        row_count: usize
+    },
+    AlterTable {
+        table_name: String,
+        statement: String
+    },
+    RenameTable {
+        table_name: String,
+        new_name: String
+    },
+    DuplicateTable {
+        table_name: String,
+        new_name: String,
+        with_data: bool
+    },
+    TruncateTable {
+        table_name: String
+    },
+    CommentOnTable {
+        table_name: String,
+        comment: Option<String>
+    },
+    CommentOnColumn {
+        table_name: String,
+        column_name: String,
+        comment: Option<String>
+    },
+    CreateSchema {
+        schema_name: String
+    },
+    DropSchema {
+        schema_name: String
+    },
+    CreateIndex {
+        table_name: String,
+        index_name: String,
+        columns: Vec<String>,
+        unique: bool
+    },
+    DropIndex {
+        table_name: String,
+        index_name: String
+    },
+    BeginTransaction {
+        database_name: String
+    },
+    CommitTransaction {
+        database_name: String,
+        statement_count: usize
+    },
+    RollbackTransaction {
+        database_name: String,
+        statement_count: usize
+    },
     
     // File operations
     SelectFile { 
@@ -43,6 +137,19 @@ pub enum Action {
     ExecuteQuery { 
         query: String 
     },
+    ExecuteDefinition {
+        statement: String
+    },
+    
+    CompareSchemas {
+        source: String,
+        target: String
+    },
+    CompareRelations {
+        left: String,
+        right: String,
+        key_columns: Vec<String>
+    },
     
     // Data import/export (for future use)
     ImportData { 
@@ -111,6 +218,13 @@ impl std::fmt::Display for Action {
             Action::DisconnectFromDatabase { database_name } => {
                 write!(f, "Disconnect from database '{}'", database_name)
             }
+            Action::AttachDatabase { database_name, path, alias, read_only } => {
+                let mode = if *read_only { "read-only" } else { "read-write" };
+                write!(f, "Attach '{}' to '{}' as '{}' ({})", path, database_name, alias, mode)
+            }
+            Action::DetachDatabase { database_name, alias } => {
+                write!(f, "Detach '{}' from '{}'", alias, database_name)
+            }
             Action::CreateNewDatabase { database_name, database_type } => {
                 write!(f, "Create new database '{}' ({:?})", database_name, database_type)
             }
@@ -124,12 +238,86 @@ impl std::fmt::Display for Action {
                 write!(f, "Select table '{}'", table_name)
             }
             Action::RefreshTables => write!(f, "Refresh tables"),
+            Action::CreateTable { table_name, statement } => {
+                write!(f, "Create table '{}': {}", table_name, statement.replace('\n', " "))
+            }
+            Action::UpdateCell { table_name, column_name, row_key, new_value } => {
+                write!(f, "Update '{}'.'{}' where {} to {}", table_name, column_name, row_key, new_value.as_deref().unwrap_or("NULL"))
+            }
+            Action::InsertRow { table_name, columns } => {
+                write!(f, "Insert row into '{}' ({})", table_name, columns.join(", "))
+            }
+            Action::DeleteRows { table_name, row_keys } => {
+                write!(f, "Delete {} row(s) from '{}' where {}", row_keys.len(), table_name, row_keys.join("; "))
+            }
+            Action::BulkUpdate { table_name, column_name, expression, filter, row_count } => {
+                write!(f, "Set '{}'.'{}' = {} on {} row(s) {}", table_name, column_name, expression, row_count, filter)
+            }
+            Action::BulkDelete { table_name, filter, row_count } => {
+                write!(f, "Delete {} row(s) from '{}' {}", row_count, table_name, filter)
+            }
+            Action::AlterTable { table_name, statement } => {
+                write!(f, "Alter table '{}': {}", table_name, statement)
+            }
+            Action::RenameTable { table_name, new_name } => {
+                write!(f, "Rename '{}' to '{}'", table_name, new_name)
+            }
+            Action::DuplicateTable { table_name, new_name, with_data } => {
+                let contents = if *with_data { "with data" } else { "structure only" };
+                write!(f, "Duplicate '{}' as '{}' ({})", table_name, new_name, contents)
+            }
+            Action::TruncateTable { table_name } => {
+                write!(f, "Truncate table '{}'", table_name)
+            }
+            Action::CommentOnTable { table_name, comment: Some(comment) } => {
+                write!(f, "Comment on '{}': {}", table_name, comment)
+            }
+            Action::CommentOnTable { table_name, comment: None } => {
+                write!(f, "Remove comment on '{}'", table_name)
+            }
+            Action::CommentOnColumn { table_name, column_name, comment: Some(comment) } => {
+                write!(f, "Comment on '{}.{}': {}", table_name, column_name, comment)
+            }
+            Action::CommentOnColumn { table_name, column_name, comment: None } => {
+                write!(f, "Remove comment on '{}.{}'", table_name, column_name)
+            }
+            Action::CreateSchema { schema_name } => {
+                write!(f, "Create schema '{}'", schema_name)
+            }
+            Action::DropSchema { schema_name } => {
+                write!(f, "Drop schema '{}' and its tables", schema_name)
+            }
+            Action::CreateIndex { table_name, index_name, columns, unique } => {
+                let kind = if *unique { "unique index" } else { "index" };
+                write!(f, "Create {} '{}' on '{}' ({})", kind, index_name, table_name, columns.join(", "))
+            }
+            Action::DropIndex { table_name, index_name } => {
+                write!(f, "Drop index '{}' on '{}'", index_name, table_name)
+            }
+            Action::BeginTransaction { database_name } => {
+                write!(f, "Begin transaction on '{}'", database_name)
+            }
+            Action::CommitTransaction { database_name, statement_count } => {
+                write!(f, "Commit {} staged change(s) on '{}'", statement_count, database_name)
+            }
+            Action::RollbackTransaction { database_name, statement_count } => {
+                write!(f, "Roll back {} staged change(s) on '{}'", statement_count, database_name)
+            }
             Action::SelectFile { path } => {
                 write!(f, "Select file '{}'", path)
             }
             Action::ExecuteQuery { query } => {
                 write!(f, "Execute query: {}", query)
             }
+            Action::ExecuteDefinition { statement } => {
+                write!(f, "Execute definition: {}", statement)
+            }
+            Action::CompareSchemas { source, target } => {
+                write!(f, "Compare schemas of '{}' and '{}'", source, target)
+            }
+            Action::CompareRelations { left, right, key_columns } => {
+                write!(f, "Compare '{}' with '{}' on {}", left, right, key_columns.join(", "))
+            }
             Action::ImportData { source_path, format, destination_table } => {
                 write!(f, "Import {} data from '{}' to table '{}'", format, source_path, destination_table)
             }
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::state::SortColumnSpec;
+
+/// A named snapshot of how a table is presented in the viewer.
+///
+/// Layouts live in ducky's own config directory rather than in the database,
+/// so they can be used on files we are not allowed to modify.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewLayout {
+    pub name: String,
+    pub column_order: Vec<String>,
+    pub hidden_columns: Vec<String>,
+    pub sort_columns: Vec<SortColumnSpec>,
+    pub column_filters: HashMap<String, String>,
+    // Expanded columns are stored by name so they survive reordering
+    pub expanded_columns: Vec<String>,
+    // Manually resized column widths (older layout files won't have these)
+    #[serde(default)]
+    pub column_widths: HashMap<String, usize>,
+}
+
+/// Persistent collection of layouts, keyed by database name and then table name
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LayoutStore {
+    layouts: HashMap<String, HashMap<String, Vec<ViewLayout>>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl LayoutStore {
+    /// Load layouts from the default location in the user's config directory
+    pub fn load() -> Result<Self> {
+        let path = Self::default_path()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+        Self::load_from(&path)
+    }
+
+    /// Load layouts from a specific file, starting empty if it doesn't exist yet
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let mut store = if path.exists() {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read layouts from '{}'", path.display()))?;
+            serde_json::from_str::<LayoutStore>(&contents)
+                .with_context(|| format!("Failed to parse layouts in '{}'", path.display()))?
+        } else {
+            LayoutStore::default()
+        };
+        store.path = Some(path.to_path_buf());
+        Ok(store)
+    }
+
+    fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("ducky").join("layouts.json"))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        // Without a file (e.g. the layouts failed to load) changes would silently be lost
+        let Some(path) = &self.path else {
+            anyhow::bail!("No layout file available, so layouts only last for this session");
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'", parent.display()))?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write layouts to '{}'", path.display()))?;
+        Ok(())
+    }
+
+    pub fn get_layouts(&self, database: &str, table: &str) -> &[ViewLayout] {
+        self.layouts
+            .get(database)
+            .and_then(|tables| tables.get(table))
+            .map(|layouts| layouts.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Insert a layout, replacing any existing layout with the same name
+    pub fn save_layout(&mut self, database: &str, table: &str, layout: ViewLayout) -> Result<()> {
+        let layouts = self.layouts
+            .entry(database.to_string())
+            .or_default()
+            .entry(table.to_string())
+            .or_default();
+
+        if let Some(existing) = layouts.iter_mut().find(|l| l.name == layout.name) {
+            *existing = layout;
+        } else {
+            layouts.push(layout);
+            layouts.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        self.save()
+    }
+
+    pub fn delete_layout(&mut self, database: &str, table: &str, name: &str) -> Result<()> {
+        let removed = if let Some(tables) = self.layouts.get_mut(database) {
+            let removed = if let Some(layouts) = tables.get_mut(table) {
+                let before = layouts.len();
+                layouts.retain(|l| l.name != name);
+                let removed = layouts.len() != before;
+
+                // Clean up empty entries
+                if layouts.is_empty() {
+                    tables.remove(table);
+                }
+                removed
+            } else {
+                false
+            };
+
+            if tables.is_empty() {
+                self.layouts.remove(database);
+            }
+            removed
+        } else {
+            false
+        };
+
+        if !removed {
+            return Err(anyhow::anyhow!("Layout '{}' not found for table '{}'", name, table));
+        }
+
+        self.save()
+    }
+
+    /// Move a table's layouts to its new name after a rename
+    pub fn rename_table(&mut self, database: &str, old: &str, new: &str) -> Result<()> {
+        let Some(tables) = self.layouts.get_mut(database) else {
+            return Ok(());
+        };
+        let Some(layouts) = tables.remove(old) else {
+            return Ok(());
+        };
+        tables.insert(new.to_string(), layouts);
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::SortDirection;
+
+    fn sample_layout(name: &str) -> ViewLayout {
+        ViewLayout {
+            name: name.to_string(),
+            column_order: vec!["b".to_string(), "a".to_string()],
+            hidden_columns: vec!["c".to_string()],
+            sort_columns: vec![SortColumnSpec {
+                column_name: "a".to_string(),
+                direction: SortDirection::Descending,
+            }],
+            column_filters: HashMap::from([("b".to_string(), "> 10".to_string())]),
+            expanded_columns: vec!["a".to_string()],
+            column_widths: HashMap::from([("a".to_string(), 30)]),
+        }
+    }
+
+    #[test]
+    fn test_layout_store_roundtrip() {
+        let path = std::env::temp_dir()
+            .join(format!("ducky_layouts_test_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut store = LayoutStore::load_from(&path).unwrap();
+        assert!(store.get_layouts("memory", "users").is_empty());
+
+        store.save_layout("memory", "users", sample_layout("by_a")).unwrap();
+        store.save_layout("memory", "users", sample_layout("another")).unwrap();
+
+        // Reload from disk and verify both layouts survived, sorted by name
+        let reloaded = LayoutStore::load_from(&path).unwrap();
+        let layouts = reloaded.get_layouts("memory", "users");
+        assert_eq!(layouts.len(), 2);
+        assert_eq!(layouts[0].name, "another");
+        assert_eq!(layouts[1], sample_layout("by_a"));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_layout_store_replace_and_delete() {
+        let path = std::env::temp_dir()
+            .join(format!("ducky_layouts_delete_test_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut store = LayoutStore::load_from(&path).unwrap();
+        store.save_layout("memory", "users", sample_layout("main")).unwrap();
+
+        // Saving under the same name replaces rather than duplicates
+        let mut updated = sample_layout("main");
+        updated.hidden_columns.clear();
+        store.save_layout("memory", "users", updated.clone()).unwrap();
+        assert_eq!(store.get_layouts("memory", "users"), &[updated]);
+
+        store.delete_layout("memory", "users", "main").unwrap();
+        assert!(store.get_layouts("memory", "users").is_empty());
+        assert!(store.delete_layout("memory", "users", "main").is_err());
+
+        fs::remove_file(&path).unwrap();
+
+        // A store without a file reports that saving failed instead of dropping the layout silently
+        let mut unsaved = LayoutStore::default();
+        assert!(unsaved.save_layout("memory", "users", sample_layout("main")).is_err());
+    }
+
+    #[test]
+    fn test_layout_store_rename_table() {
+        let path = std::env::temp_dir()
+            .join(format!("ducky_layouts_rename_test_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut store = LayoutStore::load_from(&path).unwrap();
+        store.save_layout("memory", "users", sample_layout("main")).unwrap();
+        store.rename_table("memory", "users", "people").unwrap();
+        // Tables without layouts have nothing to move
+        store.rename_table("memory", "orders", "purchases").unwrap();
+
+        let reloaded = LayoutStore::load_from(&path).unwrap();
+        assert!(reloaded.get_layouts("memory", "users").is_empty());
+        assert_eq!(reloaded.get_layouts("memory", "people"), &[sample_layout("main")]);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,10 +1,11 @@
 use std::io;
 
+pub mod layouts;
 pub mod state;
 
 use std::time::Duration;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -23,7 +24,7 @@ pub fn run() -> io::Result<()> {
 pub fn run_with_database(database_path: Option<PathBuf>) -> io::Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -31,7 +32,7 @@ pub fn run_with_database(database_path: Option<PathBuf>) -> io::Result<()> {
     let result = run_app(&mut terminal, &mut app);
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal.show_cursor()?;
 
     result
@@ -49,16 +50,23 @@ fn run_app<B: ratatui::backend::Backend>(
 
         // Use polling with timeout to allow notifications to auto-expire
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
                     match key.code {
-                        KeyCode::Char('q') => break,
-                        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                        // 'q' is an ordinary character while typing into an input
+                        KeyCode::Char('q') if !app.is_text_input_active() => app.request_quit(),
+                        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.request_quit(),
                         _ => app.handle_key(key),
                     }
                 }
+                Event::Mouse(mouse) => app.handle_mouse(mouse),
+                _ => {}
             }
         }
+
+        if app.should_quit() {
+            break;
+        }
     }
     Ok(())
 }
\ No newline at end of file
@@ -1,5 +1,18 @@
+use std::path::PathBuf;
 
-use crate::db::query::QueryResult;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::alter::ColumnChange;
+use crate::db::definition::DefinitionKind;
+use crate::db::design::ColumnDesign;
+use crate::db::diff::DiffResult;
+use crate::db::edit::RowKey;
+use crate::db::query::{ColumnInfo, QueryResult};
+use crate::db::relations::IncomingReference;
+use crate::db::schema::{quote_identifier, IndexInfo, TableSchema};
+use crate::db::storage::TableStorage;
+use super::layouts::ViewLayout;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AppState {
@@ -11,6 +24,16 @@ pub enum AppState {
     Settings,
 }
 
+// Maximum number of undo steps kept per table
+const MAX_VIEW_HISTORY: usize = 100;
+
+// Seed for samples that must read the same rows each time but were given no seed
+const REPEATABLE_SAMPLE_SEED: u64 = 42;
+
+// Bounds for column widths set by dragging column borders
+const MIN_COLUMN_WIDTH: usize = 3;
+const MAX_COLUMN_WIDTH: usize = 200;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NotificationType {
     Success,
@@ -18,7 +41,7 @@ pub enum NotificationType {
     Info,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SortDirection {
     Ascending,
     Descending,
@@ -30,12 +53,141 @@ impl Default for SortDirection {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SortColumnSpec {
     pub column_name: String,
     pub direction: SortDirection,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleSize {
+    Percent(f64),
+    Rows(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleMethod {
+    Reservoir,
+    Bernoulli,
+    System,
+}
+
+impl SampleMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SampleMethod::Reservoir => "reservoir",
+            SampleMethod::Bernoulli => "bernoulli",
+            SampleMethod::System => "system",
+        }
+    }
+}
+
+/// DuckDB table sample used instead of reading rows in storage order
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleSpec {
+    pub size: SampleSize,
+    pub method: SampleMethod,
+    pub seed: Option<u64>,
+}
+
+impl SampleSpec {
+    /// Parse user input like "10%", "5000 reservoir" or "1% bernoulli 42"
+    pub fn parse(input: &str) -> anyhow::Result<Self> {
+        let mut parts = input.split_whitespace();
+
+        let size_text = parts.next()
+            .ok_or_else(|| anyhow::anyhow!("Sample size is required"))?;
+        let size = if let Some(percent) = size_text.strip_suffix('%') {
+            let value: f64 = percent.parse()
+                .map_err(|_| anyhow::anyhow!("Invalid sample percentage: {size_text}"))?;
+            if !(value > 0.0 && value <= 100.0) {
+                return Err(anyhow::anyhow!("Sample percentage must be between 0 and 100"));
+            }
+            SampleSize::Percent(value)
+        } else {
+            let rows: u64 = size_text.parse()
+                .map_err(|_| anyhow::anyhow!("Invalid sample size: {size_text} (use e.g. 10% or 5000)"))?;
+            if rows == 0 {
+                return Err(anyhow::anyhow!("Sample row count must be greater than 0"));
+            }
+            SampleSize::Rows(rows)
+        };
+
+        // Match DuckDB's defaults when no method is given
+        let mut method = match size {
+            SampleSize::Percent(_) => SampleMethod::System,
+            SampleSize::Rows(_) => SampleMethod::Reservoir,
+        };
+        let mut seed = None;
+
+        for part in parts {
+            match part.to_lowercase().as_str() {
+                "reservoir" => method = SampleMethod::Reservoir,
+                "bernoulli" => method = SampleMethod::Bernoulli,
+                "system" => method = SampleMethod::System,
+                other => {
+                    let value = other.strip_prefix("seed=").unwrap_or(other);
+                    seed = Some(value.parse()
+                        .map_err(|_| anyhow::anyhow!("Unknown sample option: {part}"))?);
+                }
+            }
+        }
+
+        if matches!(size, SampleSize::Rows(_)) && method != SampleMethod::Reservoir {
+            return Err(anyhow::anyhow!("Row count samples require the reservoir method"));
+        }
+
+        Ok(Self { size, method, seed })
+    }
+
+    pub fn get_sql_clause(&self) -> String {
+        let size = match self.size {
+            SampleSize::Percent(percent) => format!("{percent} PERCENT"),
+            SampleSize::Rows(rows) => format!("{rows} ROWS"),
+        };
+        match self.seed {
+            Some(seed) => format!("TABLESAMPLE {size} ({}, {seed})", self.method.as_str()),
+            None => format!("TABLESAMPLE {size} ({})", self.method.as_str()),
+        }
+    }
+
+    /// Like `get_sql_clause`, but always seeded so every run reads the same rows
+    pub fn get_repeatable_sql_clause(&self) -> String {
+        let size = match self.size {
+            SampleSize::Percent(percent) => format!("{percent} PERCENT"),
+            SampleSize::Rows(rows) => format!("{rows} ROWS"),
+        };
+        format!(
+            "TABLESAMPLE {}({size}) REPEATABLE ({})",
+            self.method.as_str(),
+            self.seed.unwrap_or(REPEATABLE_SAMPLE_SEED)
+        )
+    }
+
+    /// Format the sample back into the syntax accepted by `parse`
+    pub fn to_input(&self) -> String {
+        let size = match self.size {
+            SampleSize::Percent(percent) => format!("{percent}%"),
+            SampleSize::Rows(rows) => rows.to_string(),
+        };
+        match self.seed {
+            Some(seed) => format!("{size} {} {seed}", self.method.as_str()),
+            None => format!("{size} {}", self.method.as_str()),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let size = match self.size {
+            SampleSize::Percent(percent) => format!("{percent}%"),
+            SampleSize::Rows(rows) => format!("{rows} rows"),
+        };
+        match self.seed {
+            Some(seed) => format!("{size} {} seed={seed}", self.method.as_str()),
+            None => format!("{size} {}", self.method.as_str()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Notification {
     pub message: String,
@@ -103,6 +255,16 @@ pub enum InspectSection {
     Statistics,
 }
 
+/// What the inspect view shows for a table, loaded once instead of on every frame
+#[derive(Debug, Clone)]
+pub struct InspectData {
+    pub table_name: String,
+    pub columns: QueryResult, // DESCRIBE output
+    pub table_schema: Option<TableSchema>, // None for query results
+    pub statistics: QueryResult, // SUMMARIZE output, over the sample when sampling
+    pub storage_info: Option<String>, // Row estimate, size and compression of base tables
+}
+
 impl Default for InspectSection {
     fn default() -> Self {
         Self::Schema
@@ -120,6 +282,9 @@ pub struct ApplicationState {
     pub is_creating_table: bool,
     pub new_table_name: String,
     pub table_creation_step: TableCreationStep,
+    pub table_design: Vec<ColumnDesign>,
+    pub table_design_selected: usize,
+    pub column_editor: Option<ColumnEditor>,
     // Notification system
     pub notifications: Vec<Notification>,
     // Table data display state
@@ -167,6 +332,7 @@ pub struct ApplicationState {
     pub inspect_schema_scroll_y: usize,
     pub inspect_stats_scroll_y: usize,
     pub inspect_selected_row: usize, // Selected row in the columns view
+    pub inspect_data: Option<InspectData>, // None until loaded, and again after schema changes
     // Column ordering state
     pub column_order: std::collections::HashMap<String, Vec<String>>, // table_name -> ordered_column_names
     pub original_column_names: Vec<String>, // cached for current table
@@ -175,14 +341,290 @@ pub struct ApplicationState {
     pub modify_backup_column_order: Option<Vec<String>>, // backup for cancel operation
     // Column hiding state
     pub hidden_columns: std::collections::HashMap<String, std::collections::HashSet<String>>, // table_name -> hidden_column_names
+    // Named layout picker state
+    pub is_picking_layout: bool,
+    pub layout_picker_index: usize,
+    pub is_entering_layout_name: bool,
+    pub new_layout_name: String,
+    // Undo/redo history of view-state snapshots
+    pub view_undo_stack: std::collections::HashMap<String, Vec<ViewLayout>>, // table_name -> snapshots
+    pub view_redo_stack: std::collections::HashMap<String, Vec<ViewLayout>>, // table_name -> snapshots
+    // Sampling state
+    pub sample_specs: std::collections::HashMap<String, SampleSpec>, // table_name -> active sample
+    pub is_entering_sample_spec: bool,
+    pub sample_spec_input: String,
+    // Column width overrides set by dragging column borders
+    pub column_widths: std::collections::HashMap<String, std::collections::HashMap<String, usize>>, // table_name -> column_name -> width
+    // Open tabs in the main content area (the active tab lives in the fields above)
+    pub tabs: Vec<TableTab>,
+    pub active_tab: usize,
+    // Ad-hoc query results, shown in the viewer like tables
+    pub query_sources: std::collections::HashMap<String, String>, // result name -> SQL
+    pub next_query_number: usize,
+    pub is_entering_query: bool,
+    pub query_input: String,
+    // Split view: another tab shown next to the active one
+    pub split_tab: Option<usize>,
+    pub split_sync: SplitSyncMode,
+    pub split_key_missing: bool,
+    // Row-level diffs, shown as query results
+    pub diff_results: std::collections::HashMap<String, DiffResult>, // result name -> diff
+    pub is_entering_compare: bool,
+    pub compare_input: String,
+    // Export of the current view to a file
+    pub is_entering_export_path: bool,
+    pub export_path: String,
+    // Keys of the loaded rows, used to address them for edits (None when not editable)
+    pub row_key: Option<RowKey>,
+    pub row_key_values: Vec<Vec<String>>,
+    // Inline cell editing
+    pub is_editing_cell: bool,
+    pub cell_edit_input: String,
+    // Insert-row form and visual row selection
+    pub row_form: Option<RowForm>,
+    pub visual_row_anchor: Option<usize>,
+    // Transaction mode: viewer edits are staged until committed or rolled back
+    pub transaction: Option<PendingTransaction>,
+    pub show_pending_changes: bool,
+    pub is_confirming_quit: bool,
+    // Bulk update/delete of the rows matching the current filters
+    pub is_entering_bulk_update: bool,
+    pub bulk_update_input: String,
+    pub pending_bulk_change: Option<BulkChange>,
+    // Column changes from the inspect view's schema section
+    pub is_entering_column_change: bool,
+    pub column_change_input: String,
+    pub pending_column_change: Option<PendingColumnChange>,
+    // Rename/duplicate of the table selected in the sidebar
+    pub table_operation: Option<TableOperation>,
+    pub table_operation_input: String,
+    // Name of a schema being created from the sidebar
+    pub is_entering_schema_name: bool,
+    pub schema_name_input: String,
+    // Alias and mode of a database file being attached
+    pub pending_attach: Option<PendingAttach>,
+    // Index list and create/drop forms for one table
+    pub index_manager: Option<IndexManager>,
+    // Views left by following foreign keys, most recent last
+    pub relation_trail: Vec<RelationStep>,
+    pub reverse_references: Option<ReverseReferences>,
+    // Per-column storage segments of one table
+    pub storage_breakdown: Option<StorageBreakdown>,
+    // CREATE statement of the selected catalog object
+    pub definition_view: Option<DefinitionView>,
+    // Two databases to compare schemas of, and the resulting migration script
+    pub is_entering_schema_diff: bool,
+    pub schema_diff_input: String,
+    pub schema_diff: Option<SchemaDiffView>,
+}
+
+/// A view left by following a relationship, restored by going back
+#[derive(Debug, Clone, Default)]
+pub struct RelationStep {
+    pub label: String,
+    pub view: TableTab,
+}
+
+/// Storage layout of a table, scrolled by column
+#[derive(Debug, Clone, Default)]
+pub struct StorageBreakdown {
+    pub table_name: String,
+    pub estimated_rows: Option<i64>,
+    pub storage: TableStorage,
+    pub scroll: usize,
+}
+
+/// CREATE statement of a table, view, macro or sequence, with the file it is being saved to
+#[derive(Debug, Clone)]
+pub struct DefinitionView {
+    pub object_name: String,
+    pub kind: DefinitionKind,
+    pub sql: String,
+    pub scroll: usize,
+    pub save_path: Option<String>,
+}
+
+/// Differences between two schemas and the script migrating the source to the target
+#[derive(Debug, Clone)]
+pub struct SchemaDiffView {
+    pub source: String,
+    pub target: String,
+    pub change_count: usize,
+    pub script: String,
+    pub scroll: usize,
+    pub save_path: Option<String>,
+}
+
+/// Tables with rows referencing the selected row, offered to follow
+#[derive(Debug, Clone, Default)]
+pub struct ReverseReferences {
+    pub label: String,
+    pub entries: Vec<ReverseReference>,
+    pub selected: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReverseReference {
+    pub reference: IncomingReference,
+    pub filters: std::collections::HashMap<String, String>,
+    pub row_count: usize,
+}
+
+/// Indexes of a table, with the create form or drop confirmation when open
+#[derive(Debug, Clone, Default)]
+pub struct IndexManager {
+    pub table_name: String,
+    pub indexes: Vec<IndexInfo>,
+    pub selected: usize,
+    // How the viewer's current filters use the indexes
+    pub hints: Vec<String>,
+    pub form: Option<IndexForm>,
+    pub confirming_drop: bool,
+}
+
+/// Create-index form: name, comma-separated columns and uniqueness
+#[derive(Debug, Clone, Default)]
+pub struct IndexForm {
+    pub name: String,
+    pub columns: String,
+    pub unique: bool,
+    pub field: usize,
+}
+
+/// A database file about to be ATTACHed into the current database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingAttach {
+    pub path: PathBuf,
+    pub alias: String,
+    pub read_only: bool,
+}
+
+/// A sidebar operation on a table or view that takes a typed value: a new name, or a comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableOperation {
+    pub table_name: String,
+    pub is_view: bool,
+    pub kind: TableOperationKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableOperationKind {
+    Rename,
+    Duplicate { with_data: bool },
+    Comment,
+}
+
+/// A column change that needs confirmation, with what its dry run found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingColumnChange {
+    pub table_name: String,
+    pub column_name: String,
+    pub change: ColumnChange,
+    pub report: String,
+}
+
+/// A bulk change waiting for confirmation, with the number of rows it will touch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkChange {
+    pub table_name: String,
+    pub kind: BulkChangeKind,
+    pub filter_clause: String,
+    pub row_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkChangeKind {
+    Update { column_name: String, expression: String },
+    Delete,
+}
+
+/// Edits made inside an open transaction, waiting for commit or rollback
+#[derive(Debug, Clone, Default)]
+pub struct PendingTransaction {
+    pub database: String,
+    // SQL of every staged change, with values inlined for display
+    pub statements: Vec<String>,
+    pub modified_cells: std::collections::HashSet<(String, Vec<String>, String)>, // table, row key, column
+    pub inserted_rows: std::collections::HashSet<(String, Vec<String>)>, // table, row key
+    // Tables renamed in the transaction as (old, new), undone in the view state on rollback
+    pub renamed_tables: Vec<(String, String)>,
+    // DuckDB aborts a transaction on the first failing statement; it can then only be rolled back
+    pub aborted: bool,
+}
+
+impl PendingTransaction {
+    pub fn new(database: String) -> Self {
+        Self {
+            database,
+            ..Self::default()
+        }
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.statements.is_empty() || self.aborted
+    }
+}
+
+/// Values typed into the insert-row form, one per table column
+#[derive(Debug, Clone, Default)]
+pub struct RowForm {
+    pub table_name: String,
+    pub columns: Vec<ColumnInfo>,
+    // Empty values are left to the column default
+    pub values: Vec<String>,
+    pub selected: usize,
+}
+
+/// How the split partner follows the active grid
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SplitSyncMode {
+    #[default]
+    Off,
+    RowPosition,
+    KeyColumn(String),
+}
+
+/// Viewer state of a tab that is not currently active
+#[derive(Debug, Clone, Default)]
+pub struct TableTab {
+    pub database: Option<String>,
+    pub table: Option<String>,
+    pub table_data: Option<QueryResult>,
+    pub scroll_x: usize,
+    pub scroll_y: usize,
+    pub selected_row: usize,
+    pub selected_column: Option<String>,
+    pub expanded_columns: std::collections::HashSet<usize>,
+    pub sort_columns: Vec<SortColumnSpec>,
+    pub column_filters: std::collections::HashMap<String, String>,
+    pub original_column_names: Vec<String>,
+    pub row_key: Option<RowKey>,
+    pub row_key_values: Vec<Vec<String>>,
+    pub visual_row_anchor: Option<usize>,
+    pub relation_trail: Vec<RelationStep>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum TableCreationStep {
     #[default]
     EnteringTableName,
+    ChoosingSource,
     SelectingFile,
     ImportingData,
+    DesigningColumns,
+    PreviewingDdl,
+}
+
+/// Fields of the designer's column editor, in display order
+pub const COLUMN_EDITOR_FIELDS: &[&str] = &["Name", "Type", "Type argument", "Not null", "Default", "Primary key", "Unique"];
+
+/// A column being added or edited in the table designer
+#[derive(Debug, Clone, Default)]
+pub struct ColumnEditor {
+    pub column: ColumnDesign,
+    // None when adding a new column
+    pub index: Option<usize>,
+    pub field: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -190,6 +632,9 @@ pub enum DeleteConfirmationState {
     None,
     Database(String), // Database name to delete
     Table(String),    // Table name to delete
+    Rows(String, Vec<usize>), // Table name and indices of the loaded rows to delete
+    Truncate(String), // Table name to delete all rows from
+    Schema(String),   // Schema name to drop with its tables
 }
 
 impl Default for DeleteConfirmationState {
@@ -209,6 +654,9 @@ impl ApplicationState {
             is_creating_table: false,
             new_table_name: String::new(),
             table_creation_step: TableCreationStep::default(),
+            table_design: Vec::new(),
+            table_design_selected: 0,
+            column_editor: None,
             notifications: Vec::new(),
             table_data: None,
             scroll_x: 0,
@@ -242,11 +690,64 @@ impl ApplicationState {
             inspect_schema_scroll_y: 0,
             inspect_stats_scroll_y: 0,
             inspect_selected_row: 0,
+            inspect_data: None,
             column_order: std::collections::HashMap::new(),
             original_column_names: Vec::new(),
             is_modifying: false,
             modify_backup_column_order: None,
             hidden_columns: std::collections::HashMap::new(),
+            is_picking_layout: false,
+            layout_picker_index: 0,
+            is_entering_layout_name: false,
+            new_layout_name: String::new(),
+            view_undo_stack: std::collections::HashMap::new(),
+            view_redo_stack: std::collections::HashMap::new(),
+            sample_specs: std::collections::HashMap::new(),
+            is_entering_sample_spec: false,
+            sample_spec_input: String::new(),
+            column_widths: std::collections::HashMap::new(),
+            tabs: Vec::new(),
+            active_tab: 0,
+            query_sources: std::collections::HashMap::new(),
+            next_query_number: 1,
+            is_entering_query: false,
+            query_input: String::new(),
+            split_tab: None,
+            split_sync: SplitSyncMode::Off,
+            split_key_missing: false,
+            diff_results: std::collections::HashMap::new(),
+            is_entering_compare: false,
+            compare_input: String::new(),
+            is_entering_export_path: false,
+            export_path: String::new(),
+            row_key: None,
+            row_key_values: Vec::new(),
+            is_editing_cell: false,
+            cell_edit_input: String::new(),
+            row_form: None,
+            visual_row_anchor: None,
+            transaction: None,
+            show_pending_changes: false,
+            is_confirming_quit: false,
+            is_entering_bulk_update: false,
+            bulk_update_input: String::new(),
+            pending_bulk_change: None,
+            is_entering_column_change: false,
+            column_change_input: String::new(),
+            pending_column_change: None,
+            table_operation: None,
+            table_operation_input: String::new(),
+            is_entering_schema_name: false,
+            schema_name_input: String::new(),
+            pending_attach: None,
+            index_manager: None,
+            relation_trail: Vec::new(),
+            reverse_references: None,
+            storage_breakdown: None,
+            definition_view: None,
+            is_entering_schema_diff: false,
+            schema_diff_input: String::new(),
+            schema_diff: None,
         }
     }
 
@@ -267,12 +768,17 @@ impl ApplicationState {
         
         if self.is_creating_table {
             match self.table_creation_step {
-                TableCreationStep::EnteringTableName => parts.push("Creating table...".to_string()),
+                TableCreationStep::EnteringTableName | TableCreationStep::ChoosingSource => parts.push("Creating table...".to_string()),
+                TableCreationStep::DesigningColumns | TableCreationStep::PreviewingDdl => parts.push("Designing table...".to_string()),
                 TableCreationStep::SelectingFile => parts.push("Selecting file...".to_string()),
                 TableCreationStep::ImportingData => parts.push("Importing data...".to_string()),
             }
         }
         
+        if let Some(sample) = self.get_sample_spec() {
+            parts.push(format!("SAMPLED: {}", sample.describe()));
+        }
+        
         if self.is_modifying {
             if self.inspect_mode {
                 parts.push("MODIFY MODE: j/k to move, J/K for extremes, o to hide/show, Enter to confirm, Esc to cancel".to_string());
@@ -327,6 +833,8 @@ impl ApplicationState {
         self.clear_sort();
         // Reset original column order cache when switching tables
         self.original_column_names.clear();
+        // Picking a table directly starts a new relationship trail
+        self.relation_trail.clear();
         // Note: We don't clear hidden columns when switching tables - they persist per table
     }
 
@@ -397,17 +905,130 @@ impl ApplicationState {
         self.current_state = AppState::DatabaseBrowser;
         self.new_table_name.clear();
         self.table_creation_step = TableCreationStep::EnteringTableName;
+        self.table_design.clear();
+        self.column_editor = None;
         self.set_status("Ready".to_string());
         self.show_info("Table creation cancelled".to_string());
     }
 
     pub fn confirm_table_name(&mut self) {
         if !self.new_table_name.trim().is_empty() {
-            self.table_creation_step = TableCreationStep::SelectingFile;
+            self.table_creation_step = TableCreationStep::ChoosingSource;
             self.set_status("Ready".to_string());
         }
     }
 
+    pub fn choose_file_source(&mut self) {
+        self.table_creation_step = TableCreationStep::SelectingFile;
+    }
+
+    pub fn start_table_design(&mut self) {
+        self.table_creation_step = TableCreationStep::DesigningColumns;
+        self.table_design.clear();
+        self.table_design_selected = 0;
+        self.start_column_editor(None);
+    }
+
+    // Table designer methods
+    pub fn start_column_editor(&mut self, index: Option<usize>) {
+        let column = index
+            .and_then(|index| self.table_design.get(index).cloned())
+            .unwrap_or_default();
+        self.column_editor = Some(ColumnEditor { column, index, field: 0 });
+    }
+
+    pub fn cancel_column_editor(&mut self) {
+        self.column_editor = None;
+    }
+
+    /// Store the edited column, returning false if it has no name yet
+    pub fn save_column_editor(&mut self) -> bool {
+        let Some(editor) = self.column_editor.take() else {
+            return false;
+        };
+        if editor.column.name.trim().is_empty() {
+            self.column_editor = Some(editor);
+            return false;
+        }
+        match editor.index {
+            Some(index) if index < self.table_design.len() => {
+                self.table_design[index] = editor.column;
+                self.table_design_selected = index;
+            }
+            _ => {
+                self.table_design.push(editor.column);
+                self.table_design_selected = self.table_design.len() - 1;
+            }
+        }
+        true
+    }
+
+    pub fn column_editor_next_field(&mut self) {
+        if let Some(editor) = &mut self.column_editor {
+            editor.field = (editor.field + 1) % COLUMN_EDITOR_FIELDS.len();
+        }
+    }
+
+    pub fn column_editor_prev_field(&mut self) {
+        if let Some(editor) = &mut self.column_editor {
+            editor.field = (editor.field + COLUMN_EDITOR_FIELDS.len() - 1) % COLUMN_EDITOR_FIELDS.len();
+        }
+    }
+
+    /// Left/right on the type field picks the type; space toggles the flags
+    pub fn column_editor_cycle(&mut self, forward: bool) {
+        if let Some(editor) = &mut self.column_editor {
+            match editor.field {
+                1 => editor.column.cycle_type(forward),
+                3 => editor.column.not_null = !editor.column.not_null,
+                5 => editor.column.primary_key = !editor.column.primary_key,
+                6 => editor.column.unique = !editor.column.unique,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn add_char_to_column_editor(&mut self, c: char) {
+        let Some(editor) = &mut self.column_editor else {
+            return;
+        };
+        match editor.field {
+            0 => editor.column.name.push(c),
+            2 => editor.column.type_argument.push(c),
+            4 => editor.column.default.push(c),
+            _ if c == ' ' => self.column_editor_cycle(true),
+            _ => {}
+        }
+    }
+
+    pub fn remove_char_from_column_editor(&mut self) {
+        if let Some(editor) = &mut self.column_editor {
+            match editor.field {
+                0 => { editor.column.name.pop(); }
+                2 => { editor.column.type_argument.pop(); }
+                4 => { editor.column.default.pop(); }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn remove_designed_column(&mut self) {
+        if self.table_design_selected < self.table_design.len() {
+            self.table_design.remove(self.table_design_selected);
+            self.table_design_selected = self.table_design_selected.min(self.table_design.len().saturating_sub(1));
+        }
+    }
+
+    pub fn move_design_selection(&mut self, down: bool) {
+        if down {
+            if self.table_design_selected + 1 < self.table_design.len() {
+                self.table_design_selected += 1;
+            }
+        } else {
+            self.table_design_selected = self.table_design_selected.saturating_sub(1);
+        }
+    }
+
     pub fn add_char_to_table_name(&mut self, c: char) {
         if self.is_creating_table && self.table_creation_step == TableCreationStep::EnteringTableName {
             self.new_table_name.push(c);
@@ -441,11 +1062,14 @@ impl ApplicationState {
         self.current_state = AppState::DatabaseBrowser;
         self.new_table_name.clear();
         self.table_creation_step = TableCreationStep::EnteringTableName;
+        self.table_design.clear();
+        self.column_editor = None;
         self.set_status("Ready".to_string());
     }
 
     pub fn set_table_data(&mut self, data: QueryResult) {
         self.table_data = Some(data);
+        self.visual_row_anchor = None;
         self.scroll_x = 0;
         self.scroll_y = 0;
         self.selected_row = 0;
@@ -457,6 +1081,7 @@ impl ApplicationState {
         let saved_scroll_x = self.scroll_x;
         
         self.table_data = Some(data);
+        self.visual_row_anchor = None;
         self.scroll_y = 0;  // Reset vertical scroll to show sorted results from top
         self.selected_row = 0;  // Reset to first row of sorted data
         
@@ -474,184 +1099,1084 @@ impl ApplicationState {
                 self.scroll_x = selected_idx;
             }
         }
-        // Note: We can't easily calculate right-edge visibility here without knowing
-        // the viewport width, but the existing logic should handle that
-    }
+        // Note: We can't easily calculate right-edge visibility here without knowing
+        // the viewport width, but the existing logic should handle that
+    }
+
+    pub fn scroll_table_left(&mut self) {
+        if self.scroll_x > 0 {
+            self.scroll_x -= 1;
+        }
+    }
+
+    pub fn scroll_table_right(&mut self, max_columns: usize, visible_columns: usize) {
+        if self.scroll_x + visible_columns < max_columns {
+            self.scroll_x += 1;
+        }
+    }
+
+    pub fn scroll_table_up(&mut self) {
+        if self.scroll_y > 0 {
+            self.scroll_y -= 1;
+        }
+    }
+
+    pub fn scroll_table_down(&mut self, max_rows: usize, visible_rows: usize) {
+        if self.scroll_y + visible_rows < max_rows {
+            self.scroll_y += 1;
+        }
+    }
+
+    pub fn page_table_up(&mut self) {
+        self.scroll_y = self.scroll_y.saturating_sub(self.page_size);
+    }
+
+    pub fn page_table_down(&mut self, max_rows: usize, visible_rows: usize) {
+        let new_scroll = self.scroll_y + self.page_size;
+        if new_scroll + visible_rows <= max_rows {
+            self.scroll_y = new_scroll;
+        } else if max_rows > visible_rows {
+            self.scroll_y = max_rows - visible_rows;
+        }
+    }
+
+    // Database dropdown methods
+    pub fn expand_database_dropdown(&mut self, num_databases: usize) {
+        self.database_dropdown_expanded = true;
+        // Ensure dropdown_selected_index is within bounds
+        if self.dropdown_selected_index >= num_databases {
+            self.dropdown_selected_index = 0;
+        }
+    }
+
+    pub fn collapse_database_dropdown(&mut self) {
+        self.database_dropdown_expanded = false;
+    }
+
+    pub fn dropdown_move_up(&mut self) {
+        if self.dropdown_selected_index > 0 {
+            self.dropdown_selected_index -= 1;
+        }
+    }
+
+    pub fn dropdown_move_down(&mut self, num_databases: usize) {
+        if self.dropdown_selected_index < num_databases.saturating_sub(1) {
+            self.dropdown_selected_index += 1;
+        }
+    }
+
+    pub fn set_dropdown_to_current_database(&mut self, current_db_index: usize) {
+        self.dropdown_selected_index = current_db_index;
+    }
+
+    // Left panel navigation helper
+    pub fn set_left_panel(&mut self, panel: NavigationPanel) {
+        // Only track changes to actual left panel widgets
+        if matches!(panel, NavigationPanel::DatabaseList | NavigationPanel::TableList) {
+            self.last_left_panel = panel.clone();
+        }
+        
+        // Trigger flash effect if panel is changing
+        if self.active_panel != panel {
+            self.start_panel_flash();
+        }
+        
+        self.active_panel = panel;
+    }
+
+    // Flash effect methods
+    pub fn start_panel_flash(&mut self) {
+        self.panel_flash_timer = Some(std::time::Instant::now());
+    }
+
+    pub fn is_panel_flashing(&self) -> bool {
+        if let Some(start_time) = self.panel_flash_timer {
+            start_time.elapsed().as_millis() < self.flash_duration_ms as u128
+        } else {
+            false
+        }
+    }
+
+    pub fn update_flash_timer(&mut self) {
+        if let Some(start_time) = self.panel_flash_timer {
+            if start_time.elapsed().as_millis() >= self.flash_duration_ms as u128 {
+                self.panel_flash_timer = None;
+            }
+        }
+    }
+
+    // Delete confirmation methods
+    pub fn start_database_delete_confirmation(&mut self, database_name: String) {
+        self.delete_confirmation = DeleteConfirmationState::Database(database_name);
+    }
+
+    pub fn start_table_delete_confirmation(&mut self, table_name: String) {
+        self.delete_confirmation = DeleteConfirmationState::Table(table_name);
+    }
+
+    pub fn start_truncate_confirmation(&mut self, table_name: String) {
+        self.delete_confirmation = DeleteConfirmationState::Truncate(table_name);
+    }
+
+    pub fn start_schema_drop_confirmation(&mut self, schema_name: String) {
+        self.delete_confirmation = DeleteConfirmationState::Schema(schema_name);
+    }
+
+    pub fn start_rows_delete_confirmation(&mut self, table_name: String, rows: Vec<usize>) {
+        self.delete_confirmation = DeleteConfirmationState::Rows(table_name, rows);
+    }
+
+    pub fn cancel_delete_confirmation(&mut self) {
+        self.delete_confirmation = DeleteConfirmationState::None;
+    }
+
+    pub fn is_delete_confirmation_active(&self) -> bool {
+        !matches!(self.delete_confirmation, DeleteConfirmationState::None)
+    }
+
+    // Database name input methods
+    pub fn start_database_name_input(&mut self) {
+        self.is_entering_database_name = true;
+        self.new_database_name.clear();
+    }
+
+    pub fn cancel_database_name_input(&mut self) {
+        self.is_entering_database_name = false;
+        self.new_database_name.clear();
+    }
+
+    pub fn add_char_to_database_name(&mut self, c: char) {
+        self.new_database_name.push(c);
+    }
+
+    pub fn remove_char_from_database_name(&mut self) {
+        self.new_database_name.pop();
+    }
+
+    // Save filename input methods
+    pub fn start_save_filename_input(&mut self) {
+        self.is_entering_save_filename = true;
+        self.save_filename.clear();
+    }
+
+    pub fn cancel_save_filename_input(&mut self) {
+        self.is_entering_save_filename = false;
+        self.save_filename.clear();
+    }
+
+    pub fn add_char_to_save_filename(&mut self, c: char) {
+        self.save_filename.push(c);
+    }
+
+    pub fn remove_char_from_save_filename(&mut self) {
+        self.save_filename.pop();
+    }
+
+    // View name input methods
+    pub fn start_view_name_input(&mut self) {
+        self.is_entering_view_name = true;
+        self.new_view_name.clear();
+    }
+    
+    pub fn cancel_view_name_input(&mut self) {
+        self.is_entering_view_name = false;
+        self.new_view_name.clear();
+    }
+    
+    pub fn add_char_to_view_name(&mut self, c: char) {
+        self.new_view_name.push(c);
+    }
+    
+    pub fn remove_char_from_view_name(&mut self) {
+        self.new_view_name.pop();
+    }
+
+    // Compare input methods
+    pub fn start_compare_input(&mut self) {
+        self.is_entering_compare = true;
+        // Seed with the tables in the split view (or the current one) and the selected column as key
+        let left = self.selected_table.clone().unwrap_or_default();
+        let right = self.split_tab
+            .and_then(|index| self.tabs.get(index))
+            .and_then(|tab| tab.table.clone())
+            .unwrap_or_default();
+        let key = self.selected_column.clone().unwrap_or_default();
+        self.compare_input = [left, right, key].iter()
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    pub fn cancel_compare_input(&mut self) {
+        self.is_entering_compare = false;
+        self.compare_input.clear();
+    }
+
+    pub fn add_char_to_compare(&mut self, c: char) {
+        self.compare_input.push(c);
+    }
+
+    pub fn remove_char_from_compare(&mut self) {
+        self.compare_input.pop();
+    }
+
+    // Export path input methods
+    pub fn start_export_path_input(&mut self) {
+        self.is_entering_export_path = true;
+        self.export_path = self.selected_table.as_ref()
+            .map(|table| format!("{table}.csv"))
+            .unwrap_or_default();
+    }
+
+    pub fn cancel_export_path_input(&mut self) {
+        self.is_entering_export_path = false;
+        self.export_path.clear();
+    }
+
+    pub fn add_char_to_export_path(&mut self, c: char) {
+        self.export_path.push(c);
+    }
+
+    pub fn remove_char_from_export_path(&mut self) {
+        self.export_path.pop();
+    }
+
+    // Cell editing methods
+    /// Open the cell edit prompt seeded with the selected cell's value
+    pub fn start_cell_edit(&mut self) -> bool {
+        let Some(value) = self.get_selected_cell_value() else {
+            return false;
+        };
+        let is_null = self.get_selected_column_index()
+            .zip(self.table_data.as_ref())
+            .is_some_and(|(column, data)| data.is_null(self.selected_row, column));
+        self.is_editing_cell = true;
+        // NULLs are displayed as "Null"; the prompt uses the SQL spelling
+        self.cell_edit_input = if is_null { "NULL".to_string() } else { value };
+        true
+    }
+
+    pub fn cancel_cell_edit(&mut self) {
+        self.is_editing_cell = false;
+        self.cell_edit_input.clear();
+    }
+
+    pub fn add_char_to_cell_edit(&mut self, c: char) {
+        self.cell_edit_input.push(c);
+    }
+
+    pub fn remove_char_from_cell_edit(&mut self) {
+        self.cell_edit_input.pop();
+    }
+
+    /// Open the insert-row form, optionally seeded with values by column name
+    pub fn start_row_form(&mut self, table_name: String, columns: Vec<ColumnInfo>, seed: Option<std::collections::HashMap<String, Option<String>>>) {
+        let values = columns.iter()
+            .map(|column| {
+                seed.as_ref()
+                    .and_then(|seed| seed.get(&column.name))
+                    .map(|value| value.clone().unwrap_or_else(|| "NULL".to_string()))
+                    .unwrap_or_default()
+            })
+            .collect();
+        self.row_form = Some(RowForm {
+            table_name,
+            columns,
+            values,
+            selected: 0,
+        });
+    }
+
+    pub fn cancel_row_form(&mut self) {
+        self.row_form = None;
+    }
+
+    pub fn row_form_next_field(&mut self) {
+        if let Some(form) = self.row_form.as_mut() {
+            if form.selected + 1 < form.columns.len() {
+                form.selected += 1;
+            }
+        }
+    }
+
+    pub fn row_form_prev_field(&mut self) {
+        if let Some(form) = self.row_form.as_mut() {
+            form.selected = form.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn add_char_to_row_form(&mut self, c: char) {
+        if let Some(form) = self.row_form.as_mut() {
+            if let Some(value) = form.values.get_mut(form.selected) {
+                value.push(c);
+            }
+        }
+    }
+
+    pub fn remove_char_from_row_form(&mut self) {
+        if let Some(form) = self.row_form.as_mut() {
+            if let Some(value) = form.values.get_mut(form.selected) {
+                value.pop();
+            }
+        }
+    }
+
+    /// Values of the selected row keyed by column name (None for NULL), used to duplicate it
+    pub fn get_selected_row_values(&self) -> Option<std::collections::HashMap<String, Option<String>>> {
+        let data = self.table_data.as_ref()?;
+        let row = data.rows.get(self.selected_row)?;
+        Some(data.columns.iter().cloned()
+            .zip(row.iter().enumerate().map(|(column, value)| (!data.is_null(self.selected_row, column)).then(|| value.clone())))
+            .collect())
+    }
+
+    /// Start or stop selecting a range of rows from the current one
+    pub fn toggle_visual_row_selection(&mut self) {
+        self.visual_row_anchor = match self.visual_row_anchor {
+            Some(_) => None,
+            None => Some(self.selected_row),
+        };
+    }
+
+    /// Rows covered by the visual selection, or just the selected row
+    pub fn get_selected_row_indices(&self) -> Vec<usize> {
+        let Some(data) = self.table_data.as_ref() else {
+            return Vec::new();
+        };
+        let (start, end) = match self.visual_row_anchor {
+            Some(anchor) => (anchor.min(self.selected_row), anchor.max(self.selected_row)),
+            None => (self.selected_row, self.selected_row),
+        };
+        (start..=end).filter(|&row| row < data.rows.len()).collect()
+    }
+
+    pub fn is_row_visually_selected(&self, row: usize) -> bool {
+        self.visual_row_anchor.is_some_and(|anchor| {
+            row >= anchor.min(self.selected_row) && row <= anchor.max(self.selected_row)
+        })
+    }
+
+    pub fn start_bulk_update_input(&mut self) {
+        self.is_entering_bulk_update = true;
+        self.bulk_update_input.clear();
+    }
+
+    pub fn cancel_bulk_update_input(&mut self) {
+        self.is_entering_bulk_update = false;
+        self.bulk_update_input.clear();
+    }
+
+    pub fn add_char_to_bulk_update(&mut self, c: char) {
+        self.bulk_update_input.push(c);
+    }
+
+    pub fn remove_char_from_bulk_update(&mut self) {
+        self.bulk_update_input.pop();
+    }
+
+    pub fn start_column_change_input(&mut self) {
+        self.is_entering_column_change = true;
+        self.column_change_input.clear();
+    }
+
+    pub fn cancel_column_change_input(&mut self) {
+        self.is_entering_column_change = false;
+        self.column_change_input.clear();
+    }
+
+    pub fn add_char_to_column_change(&mut self, c: char) {
+        self.column_change_input.push(c);
+    }
+
+    pub fn remove_char_from_column_change(&mut self) {
+        self.column_change_input.pop();
+    }
+
+    pub fn start_table_operation(&mut self, table_name: String, is_view: bool, kind: TableOperationKind) {
+        self.table_operation_input = match kind {
+            TableOperationKind::Rename => table_name.clone(),
+            TableOperationKind::Duplicate { .. } => format!("{table_name}_copy"),
+            // Filled with the current comment by the caller
+            TableOperationKind::Comment => String::new(),
+        };
+        self.table_operation = Some(TableOperation { table_name, is_view, kind });
+    }
+
+    pub fn cancel_table_operation(&mut self) {
+        self.table_operation = None;
+        self.table_operation_input.clear();
+    }
+
+    pub fn add_char_to_table_operation(&mut self, c: char) {
+        self.table_operation_input.push(c);
+    }
+
+    pub fn remove_char_from_table_operation(&mut self) {
+        self.table_operation_input.pop();
+    }
+
+    pub fn start_schema_name_input(&mut self) {
+        self.is_entering_schema_name = true;
+        self.schema_name_input.clear();
+    }
+
+    pub fn cancel_schema_name_input(&mut self) {
+        self.is_entering_schema_name = false;
+        self.schema_name_input.clear();
+    }
+
+    pub fn add_char_to_schema_name(&mut self, c: char) {
+        self.schema_name_input.push(c);
+    }
+
+    pub fn remove_char_from_schema_name(&mut self) {
+        self.schema_name_input.pop();
+    }
+
+    /// Ask for the alias of a database file to attach, suggesting its file stem; read-only by default
+    pub fn start_attach(&mut self, path: PathBuf) {
+        let alias: String = path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.pending_attach = Some(PendingAttach { path, alias, read_only: true });
+    }
+
+    pub fn cancel_attach(&mut self) {
+        self.pending_attach = None;
+    }
+
+    pub fn add_char_to_attach_alias(&mut self, c: char) {
+        if let Some(attach) = &mut self.pending_attach {
+            attach.alias.push(c);
+        }
+    }
+
+    pub fn remove_char_from_attach_alias(&mut self) {
+        if let Some(attach) = &mut self.pending_attach {
+            attach.alias.pop();
+        }
+    }
+
+    pub fn toggle_attach_read_only(&mut self) {
+        if let Some(attach) = &mut self.pending_attach {
+            attach.read_only = !attach.read_only;
+        }
+    }
+
+    pub fn open_index_manager(&mut self, table_name: String, indexes: Vec<IndexInfo>, hints: Vec<String>) {
+        // Keep the selection when reopening after a create or drop
+        let selected = self.index_manager.as_ref()
+            .filter(|manager| manager.table_name == table_name)
+            .map(|manager| manager.selected.min(indexes.len().saturating_sub(1)))
+            .unwrap_or(0);
+        self.index_manager = Some(IndexManager {
+            table_name,
+            indexes,
+            selected,
+            hints,
+            form: None,
+            confirming_drop: false,
+        });
+    }
+
+    pub fn close_index_manager(&mut self) {
+        self.index_manager = None;
+    }
+
+    pub fn move_index_selection(&mut self, down: bool) {
+        if let Some(manager) = &mut self.index_manager {
+            if down {
+                if manager.selected + 1 < manager.indexes.len() {
+                    manager.selected += 1;
+                }
+            } else {
+                manager.selected = manager.selected.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn get_selected_index(&self) -> Option<&IndexInfo> {
+        self.index_manager.as_ref().and_then(|manager| manager.indexes.get(manager.selected))
+    }
+
+    pub fn start_index_form(&mut self) {
+        if let Some(manager) = &mut self.index_manager {
+            // Seed the columns with the selected viewer column of the same table
+            let columns = self.selected_column.clone()
+                .filter(|_| self.selected_table.as_deref() == Some(manager.table_name.as_str()))
+                .unwrap_or_default();
+            let name = if columns.is_empty() {
+                format!("{}_idx", manager.table_name)
+            } else {
+                format!("{}_{}_idx", manager.table_name, columns)
+            };
+            manager.form = Some(IndexForm { name, columns, unique: false, field: 0 });
+        }
+    }
+
+    pub fn cancel_index_form(&mut self) {
+        if let Some(manager) = &mut self.index_manager {
+            manager.form = None;
+        }
+    }
+
+    pub fn index_form_next_field(&mut self) {
+        if let Some(form) = self.index_manager.as_mut().and_then(|manager| manager.form.as_mut()) {
+            form.field = (form.field + 1) % 3;
+        }
+    }
+
+    pub fn add_char_to_index_form(&mut self, c: char) {
+        if let Some(form) = self.index_manager.as_mut().and_then(|manager| manager.form.as_mut()) {
+            match form.field {
+                0 => form.name.push(c),
+                1 => form.columns.push(c),
+                _ if c == ' ' => form.unique = !form.unique,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn remove_char_from_index_form(&mut self) {
+        if let Some(form) = self.index_manager.as_mut().and_then(|manager| manager.form.as_mut()) {
+            match form.field {
+                0 => { form.name.pop(); }
+                1 => { form.columns.pop(); }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn set_confirming_index_drop(&mut self, confirming: bool) {
+        if let Some(manager) = &mut self.index_manager {
+            manager.confirming_drop = confirming && !manager.indexes.is_empty();
+        }
+    }
+
+    /// Remember the current view before following a relationship away from it
+    pub fn push_relation_step(&mut self, label: String) {
+        let mut view = self.stash_current_tab();
+        view.relation_trail.clear();
+        self.relation_trail.push(RelationStep { label, view });
+    }
+
+    /// Return to the view the last relationship was followed from
+    pub fn pop_relation_step(&mut self) -> bool {
+        let Some(step) = self.relation_trail.pop() else {
+            return false;
+        };
+        let trail = std::mem::take(&mut self.relation_trail);
+        self.restore_tab(step.view);
+        self.relation_trail = trail;
+        true
+    }
+
+    /// Breadcrumb of followed relationships ending at the current table
+    pub fn get_relation_breadcrumb(&self) -> Option<String> {
+        if self.relation_trail.is_empty() {
+            return None;
+        }
+        let mut parts: Vec<&str> = self.relation_trail.iter().map(|step| step.label.as_str()).collect();
+        parts.push(self.selected_table.as_deref().unwrap_or("?"));
+        Some(parts.join(" › "))
+    }
+
+    pub fn move_reverse_reference_selection(&mut self, down: bool) {
+        if let Some(references) = &mut self.reverse_references {
+            if down {
+                if references.selected + 1 < references.entries.len() {
+                    references.selected += 1;
+                }
+            } else {
+                references.selected = references.selected.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn scroll_storage_breakdown(&mut self, down: bool) {
+        if let Some(breakdown) = &mut self.storage_breakdown {
+            if down {
+                if breakdown.scroll + 1 < breakdown.storage.columns.len() {
+                    breakdown.scroll += 1;
+                }
+            } else {
+                breakdown.scroll = breakdown.scroll.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn open_definition_view(&mut self, object_name: String, kind: DefinitionKind, sql: String) {
+        self.definition_view = Some(DefinitionView { object_name, kind, sql, scroll: 0, save_path: None });
+    }
+
+    pub fn scroll_definition_view(&mut self, down: bool) {
+        if let Some(view) = &mut self.definition_view {
+            if down {
+                if view.scroll + 1 < view.sql.lines().count() {
+                    view.scroll += 1;
+                }
+            } else {
+                view.scroll = view.scroll.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn start_definition_save(&mut self) {
+        if let Some(view) = &mut self.definition_view {
+            let (_, name) = crate::db::schema::split_table_name(&view.object_name);
+            view.save_path = Some(format!("{name}.sql"));
+        }
+    }
+
+    pub fn cancel_definition_save(&mut self) {
+        if let Some(view) = &mut self.definition_view {
+            view.save_path = None;
+        }
+    }
+
+    pub fn add_char_to_definition_save(&mut self, c: char) {
+        if let Some(path) = self.definition_view.as_mut().and_then(|view| view.save_path.as_mut()) {
+            path.push(c);
+        }
+    }
+
+    pub fn remove_char_from_definition_save(&mut self) {
+        if let Some(path) = self.definition_view.as_mut().and_then(|view| view.save_path.as_mut()) {
+            path.pop();
+        }
+    }
+
+    // Schema diff methods
+    pub fn start_schema_diff_input(&mut self, databases: &[String], current: Option<&str>) {
+        self.is_entering_schema_diff = true;
+        // Seed with the current database and the next open one
+        let left = current.map(str::to_string).or_else(|| databases.first().cloned()).unwrap_or_default();
+        let right = databases.iter().find(|name| **name != left).cloned().unwrap_or_default();
+        self.schema_diff_input = [left, right].iter()
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    pub fn cancel_schema_diff_input(&mut self) {
+        self.is_entering_schema_diff = false;
+        self.schema_diff_input.clear();
+    }
+
+    pub fn add_char_to_schema_diff(&mut self, c: char) {
+        self.schema_diff_input.push(c);
+    }
+
+    pub fn remove_char_from_schema_diff(&mut self) {
+        self.schema_diff_input.pop();
+    }
+
+    pub fn scroll_schema_diff(&mut self, down: bool) {
+        if let Some(view) = &mut self.schema_diff {
+            if down {
+                if view.scroll + 1 < view.script.lines().count() {
+                    view.scroll += 1;
+                }
+            } else {
+                view.scroll = view.scroll.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn start_schema_diff_save(&mut self) {
+        if let Some(view) = &mut self.schema_diff {
+            let file_name = |spec: &str| spec.replace([':', '/', '\\'], "_");
+            view.save_path = Some(format!("migrate_{}_to_{}.sql", file_name(&view.source), file_name(&view.target)));
+        }
+    }
+
+    pub fn cancel_schema_diff_save(&mut self) {
+        if let Some(view) = &mut self.schema_diff {
+            view.save_path = None;
+        }
+    }
+
+    pub fn add_char_to_schema_diff_save(&mut self, c: char) {
+        if let Some(path) = self.schema_diff.as_mut().and_then(|view| view.save_path.as_mut()) {
+            path.push(c);
+        }
+    }
+
+    pub fn remove_char_from_schema_diff_save(&mut self) {
+        if let Some(path) = self.schema_diff.as_mut().and_then(|view| view.save_path.as_mut()) {
+            path.pop();
+        }
+    }
+
+    /// Move view state kept per table name over to a renamed table
+    pub fn apply_table_rename(&mut self, database: &str, old: &str, new: &str) {
+        fn rekey<V>(map: &mut std::collections::HashMap<String, V>, old: &str, new: &str) {
+            if let Some(value) = map.remove(old) {
+                map.insert(new.to_string(), value);
+            }
+        }
+        rekey(&mut self.column_order, old, new);
+        rekey(&mut self.hidden_columns, old, new);
+        rekey(&mut self.column_widths, old, new);
+        rekey(&mut self.sample_specs, old, new);
+        rekey(&mut self.view_undo_stack, old, new);
+        rekey(&mut self.view_redo_stack, old, new);
+
+        let same_database = self.selected_database.as_deref() == Some(database);
+        if same_database && self.selected_table.as_deref() == Some(old) {
+            self.selected_table = Some(new.to_string());
+        }
+        for tab in &mut self.tabs {
+            if tab.database.as_deref() == Some(database) && tab.table.as_deref() == Some(old) {
+                tab.table = Some(new.to_string());
+            }
+        }
+    }
+
+    /// Column selected in the inspect view's schema section
+    pub fn get_inspect_selected_column(&self) -> Option<String> {
+        self.get_virtual_column_order().get(self.inspect_selected_row).cloned()
+    }
+
+    /// Keep a table's view state in step with a column being added (`old` None),
+    /// renamed, or dropped (`new` None)
+    pub fn apply_column_rename(&mut self, table_name: &str, old: Option<&str>, new: Option<&str>) {
+        let rename = |names: &mut Vec<String>| {
+            match (old, new) {
+                (Some(old), Some(new)) => names.iter_mut()
+                    .filter(|name| name.as_str() == old)
+                    .for_each(|name| *name = new.to_string()),
+                (Some(old), None) => names.retain(|name| name != old),
+                (None, Some(new)) => {
+                    if !names.iter().any(|name| name == new) {
+                        names.push(new.to_string());
+                    }
+                }
+                (None, None) => {}
+            }
+        };
+
+        if let Some(order) = self.column_order.get_mut(table_name) {
+            rename(order);
+        }
+        if let Some(hidden) = self.hidden_columns.get_mut(table_name) {
+            if let Some(old) = old {
+                if hidden.remove(old) {
+                    if let Some(new) = new {
+                        hidden.insert(new.to_string());
+                    }
+                }
+            }
+        }
+        if let (Some(widths), Some(old)) = (self.column_widths.get_mut(table_name), old) {
+            if let Some(width) = widths.remove(old) {
+                if let Some(new) = new {
+                    widths.insert(new.to_string(), width);
+                }
+            }
+        }
 
-    pub fn scroll_table_left(&mut self) {
-        if self.scroll_x > 0 {
-            self.scroll_x -= 1;
+        // Filters, sorting and selection belong to the live view of the selected table
+        if self.selected_table.as_deref() != Some(table_name) {
+            return;
+        }
+        rename(&mut self.original_column_names);
+        if let Some(old) = old {
+            if let Some(filter) = self.column_filters.remove(old) {
+                if let Some(new) = new {
+                    self.column_filters.insert(new.to_string(), filter);
+                }
+            }
+            match new {
+                Some(new) => self.sort_columns.iter_mut()
+                    .filter(|spec| spec.column_name == old)
+                    .for_each(|spec| spec.column_name = new.to_string()),
+                None => self.sort_columns.retain(|spec| spec.column_name != old),
+            }
+            if self.selected_column.as_deref() == Some(old) {
+                self.selected_column = new.map(str::to_string);
+            }
         }
+        self.expanded_columns.clear();
+        let column_count = self.get_virtual_column_order().len();
+        self.inspect_selected_row = self.inspect_selected_row.min(column_count.saturating_sub(1));
     }
 
-    pub fn scroll_table_right(&mut self, max_columns: usize, visible_columns: usize) {
-        if self.scroll_x + visible_columns < max_columns {
-            self.scroll_x += 1;
-        }
+    /// WHERE clause of the current filters, required before any bulk change
+    pub fn get_bulk_filter_clause(&self) -> Option<String> {
+        self.get_filter_sql_clause(&self.get_original_column_names())
     }
 
-    pub fn scroll_table_up(&mut self) {
-        if self.scroll_y > 0 {
-            self.scroll_y -= 1;
-        }
+    /// Whether edits to the given database are currently being staged
+    pub fn is_staging_edits(&self, database: Option<&str>) -> bool {
+        self.transaction.as_ref()
+            .is_some_and(|transaction| Some(transaction.database.as_str()) == database)
     }
 
-    pub fn scroll_table_down(&mut self, max_rows: usize, visible_rows: usize) {
-        if self.scroll_y + visible_rows < max_rows {
-            self.scroll_y += 1;
+    /// Whether a loaded cell was changed or inserted in the open transaction
+    pub fn is_cell_modified(&self, row: usize, column_name: &str) -> bool {
+        let (Some(transaction), Some(table), Some(key)) = (
+            self.transaction.as_ref(),
+            self.selected_table.as_ref(),
+            self.row_key_values.get(row),
+        ) else {
+            return false;
+        };
+        if !self.is_staging_edits(self.selected_database.as_deref()) {
+            return false;
         }
+        transaction.inserted_rows.contains(&(table.clone(), key.clone()))
+            || transaction.modified_cells.contains(&(table.clone(), key.clone(), column_name.to_string()))
     }
 
-    pub fn page_table_up(&mut self) {
-        self.scroll_y = self.scroll_y.saturating_sub(self.page_size);
+    pub fn get_selected_cell_value(&self) -> Option<String> {
+        let column_index = self.get_selected_column_index()?;
+        self.table_data.as_ref()?
+            .rows.get(self.selected_row)?
+            .get(column_index)
+            .cloned()
     }
 
-    pub fn page_table_down(&mut self, max_rows: usize, visible_rows: usize) {
-        let new_scroll = self.scroll_y + self.page_size;
-        if new_scroll + visible_rows <= max_rows {
-            self.scroll_y = new_scroll;
-        } else if max_rows > visible_rows {
-            self.scroll_y = max_rows - visible_rows;
-        }
+    // Sampling methods
+    pub fn start_sample_spec_input(&mut self) {
+        self.is_entering_sample_spec = true;
+        // Seed the input with the active sample so it can be tweaked
+        self.sample_spec_input = self.get_sample_spec()
+            .map(|sample| sample.to_input())
+            .unwrap_or_default();
     }
 
-    // Database dropdown methods
-    pub fn expand_database_dropdown(&mut self, num_databases: usize) {
-        self.database_dropdown_expanded = true;
-        // Ensure dropdown_selected_index is within bounds
-        if self.dropdown_selected_index >= num_databases {
-            self.dropdown_selected_index = 0;
-        }
+    pub fn cancel_sample_spec_input(&mut self) {
+        self.is_entering_sample_spec = false;
+        self.sample_spec_input.clear();
     }
 
-    pub fn collapse_database_dropdown(&mut self) {
-        self.database_dropdown_expanded = false;
+    pub fn add_char_to_sample_spec(&mut self, c: char) {
+        self.sample_spec_input.push(c);
     }
 
-    pub fn dropdown_move_up(&mut self) {
-        if self.dropdown_selected_index > 0 {
-            self.dropdown_selected_index -= 1;
-        }
+    pub fn remove_char_from_sample_spec(&mut self) {
+        self.sample_spec_input.pop();
     }
 
-    pub fn dropdown_move_down(&mut self, num_databases: usize) {
-        if self.dropdown_selected_index < num_databases.saturating_sub(1) {
-            self.dropdown_selected_index += 1;
+    pub fn get_sample_spec(&self) -> Option<&SampleSpec> {
+        self.selected_table.as_ref()
+            .and_then(|table_name| self.sample_specs.get(table_name))
+    }
+
+    pub fn set_sample_spec(&mut self, sample: Option<SampleSpec>) {
+        if let Some(table_name) = self.selected_table.clone() {
+            match sample {
+                Some(sample) => {
+                    self.sample_specs.insert(table_name, sample);
+                }
+                None => {
+                    self.sample_specs.remove(&table_name);
+                }
+            }
         }
     }
 
-    pub fn set_dropdown_to_current_database(&mut self, current_db_index: usize) {
-        self.dropdown_selected_index = current_db_index;
+    pub fn get_sample_sql_clause(&self) -> Option<String> {
+        self.get_sample_spec().map(|sample| sample.get_sql_clause())
     }
 
-    // Left panel navigation helper
-    pub fn set_left_panel(&mut self, panel: NavigationPanel) {
-        // Only track changes to actual left panel widgets
-        if matches!(panel, NavigationPanel::DatabaseList | NavigationPanel::TableList) {
-            self.last_left_panel = panel.clone();
-        }
-        
-        // Trigger flash effect if panel is changing
-        if self.active_panel != panel {
-            self.start_panel_flash();
-        }
-        
-        self.active_panel = panel;
+    // Layout picker methods
+    pub fn start_layout_picker(&mut self) {
+        self.is_picking_layout = true;
+        self.layout_picker_index = 0;
     }
 
-    // Flash effect methods
-    pub fn start_panel_flash(&mut self) {
-        self.panel_flash_timer = Some(std::time::Instant::now());
+    pub fn close_layout_picker(&mut self) {
+        self.is_picking_layout = false;
+        self.layout_picker_index = 0;
     }
 
-    pub fn is_panel_flashing(&self) -> bool {
-        if let Some(start_time) = self.panel_flash_timer {
-            start_time.elapsed().as_millis() < self.flash_duration_ms as u128
-        } else {
-            false
+    pub fn layout_picker_move_up(&mut self) {
+        if self.layout_picker_index > 0 {
+            self.layout_picker_index -= 1;
         }
     }
 
-    pub fn update_flash_timer(&mut self) {
-        if let Some(start_time) = self.panel_flash_timer {
-            if start_time.elapsed().as_millis() >= self.flash_duration_ms as u128 {
-                self.panel_flash_timer = None;
-            }
+    pub fn layout_picker_move_down(&mut self, num_layouts: usize) {
+        if self.layout_picker_index < num_layouts.saturating_sub(1) {
+            self.layout_picker_index += 1;
         }
     }
 
-    // Delete confirmation methods
-    pub fn start_database_delete_confirmation(&mut self, database_name: String) {
-        self.delete_confirmation = DeleteConfirmationState::Database(database_name);
+    // Layout name input methods
+    pub fn start_layout_name_input(&mut self) {
+        self.is_entering_layout_name = true;
+        self.new_layout_name.clear();
     }
 
-    pub fn start_table_delete_confirmation(&mut self, table_name: String) {
-        self.delete_confirmation = DeleteConfirmationState::Table(table_name);
+    pub fn cancel_layout_name_input(&mut self) {
+        self.is_entering_layout_name = false;
+        self.new_layout_name.clear();
     }
 
-    pub fn cancel_delete_confirmation(&mut self) {
-        self.delete_confirmation = DeleteConfirmationState::None;
+    pub fn add_char_to_layout_name(&mut self, c: char) {
+        self.new_layout_name.push(c);
     }
 
-    pub fn is_delete_confirmation_active(&self) -> bool {
-        !matches!(self.delete_confirmation, DeleteConfirmationState::None)
+    pub fn remove_char_from_layout_name(&mut self) {
+        self.new_layout_name.pop();
     }
 
-    // Database name input methods
-    pub fn start_database_name_input(&mut self) {
-        self.is_entering_database_name = true;
-        self.new_database_name.clear();
-    }
+    // Capture the current table's view state as a named layout
+    pub fn capture_layout(&self, name: String) -> Option<ViewLayout> {
+        self.selected_table.as_ref()?;
 
-    pub fn cancel_database_name_input(&mut self) {
-        self.is_entering_database_name = false;
-        self.new_database_name.clear();
-    }
+        let column_order = self.get_virtual_column_order();
+        let mut hidden_columns: Vec<String> = self.get_hidden_columns().into_iter().collect();
+        hidden_columns.sort();
 
-    pub fn add_char_to_database_name(&mut self, c: char) {
-        self.new_database_name.push(c);
-    }
+        let expanded_columns = self.expanded_columns.iter()
+            .filter_map(|&idx| self.get_column_name_by_index(idx))
+            .collect();
 
-    pub fn remove_char_from_database_name(&mut self) {
-        self.new_database_name.pop();
-    }
+        let column_widths = self.selected_table.as_ref()
+            .and_then(|table_name| self.column_widths.get(table_name))
+            .cloned()
+            .unwrap_or_default();
 
-    // Save filename input methods
-    pub fn start_save_filename_input(&mut self) {
-        self.is_entering_save_filename = true;
-        self.save_filename.clear();
+        Some(ViewLayout {
+            name,
+            column_order,
+            hidden_columns,
+            sort_columns: self.sort_columns.clone(),
+            column_filters: self.column_filters.clone(),
+            expanded_columns,
+            column_widths,
+        })
     }
 
-    pub fn cancel_save_filename_input(&mut self) {
-        self.is_entering_save_filename = false;
-        self.save_filename.clear();
-    }
+    // Apply a saved layout to the current table, ignoring columns that no longer exist
+    pub fn apply_layout(&mut self, layout: &ViewLayout) {
+        let Some(table_name) = self.selected_table.clone() else {
+            return;
+        };
 
-    pub fn add_char_to_save_filename(&mut self, c: char) {
-        self.save_filename.push(c);
-    }
+        let original = self.get_original_column_names();
+        let exists = |name: &String| original.is_empty() || original.contains(name);
+
+        // Keep the saved order for known columns and append any new ones at the end
+        let mut column_order: Vec<String> = layout.column_order.iter()
+            .filter(|name| exists(name))
+            .cloned()
+            .collect();
+        for name in &original {
+            if !column_order.contains(name) {
+                column_order.push(name.clone());
+            }
+        }
+        self.column_order.insert(table_name.clone(), column_order);
 
-    pub fn remove_char_from_save_filename(&mut self) {
-        self.save_filename.pop();
+        let widths: std::collections::HashMap<String, usize> = layout.column_widths.iter()
+            .filter(|(name, _)| exists(name))
+            .map(|(name, width)| (name.clone(), *width))
+            .collect();
+        if widths.is_empty() {
+            self.column_widths.remove(&table_name);
+        } else {
+            self.column_widths.insert(table_name.clone(), widths);
+        }
+
+        let hidden: std::collections::HashSet<String> = layout.hidden_columns.iter()
+            .filter(|name| exists(name))
+            .cloned()
+            .collect();
+        if hidden.is_empty() {
+            self.hidden_columns.remove(&table_name);
+        } else {
+            self.hidden_columns.insert(table_name, hidden);
+        }
+
+        self.sort_columns = layout.sort_columns.iter()
+            .filter(|spec| exists(&spec.column_name))
+            .cloned()
+            .collect();
+
+        self.column_filters = layout.column_filters.iter()
+            .filter(|(name, _)| exists(name))
+            .map(|(name, filter)| (name.clone(), filter.clone()))
+            .collect();
+
+        self.expanded_columns = layout.expanded_columns.iter()
+            .filter_map(|name| self.get_column_index_by_name(name))
+            .collect();
+
+        // Keep the selection on a visible column
+        if let Some(selected) = self.selected_column.clone() {
+            if self.is_column_hidden_by_name(&selected) || self.get_column_index_by_name(&selected).is_none() {
+                self.selected_column = self.get_first_visible_column();
+            }
+        }
     }
 
-    // View name input methods
-    pub fn start_view_name_input(&mut self) {
-        self.is_entering_view_name = true;
-        self.new_view_name.clear();
+    // Undo/redo methods
+    // Call before mutating the view state; pair with end_view_change afterwards
+    pub fn begin_view_change(&mut self) {
+        if let (Some(table_name), Some(snapshot)) = (self.selected_table.clone(), self.capture_layout(String::new())) {
+            let undo_stack = self.view_undo_stack.entry(table_name).or_default();
+            undo_stack.push(snapshot);
+            if undo_stack.len() > MAX_VIEW_HISTORY {
+                undo_stack.remove(0);
+            }
+        }
     }
-    
-    pub fn cancel_view_name_input(&mut self) {
-        self.is_entering_view_name = false;
-        self.new_view_name.clear();
+
+    // Drop the pending snapshot if nothing changed, otherwise invalidate the redo history
+    pub fn end_view_change(&mut self) {
+        if let (Some(table_name), Some(current)) = (self.selected_table.clone(), self.capture_layout(String::new())) {
+            let unchanged = self.view_undo_stack
+                .get(&table_name)
+                .and_then(|stack| stack.last())
+                .is_some_and(|last| *last == current);
+
+            if unchanged {
+                if let Some(stack) = self.view_undo_stack.get_mut(&table_name) {
+                    stack.pop();
+                }
+            } else {
+                self.view_redo_stack.remove(&table_name);
+            }
+        }
     }
-    
-    pub fn add_char_to_view_name(&mut self, c: char) {
-        self.new_view_name.push(c);
+
+    pub fn undo_view_change(&mut self) -> bool {
+        let Some(table_name) = self.selected_table.clone() else {
+            return false;
+        };
+        let Some(previous) = self.view_undo_stack.get_mut(&table_name).and_then(|stack| stack.pop()) else {
+            return false;
+        };
+
+        if let Some(current) = self.capture_layout(String::new()) {
+            self.view_redo_stack.entry(table_name).or_default().push(current);
+        }
+        self.apply_layout(&previous);
+        true
     }
-    
-    pub fn remove_char_from_view_name(&mut self) {
-        self.new_view_name.pop();
+
+    pub fn redo_view_change(&mut self) -> bool {
+        let Some(table_name) = self.selected_table.clone() else {
+            return false;
+        };
+        let Some(next) = self.view_redo_stack.get_mut(&table_name).and_then(|stack| stack.pop()) else {
+            return false;
+        };
+
+        if let Some(current) = self.capture_layout(String::new()) {
+            self.view_undo_stack.entry(table_name).or_default().push(current);
+        }
+        self.apply_layout(&next);
+        true
     }
 
     // Generate SQL query for current view state
@@ -665,8 +2190,12 @@ impl ApplicationState {
             }
             
             // Build SELECT clause with virtual column order
-            let columns_sql = visible_column_names.join(", ");
-            let mut sql = format!("SELECT {columns_sql} FROM {table_name}");
+            let columns_sql = visible_column_names.iter()
+                .map(|column| quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let source = self.get_source_sql(table_name);
+            let mut sql = format!("SELECT {columns_sql} FROM {source}");
             
             // Add WHERE clause for filters
             let original_column_names = self.get_original_column_names();
@@ -830,7 +2359,7 @@ impl ApplicationState {
                 SortDirection::Ascending => "ASC",
                 SortDirection::Descending => "DESC",
             };
-            sort_parts.push(format!("{} {}", sort_spec.column_name, direction));
+            sort_parts.push(format!("{} {}", quote_identifier(&sort_spec.column_name), direction));
         }
 
         if sort_parts.is_empty() {
@@ -910,7 +2439,7 @@ impl ApplicationState {
         let mut filter_parts = Vec::new();
         for (column_name, filter_text) in &self.column_filters {
             // Use the filter text directly as SQL (user responsibility for syntax)
-            filter_parts.push(format!("{column_name} {filter_text}"));
+            filter_parts.push(format!("{} {filter_text}", quote_identifier(column_name)));
         }
 
         if filter_parts.is_empty() {
@@ -935,6 +2464,7 @@ impl ApplicationState {
         self.inspect_schema_scroll_y = 0;
         self.inspect_stats_scroll_y = 0;
         self.inspect_selected_row = 0;
+        self.inspect_data = None;
     }
 
     pub fn exit_inspect_mode(&mut self) {
@@ -943,6 +2473,12 @@ impl ApplicationState {
         self.inspect_schema_scroll_y = 0;
         self.inspect_stats_scroll_y = 0;
         self.inspect_selected_row = 0;
+        self.inspect_data = None;
+    }
+
+    /// Whether the inspect view needs its data (re)loaded for the selected table
+    pub fn is_inspect_data_outdated(&self) -> bool {
+        self.inspect_mode && self.inspect_data.as_ref().map(|data| &data.table_name) != self.selected_table.as_ref()
     }
 
     pub fn inspect_cycle_section(&mut self) {
@@ -1080,6 +2616,8 @@ impl ApplicationState {
     // Modal modification methods (reordering + hiding)
     pub fn start_modifying(&mut self) {
         if !self.is_modifying {
+            // The whole modify session becomes a single undo step
+            self.begin_view_change();
             // Backup current column order for cancel operation
             self.modify_backup_column_order = Some(self.get_virtual_column_order());
             self.is_modifying = true;
@@ -1096,6 +2634,7 @@ impl ApplicationState {
             }
             self.is_modifying = false;
             self.modify_backup_column_order = None;
+            self.end_view_change();
         }
     }
 
@@ -1104,6 +2643,7 @@ impl ApplicationState {
             // Just exit modifying mode - changes are already applied
             self.is_modifying = false;
             self.modify_backup_column_order = None;
+            self.end_view_change();
         }
     }
 
@@ -1426,9 +2966,680 @@ impl ApplicationState {
         }
     }
 
+    // Tab methods
+    fn stash_current_tab(&self) -> TableTab {
+        TableTab {
+            database: self.selected_database.clone(),
+            table: self.selected_table.clone(),
+            table_data: self.table_data.clone(),
+            scroll_x: self.scroll_x,
+            scroll_y: self.scroll_y,
+            selected_row: self.selected_row,
+            selected_column: self.selected_column.clone(),
+            expanded_columns: self.expanded_columns.clone(),
+            sort_columns: self.sort_columns.clone(),
+            column_filters: self.column_filters.clone(),
+            original_column_names: self.original_column_names.clone(),
+            row_key: self.row_key.clone(),
+            row_key_values: self.row_key_values.clone(),
+            visual_row_anchor: self.visual_row_anchor,
+            relation_trail: self.relation_trail.clone(),
+        }
+    }
+
+    fn restore_tab(&mut self, tab: TableTab) {
+        self.selected_database = tab.database;
+        self.selected_table = tab.table;
+        self.table_data = tab.table_data;
+        self.scroll_x = tab.scroll_x;
+        self.scroll_y = tab.scroll_y;
+        self.selected_row = tab.selected_row;
+        self.selected_column = tab.selected_column;
+        self.expanded_columns = tab.expanded_columns;
+        self.sort_columns = tab.sort_columns;
+        self.column_filters = tab.column_filters;
+        self.original_column_names = tab.original_column_names;
+        self.row_key = tab.row_key;
+        self.row_key_values = tab.row_key_values;
+        self.visual_row_anchor = tab.visual_row_anchor;
+        self.relation_trail = tab.relation_trail;
+        // Inspect mode belongs to the tab that was left
+        self.inspect_mode = false;
+    }
+
+    // The view that was open before any tab was created becomes the first tab
+    fn ensure_first_tab(&mut self) {
+        if self.tabs.is_empty() && self.selected_table.is_some() {
+            self.tabs.push(self.stash_current_tab());
+            self.active_tab = 0;
+        }
+    }
+
+    /// Start an empty tab and make it active; the caller then selects what it shows
+    pub fn open_new_tab(&mut self) {
+        self.ensure_first_tab();
+        let current = self.stash_current_tab();
+        if let Some(slot) = self.tabs.get_mut(self.active_tab) {
+            *slot = current;
+        }
+        let database = self.selected_database.clone();
+        self.restore_tab(TableTab { database, ..TableTab::default() });
+        self.tabs.push(TableTab::default());
+        self.active_tab = self.tabs.len() - 1;
+    }
+
+    /// Switch to another tab, returning its database so the caller can reconnect to it
+    pub fn switch_to_tab(&mut self, index: usize) -> Option<String> {
+        if index >= self.tabs.len() || index == self.active_tab {
+            return None;
+        }
+        self.tabs[self.active_tab] = self.stash_current_tab();
+        let tab = self.tabs[index].clone();
+        // Moving onto the split partner keeps the pair, with the panes swapped
+        if self.split_tab == Some(index) {
+            self.split_tab = Some(self.active_tab);
+        }
+        self.active_tab = index;
+        self.restore_tab(tab);
+        self.selected_database.clone()
+    }
+
+    pub fn next_tab(&mut self) -> Option<String> {
+        if self.tabs.len() < 2 {
+            return None;
+        }
+        self.switch_to_tab((self.active_tab + 1) % self.tabs.len())
+    }
+
+    pub fn prev_tab(&mut self) -> Option<String> {
+        if self.tabs.len() < 2 {
+            return None;
+        }
+        self.switch_to_tab((self.active_tab + self.tabs.len() - 1) % self.tabs.len())
+    }
+
+    /// Close the active tab, returning the database of the tab that takes its place
+    pub fn close_active_tab(&mut self) -> Option<String> {
+        self.ensure_first_tab();
+        if self.tabs.is_empty() {
+            return None;
+        }
+
+        self.tabs.remove(self.active_tab);
+        self.split_tab = None;
+        if self.tabs.is_empty() {
+            // Closing the last tab leaves an empty viewer
+            let database = self.selected_database.clone();
+            self.restore_tab(TableTab { database, ..TableTab::default() });
+            self.active_tab = 0;
+            return None;
+        }
+
+        self.active_tab = self.active_tab.min(self.tabs.len() - 1);
+        let tab = self.tabs[self.active_tab].clone();
+        self.restore_tab(tab);
+        self.selected_database.clone()
+    }
+
+    /// Drop tabs showing a database that is no longer connected
+    pub fn close_tabs_for_database(&mut self, database: &str) {
+        if self.tabs.is_empty() {
+            return;
+        }
+        self.tabs[self.active_tab] = self.stash_current_tab();
+        self.tabs.retain(|tab| tab.database.as_deref() != Some(database));
+        self.split_tab = None;
+        self.active_tab = 0;
+        let tab = self.tabs.first().cloned().unwrap_or_default();
+        self.restore_tab(tab);
+    }
+
+    // Split view methods
+
+    /// Show the next tab beside the active one, or close the split. Returns false if there is no other tab.
+    pub fn toggle_split(&mut self) -> bool {
+        if self.split_tab.take().is_some() {
+            return true;
+        }
+        if self.tabs.len() < 2 {
+            return false;
+        }
+        self.split_tab = Some((self.active_tab + 1) % self.tabs.len());
+        true
+    }
+
+    /// Cycle sync mode: off -> row position -> selected column as key -> off
+    pub fn cycle_split_sync(&mut self) {
+        self.split_sync = match &self.split_sync {
+            SplitSyncMode::Off => SplitSyncMode::RowPosition,
+            SplitSyncMode::RowPosition => match &self.selected_column {
+                Some(column) => SplitSyncMode::KeyColumn(column.clone()),
+                None => SplitSyncMode::Off,
+            },
+            SplitSyncMode::KeyColumn(_) => SplitSyncMode::Off,
+        };
+        self.split_key_missing = false;
+    }
+
+    pub fn get_split_sync_display(&self) -> Option<String> {
+        match &self.split_sync {
+            SplitSyncMode::Off => None,
+            SplitSyncMode::RowPosition => Some("sync by row".to_string()),
+            SplitSyncMode::KeyColumn(column) => Some(format!("sync by {column}")),
+        }
+    }
+
+    /// Move the split partner's selection to follow the active grid
+    pub fn sync_split_tab(&mut self, visible_rows: usize) {
+        let Some(partner) = self.split_tab.and_then(|index| self.tabs.get_mut(index)) else {
+            return;
+        };
+        let Some(partner_data) = &partner.table_data else {
+            return;
+        };
+        if partner_data.rows.is_empty() {
+            return;
+        }
+
+        let target_row = match &self.split_sync {
+            SplitSyncMode::Off => return,
+            SplitSyncMode::RowPosition => self.selected_row.min(partner_data.rows.len() - 1),
+            SplitSyncMode::KeyColumn(column) => {
+                let key_value = self.table_data.as_ref().and_then(|data| {
+                    let index = data.columns.iter().position(|name| name == column)?;
+                    data.rows.get(self.selected_row)?.get(index)
+                });
+                let partner_index = partner_data.columns.iter().position(|name| name == column);
+                let matched = key_value.zip(partner_index).and_then(|(value, index)| {
+                    partner_data.rows.iter().position(|row| row.get(index) == Some(value))
+                });
+                self.split_key_missing = matched.is_none();
+                match matched {
+                    Some(row) => row,
+                    None => return,
+                }
+            }
+        };
+
+        partner.selected_row = target_row;
+        if self.split_sync == SplitSyncMode::RowPosition {
+            partner.scroll_y = self.scroll_y.min(target_row);
+        }
+        // Keep the selected row inside the visible part of the partner grid
+        let visible_rows = visible_rows.max(1);
+        if target_row < partner.scroll_y {
+            partner.scroll_y = target_row;
+        } else if target_row >= partner.scroll_y + visible_rows {
+            partner.scroll_y = target_row + 1 - visible_rows;
+        }
+    }
+
+    /// Exchange the live viewer state with a stashed tab without copying table data.
+    /// Calling it twice with the same index restores the original state.
+    pub fn swap_live_with_tab(&mut self, index: usize) {
+        let Some(tab) = self.tabs.get_mut(index) else {
+            return;
+        };
+        std::mem::swap(&mut self.selected_database, &mut tab.database);
+        std::mem::swap(&mut self.selected_table, &mut tab.table);
+        std::mem::swap(&mut self.table_data, &mut tab.table_data);
+        std::mem::swap(&mut self.scroll_x, &mut tab.scroll_x);
+        std::mem::swap(&mut self.scroll_y, &mut tab.scroll_y);
+        std::mem::swap(&mut self.selected_row, &mut tab.selected_row);
+        std::mem::swap(&mut self.selected_column, &mut tab.selected_column);
+        std::mem::swap(&mut self.expanded_columns, &mut tab.expanded_columns);
+        std::mem::swap(&mut self.sort_columns, &mut tab.sort_columns);
+        std::mem::swap(&mut self.column_filters, &mut tab.column_filters);
+        std::mem::swap(&mut self.original_column_names, &mut tab.original_column_names);
+        std::mem::swap(&mut self.row_key, &mut tab.row_key);
+        std::mem::swap(&mut self.row_key_values, &mut tab.row_key_values);
+        std::mem::swap(&mut self.visual_row_anchor, &mut tab.visual_row_anchor);
+        std::mem::swap(&mut self.relation_trail, &mut tab.relation_trail);
+    }
+
+    /// Labels for the tab bar, using live state for the active tab
+    pub fn get_tab_titles(&self) -> Vec<String> {
+        self.tabs.iter().enumerate()
+            .map(|(i, tab)| {
+                let table = if i == self.active_tab { &self.selected_table } else { &tab.table };
+                format!("{}:{}", i + 1, table.as_deref().unwrap_or("(empty)"))
+            })
+            .collect()
+    }
+
+    // Query input methods
+    pub fn start_query_input(&mut self) {
+        self.is_entering_query = true;
+        self.query_input.clear();
+    }
+
+    pub fn cancel_query_input(&mut self) {
+        self.is_entering_query = false;
+        self.query_input.clear();
+    }
+
+    pub fn add_char_to_query(&mut self, c: char) {
+        self.query_input.push(c);
+    }
+
+    pub fn remove_char_from_query(&mut self) {
+        self.query_input.pop();
+    }
+
+    /// Register a query result under a generated name so it can be shown like a table
+    pub fn add_query_source(&mut self, prefix: &str, sql: String) -> String {
+        let name = format!("{prefix}_{}", self.next_query_number);
+        self.next_query_number += 1;
+        self.query_sources.insert(name.clone(), sql);
+        name
+    }
+
+    /// The FROM target for a table or query result shown in the viewer
+    pub fn get_source_sql(&self, table_name: &str) -> String {
+        match self.query_sources.get(table_name) {
+            Some(sql) => format!("({}) AS {}", sql.trim().trim_end_matches(';'), table_name),
+            None => crate::db::schema::quote_table_name(table_name),
+        }
+    }
+
+    pub fn is_query_result(&self, table_name: &str) -> bool {
+        self.query_sources.contains_key(table_name)
+    }
+
+    // Column width override methods
+    pub fn get_column_width_override(&self, column_name: &str) -> Option<usize> {
+        self.selected_table.as_ref()
+            .and_then(|table_name| self.column_widths.get(table_name))
+            .and_then(|widths| widths.get(column_name))
+            .copied()
+    }
+
+    pub fn set_column_width(&mut self, column_name: &str, width: usize) {
+        if let Some(table_name) = self.selected_table.clone() {
+            self.column_widths
+                .entry(table_name)
+                .or_default()
+                .insert(column_name.to_string(), width.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH));
+        }
+    }
+
     pub fn clear_hidden_columns(&mut self) {
         if let Some(table_name) = &self.selected_table {
             self.hidden_columns.remove(table_name);
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_table() -> ApplicationState {
+        let mut state = ApplicationState::new();
+        state.select_table("users".to_string());
+        state.initialize_column_order(vec!["id".to_string(), "name".to_string(), "age".to_string()]);
+        state.selected_column = Some("name".to_string());
+        state
+    }
+
+    #[test]
+    fn test_undo_redo_sort_and_filter_changes() {
+        let mut state = state_with_table();
+
+        state.begin_view_change();
+        state.toggle_in_sort_chain(true);
+        state.end_view_change();
+
+        state.begin_view_change();
+        state.column_filters.insert("age".to_string(), "> 30".to_string());
+        state.end_view_change();
+
+        assert!(state.undo_view_change());
+        assert!(state.column_filters.is_empty());
+        assert_eq!(state.sort_columns.len(), 1);
+
+        assert!(state.undo_view_change());
+        assert!(state.sort_columns.is_empty());
+        assert!(!state.undo_view_change(), "History should be exhausted");
+
+        assert!(state.redo_view_change());
+        assert_eq!(state.sort_columns[0].column_name, "name");
+        assert!(state.redo_view_change());
+        assert_eq!(state.column_filters.get("age").map(String::as_str), Some("> 30"));
+        assert!(!state.redo_view_change());
+    }
+
+    #[test]
+    fn test_modify_session_is_single_undo_step() {
+        let mut state = state_with_table();
+
+        state.start_modifying();
+        assert!(state.move_column_extreme_left());
+        state.toggle_column_visibility();
+        state.confirm_modifying();
+
+        assert_eq!(state.get_virtual_column_order()[0], "name");
+        assert!(state.is_column_hidden_by_name("name"));
+
+        assert!(state.undo_view_change());
+        assert_eq!(state.get_virtual_column_order(), vec!["id", "name", "age"]);
+        assert!(!state.has_hidden_columns());
+        assert!(!state.undo_view_change());
+    }
+
+    #[test]
+    fn test_sample_spec_parse() {
+        let sample = SampleSpec::parse("10%").unwrap();
+        assert_eq!(sample.size, SampleSize::Percent(10.0));
+        assert_eq!(sample.method, SampleMethod::System);
+        assert_eq!(sample.seed, None);
+
+        let sample = SampleSpec::parse("5000 reservoir 42").unwrap();
+        assert_eq!(sample.size, SampleSize::Rows(5000));
+        assert_eq!(sample.method, SampleMethod::Reservoir);
+        assert_eq!(sample.seed, Some(42));
+        assert_eq!(SampleSpec::parse(&sample.to_input()).unwrap(), sample);
+
+        let sample = SampleSpec::parse("0.5% Bernoulli seed=7").unwrap();
+        assert_eq!(sample.get_sql_clause(), "TABLESAMPLE 0.5 PERCENT (bernoulli, 7)");
+
+        assert!(SampleSpec::parse("").is_err());
+        assert!(SampleSpec::parse("150%").is_err());
+        assert!(SampleSpec::parse("100 bernoulli").is_err(), "Row samples need reservoir");
+        assert!(SampleSpec::parse("10% fast").is_err());
+    }
+
+    #[test]
+    fn test_sample_clause_is_valid_duckdb_sql() {
+        let conn = duckdb::Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t AS SELECT range AS id FROM range(10000)").unwrap();
+
+        let sample = SampleSpec::parse("100 reservoir 1").unwrap();
+        let sql = format!(
+            "SELECT id FROM t {} WHERE id >= 0 ORDER BY id LIMIT 1000",
+            sample.get_sql_clause()
+        );
+        let count = conn.prepare(&sql).unwrap()
+            .query_map([], |row| row.get::<_, i64>(0)).unwrap()
+            .count();
+        assert_eq!(count, 100);
+
+        let sample = SampleSpec::parse("10% bernoulli 3").unwrap();
+        let sql = format!("SUMMARIZE SELECT * FROM t {}", sample.get_sql_clause());
+        assert!(conn.prepare(&sql).is_ok());
+
+        // Unseeded samples read the same rows every time once made repeatable
+        let sample = SampleSpec::parse("1% bernoulli").unwrap();
+        assert_eq!(sample.get_repeatable_sql_clause(), "TABLESAMPLE bernoulli(1 PERCENT) REPEATABLE (42)");
+        let sql = format!("SELECT sum(id) FROM t {}", sample.get_repeatable_sql_clause());
+        let sum = |conn: &duckdb::Connection| conn.query_row(&sql, [], |row| row.get::<_, Option<i64>>(0)).unwrap();
+        assert_eq!(sum(&conn), sum(&conn));
+        let sample = SampleSpec::parse("100").unwrap();
+        let sql = format!("SUMMARIZE SELECT * FROM t {}", sample.get_repeatable_sql_clause());
+        assert!(conn.prepare(&sql).is_ok());
+    }
+
+    #[test]
+    fn test_unchanged_view_is_not_recorded() {
+        let mut state = state_with_table();
+
+        // Cancelling an empty modify session leaves nothing to undo
+        state.start_modifying();
+        state.cancel_modifying();
+        assert!(!state.undo_view_change());
+
+        // A new change clears the redo history
+        state.begin_view_change();
+        state.toggle_in_sort_chain(false);
+        state.end_view_change();
+        assert!(state.undo_view_change());
+        state.begin_view_change();
+        state.toggle_in_sort_chain(true);
+        state.end_view_change();
+        assert!(!state.redo_view_change());
+    }
+
+    #[test]
+    fn test_column_resize_is_undoable() {
+        let mut state = state_with_table();
+
+        state.begin_view_change();
+        state.set_column_width("name", 1000);
+        state.end_view_change();
+        assert_eq!(state.get_column_width_override("name"), Some(MAX_COLUMN_WIDTH));
+        assert_eq!(state.capture_layout(String::new()).unwrap().column_widths.len(), 1);
+
+        assert!(state.undo_view_change());
+        assert_eq!(state.get_column_width_override("name"), None);
+    }
+
+    #[test]
+    fn test_tabs_keep_independent_view_state() {
+        let mut state = state_with_table();
+        state.select_database("memory".to_string());
+        state.selected_row = 5;
+        state.toggle_in_sort_chain(true);
+
+        state.open_new_tab();
+        assert_eq!(state.tabs.len(), 2);
+        assert_eq!(state.selected_table, None);
+        state.select_table("orders".to_string());
+        state.column_filters.insert("total".to_string(), "> 10".to_string());
+
+        // Switching back restores the first tab untouched
+        assert_eq!(state.prev_tab(), Some("memory".to_string()));
+        assert_eq!(state.selected_table.as_deref(), Some("users"));
+        assert_eq!(state.selected_row, 5);
+        assert_eq!(state.sort_columns.len(), 1);
+        assert!(state.column_filters.is_empty());
+        assert_eq!(state.get_tab_titles(), vec!["1:users", "2:orders"]);
+
+        // Closing it leaves the other tab active
+        state.close_active_tab();
+        assert_eq!(state.tabs.len(), 1);
+        assert_eq!(state.selected_table.as_deref(), Some("orders"));
+        assert!(state.sort_columns.is_empty());
+    }
+
+    #[test]
+    fn test_query_source_is_valid_duckdb_sql() {
+        let conn = duckdb::Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t AS SELECT range AS id FROM range(100)").unwrap();
+
+        let mut state = ApplicationState::new();
+        let name = state.add_query_source("query", "SELECT id, id * 2 AS double FROM t;".to_string());
+        assert!(state.is_query_result(&name));
+
+        let sql = format!(
+            "SELECT double FROM {} {} WHERE id < 50 ORDER BY id LIMIT 1000",
+            state.get_source_sql(&name),
+            SampleSpec::parse("10 reservoir 1").unwrap().get_sql_clause()
+        );
+        let count = conn.prepare(&sql).unwrap()
+            .query_map([], |row| row.get::<_, i64>(0)).unwrap()
+            .count();
+        assert!(count <= 10);
+        assert_eq!(state.get_source_sql("t"), "\"t\"");
+    }
+
+    #[test]
+    fn test_column_rename_keeps_view_state() {
+        let mut state = state_with_table();
+        let table = state.selected_table.clone().unwrap();
+        let first = state.original_column_names[0].clone();
+        let last = state.original_column_names.last().unwrap().clone();
+        state.column_filters.insert(first.clone(), "> 1".to_string());
+        state.hidden_columns.entry(table.clone()).or_default().insert(last.clone());
+        state.selected_column = Some(first.clone());
+
+        state.apply_column_rename(&table, Some(&first), Some("renamed"));
+        assert_eq!(state.get_virtual_column_order()[0], "renamed");
+        assert_eq!(state.column_filters.get("renamed").map(String::as_str), Some("> 1"));
+        assert_eq!(state.selected_column.as_deref(), Some("renamed"));
+
+        state.apply_column_rename(&table, Some(&last), None);
+        assert!(!state.get_virtual_column_order().contains(&last));
+        assert!(!state.is_column_hidden_by_name(&last));
+
+        state.apply_column_rename(&table, None, Some("added"));
+        assert_eq!(state.get_virtual_column_order().last().map(String::as_str), Some("added"));
+    }
+
+    #[test]
+    fn test_filter_and_sort_quote_column_names() {
+        let conn = duckdb::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE people (id INTEGER, \"Full Name\" VARCHAR);
+             INSERT INTO people VALUES (1, 'Ada'), (2, 'Bob'), (3, 'Cy');",
+        ).unwrap();
+
+        let mut state = ApplicationState::new();
+        state.column_filters.insert("Full Name".to_string(), "<> 'Bob'".to_string());
+        state.sort_columns.push(SortColumnSpec {
+            column_name: "Full Name".to_string(),
+            direction: SortDirection::Descending,
+        });
+        let sql = format!(
+            "SELECT id FROM people {} {}",
+            state.get_filter_sql_clause(&[]).unwrap(),
+            state.get_sort_sql_clause(&[]).unwrap()
+        );
+        let ids: Vec<i32> = conn.prepare(&sql).unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(ids, vec![3, 1]);
+    }
+
+    #[test]
+    fn test_view_sql_quotes_column_names() {
+        let conn = duckdb::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE people (\"Full Name\" VARCHAR, \"v1.2\" INTEGER, \"Age\" INTEGER);
+             INSERT INTO people VALUES ('Ada', 1, 36);",
+        ).unwrap();
+
+        let mut state = ApplicationState::new();
+        state.select_table("people".to_string());
+        state.initialize_column_order(vec!["Full Name".to_string(), "v1.2".to_string(), "Age".to_string()]);
+        let sql = state.generate_view_sql("people").unwrap();
+        let row: (String, i32, i32) = conn.query_row(&sql, [], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))).unwrap();
+        assert_eq!(row, ("Ada".to_string(), 1, 36));
+    }
+
+    #[test]
+    fn test_relation_trail_goes_back() {
+        let mut state = state_with_table();
+        state.column_filters.insert("age".to_string(), "> 30".to_string());
+        state.selected_row = 0;
+
+        state.push_relation_step("users(id=1)".to_string());
+        let trail = std::mem::take(&mut state.relation_trail);
+        state.select_table("orders".to_string());
+        state.relation_trail = trail;
+        state.column_filters = [("user_id".to_string(), "= '1'".to_string())].into_iter().collect();
+        assert_eq!(state.get_relation_breadcrumb().as_deref(), Some("users(id=1) › orders"));
+
+        assert!(state.pop_relation_step());
+        assert_eq!(state.selected_table.as_deref(), Some("users"));
+        assert_eq!(state.column_filters.get("age").map(String::as_str), Some("> 30"));
+        assert!(state.get_relation_breadcrumb().is_none());
+        assert!(!state.pop_relation_step());
+    }
+
+    #[test]
+    fn test_table_rename_keeps_view_state() {
+        let mut state = state_with_table();
+        state.selected_database = Some("db".to_string());
+        state.hidden_columns.entry("users".to_string()).or_default().insert("age".to_string());
+        state.column_order.insert("users".to_string(), vec!["age".to_string(), "id".to_string(), "name".to_string()]);
+
+        state.apply_table_rename("db", "users", "people");
+        assert_eq!(state.selected_table.as_deref(), Some("people"));
+        assert!(!state.column_order.contains_key("users"));
+        assert_eq!(state.column_order["people"][0], "age");
+        assert!(state.is_column_hidden_by_name("age"));
+
+        // A rolled back rename is undone by renaming back
+        state.apply_table_rename("db", "people", "users");
+        assert_eq!(state.selected_table.as_deref(), Some("users"));
+        assert_eq!(state.column_order["users"][0], "age");
+    }
+
+    fn query_result(columns: &[&str], rows: &[&[&str]]) -> QueryResult {
+        QueryResult {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: rows.iter().map(|row| row.iter().map(|v| v.to_string()).collect()).collect(),
+            row_count: rows.len(),
+            execution_time_ms: 0,
+            null_cells: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_split_sync_by_row_and_key_column() {
+        let mut state = state_with_table();
+        state.table_data = Some(query_result(&["id", "name"], &[&["1", "a"], &["2", "b"], &["3", "c"]]));
+        state.open_new_tab();
+        state.select_table("users_staging".to_string());
+        state.table_data = Some(query_result(&["id", "name"], &[&["3", "c"], &["1", "a"]]));
+        state.prev_tab();
+
+        assert!(state.toggle_split());
+        assert_eq!(state.split_tab, Some(1));
+
+        // Row position sync clamps to the partner's row count
+        state.cycle_split_sync();
+        state.selected_row = 2;
+        state.sync_split_tab(10);
+        assert_eq!(state.tabs[1].selected_row, 1);
+
+        // Key column sync finds the row with the same key value
+        state.selected_column = Some("id".to_string());
+        state.cycle_split_sync();
+        assert_eq!(state.split_sync, SplitSyncMode::KeyColumn("id".to_string()));
+        state.sync_split_tab(10);
+        assert_eq!(state.tabs[1].selected_row, 0);
+        state.selected_row = 1;
+        state.sync_split_tab(10);
+        assert!(state.split_key_missing);
+
+        // Focusing the partner swaps the panes
+        state.switch_to_tab(1);
+        assert_eq!(state.split_tab, Some(0));
+        assert_eq!(state.selected_table.as_deref(), Some("users_staging"));
+    }
+
+    #[test]
+    fn test_selected_row_values_keep_nulls_apart() {
+        let mut state = state_with_table();
+        let mut data = query_result(&["id", "user_id", "note"], &[&["13", "Null", "Null"]]);
+        // Only user_id is NULL; note holds the text "Null"
+        data.null_cells.insert((0, 1));
+        state.table_data = Some(data);
+
+        let values = state.get_selected_row_values().unwrap();
+        assert_eq!(values["user_id"], None);
+        assert_eq!(values["note"], Some("Null".to_string()));
+    }
+
+    #[test]
+    fn test_inspect_data_reloads_for_another_table() {
+        let mut state = state_with_table();
+        assert!(!state.is_inspect_data_outdated());
+
+        state.enter_inspect_mode();
+        assert!(state.is_inspect_data_outdated());
+        state.inspect_data = Some(InspectData {
+            table_name: "users".to_string(),
+            columns: QueryResult::new(),
+            table_schema: None,
+            statistics: QueryResult::new(),
+            storage_info: None,
+        });
+        assert!(!state.is_inspect_data_outdated());
+
+        state.select_table("orders".to_string());
+        assert!(state.is_inspect_data_outdated());
+        state.exit_inspect_mode();
+        assert!(state.inspect_data.is_none() && !state.is_inspect_data_outdated());
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::schema::{quote_identifier, quote_table_name, split_table_name};
+
+/// A schema change to one column, as typed into the inspect view's alter prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnChange {
+    Rename(String),
+    ChangeType(String),
+    Add {
+        name: String,
+        data_type: String,
+        default: Option<String>,
+    },
+    Drop,
+    SetNotNull,
+    DropNotNull,
+    SetDefault(String),
+    DropDefault,
+    // None removes the comment
+    Comment(Option<String>),
+}
+
+// Shown in the prompt so the syntax doesn't need to be remembered
+pub const ALTER_SYNTAX: &str =
+    "rename <name> | type <TYPE> | add <name> <TYPE> [default <expr>] | drop | not null | null | default <expr> | no default | comment <text> | no comment";
+
+impl ColumnChange {
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (command, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+        let rest = rest.trim();
+        let lower = input.to_lowercase();
+
+        let change = match command.to_lowercase().as_str() {
+            "rename" if !rest.is_empty() => Self::Rename(rest.to_string()),
+            "type" if !rest.is_empty() => Self::ChangeType(rest.to_string()),
+            "add" => {
+                let (definition, default) = match find_ascii_keyword(rest, " default ") {
+                    Some(position) => (&rest[..position], Some(rest[position + " default ".len()..].trim().to_string())),
+                    None => (rest, None),
+                };
+                let (name, data_type) = definition.trim().split_once(char::is_whitespace)
+                    .ok_or_else(|| anyhow::anyhow!("Expected: add <name> <TYPE> [default <expr>]"))?;
+                Self::Add {
+                    name: name.to_string(),
+                    data_type: data_type.trim().to_string(),
+                    default,
+                }
+            }
+            "drop" if rest.is_empty() => Self::Drop,
+            "null" if rest.is_empty() => Self::DropNotNull,
+            "default" if !rest.is_empty() => Self::SetDefault(rest.to_string()),
+            "comment" if !rest.is_empty() => Self::Comment(Some(rest.to_string())),
+            _ if lower.split_whitespace().eq(["not", "null"]) => Self::SetNotNull,
+            _ if lower.split_whitespace().eq(["no", "default"]) => Self::DropDefault,
+            _ if lower.split_whitespace().eq(["no", "comment"]) => Self::Comment(None),
+            _ => return Err(anyhow::anyhow!("Unknown change '{input}'; expected {ALTER_SYNTAX}")),
+        };
+        Ok(change)
+    }
+
+    /// The ALTER TABLE (or COMMENT ON) statement applying this change to `column_name`
+    pub fn alter_sql(&self, table_name: &str, column_name: &str) -> String {
+        let column = quote_identifier(column_name);
+        let action = match self {
+            Self::Rename(new_name) => format!("RENAME COLUMN {column} TO {}", quote_identifier(new_name)),
+            Self::ChangeType(data_type) => format!("ALTER COLUMN {column} TYPE {data_type}"),
+            Self::Add { name, data_type, default } => {
+                let default = default.as_ref().map(|default| format!(" DEFAULT {default}")).unwrap_or_default();
+                format!("ADD COLUMN {} {data_type}{default}", quote_identifier(name))
+            }
+            Self::Drop => format!("DROP COLUMN {column}"),
+            Self::SetNotNull => format!("ALTER COLUMN {column} SET NOT NULL"),
+            Self::DropNotNull => format!("ALTER COLUMN {column} DROP NOT NULL"),
+            Self::SetDefault(default) => format!("ALTER COLUMN {column} SET DEFAULT {default}"),
+            Self::DropDefault => format!("ALTER COLUMN {column} DROP DEFAULT"),
+            Self::Comment(comment) => {
+                return format!("COMMENT ON COLUMN {}.{column} IS {}", quote_table_name(table_name), comment_literal(comment.as_deref()));
+            }
+        };
+        format!("ALTER TABLE {} {action}", quote_table_name(table_name))
+    }
+
+    /// Column names before and after the change, for keeping view state in step
+    pub fn renamed_columns(&self, column_name: &str) -> (Option<String>, Option<String>) {
+        match self {
+            Self::Rename(new_name) => (Some(column_name.to_string()), Some(new_name.clone())),
+            Self::Add { name, .. } => (None, Some(name.clone())),
+            Self::Drop => (Some(column_name.to_string()), None),
+            _ => (Some(column_name.to_string()), Some(column_name.to_string())),
+        }
+    }
+}
+
+// Byte offset of an ASCII keyword in `text`, ignoring ASCII case. Unlike searching a lowercased
+// copy, the offset is valid for `text` itself: lowercasing can change the length of other characters
+fn find_ascii_keyword(text: &str, keyword: &str) -> Option<usize> {
+    text.as_bytes()
+        .windows(keyword.len())
+        .position(|window| window.eq_ignore_ascii_case(keyword.as_bytes()))
+}
+
+/// ALTER statement renaming a table or view within its schema
+pub fn rename_relation_sql(name: &str, new_name: &str, is_view: bool) -> String {
+    let kind = if is_view { "VIEW" } else { "TABLE" };
+    let (_, new_table) = split_table_name(new_name);
+    format!("ALTER {kind} {} RENAME TO {}", quote_table_name(name), quote_identifier(&new_table))
+}
+
+/// Copy of a table or view as a new table, optionally without its rows
+pub fn duplicate_table_sql(name: &str, new_name: &str, with_data: bool) -> String {
+    let limit = if with_data { "" } else { " LIMIT 0" };
+    format!("CREATE TABLE {} AS SELECT * FROM {}{limit}", quote_table_name(new_name), quote_table_name(name))
+}
+
+pub fn truncate_table_sql(name: &str) -> String {
+    format!("TRUNCATE {}", quote_table_name(name))
+}
+
+pub fn create_schema_sql(schema_name: &str) -> String {
+    format!("CREATE SCHEMA {}", quote_table_name(schema_name))
+}
+
+/// DROP SCHEMA statement, taking the schema's tables and views with it
+pub fn drop_schema_sql(schema_name: &str) -> String {
+    format!("DROP SCHEMA {} CASCADE", quote_table_name(schema_name))
+}
+
+/// Dry run of a type change: how many non-NULL values would fail to cast
+pub fn count_cast_failures(connection: &Connection, table_name: &str, column_name: &str, data_type: &str) -> Result<usize> {
+    let column = quote_identifier(column_name);
+    let count = connection.query_row(
+        &format!(
+            "SELECT count(*) FROM {} WHERE {column} IS NOT NULL AND TRY_CAST({column} AS {data_type}) IS NULL",
+            quote_table_name(table_name)
+        ),
+        [],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(count as usize)
+}
+
+fn comment_literal(comment: Option<&str>) -> String {
+    match comment {
+        Some(comment) => format!("'{}'", comment.replace('\'', "''")),
+        None => "NULL".to_string(),
+    }
+}
+
+/// COMMENT ON statement setting or, with None, removing the comment of a table or view
+pub fn comment_on_table_sql(name: &str, is_view: bool, comment: Option<&str>) -> String {
+    let kind = if is_view { "VIEW" } else { "TABLE" };
+    format!("COMMENT ON {kind} {} IS {}", quote_table_name(name), comment_literal(comment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_column_changes() {
+        assert_eq!(ColumnChange::parse("rename full_name").unwrap(), ColumnChange::Rename("full_name".to_string()));
+        assert_eq!(ColumnChange::parse("type DECIMAL(10, 2)").unwrap(), ColumnChange::ChangeType("DECIMAL(10, 2)".to_string()));
+        assert_eq!(ColumnChange::parse("add status VARCHAR default 'new'").unwrap(), ColumnChange::Add {
+            name: "status".to_string(),
+            data_type: "VARCHAR".to_string(),
+            default: Some("'new'".to_string()),
+        });
+        // Characters whose lowercase form has a different length don't shift the default
+        assert_eq!(ColumnChange::parse("add İtem VARCHAR DEFAULT 1").unwrap(), ColumnChange::Add {
+            name: "İtem".to_string(),
+            data_type: "VARCHAR".to_string(),
+            default: Some("1".to_string()),
+        });
+        assert!(matches!(ColumnChange::parse("add İ VARCHAR default é").unwrap(), ColumnChange::Add { default: Some(d), .. } if d == "é"));
+        assert_eq!(ColumnChange::parse("NOT NULL").unwrap(), ColumnChange::SetNotNull);
+        assert_eq!(ColumnChange::parse("no default").unwrap(), ColumnChange::DropDefault);
+        assert_eq!(ColumnChange::parse("comment Net of tax").unwrap(), ColumnChange::Comment(Some("Net of tax".to_string())));
+        assert_eq!(ColumnChange::parse("no comment").unwrap(), ColumnChange::Comment(None));
+        assert!(ColumnChange::parse("rename").is_err());
+        assert!(ColumnChange::parse("explode").is_err());
+    }
+
+    #[test]
+    fn test_type_change_dry_run_and_alter() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (code VARCHAR); INSERT INTO t VALUES ('1'), ('2'), ('x'), (NULL);").unwrap();
+
+        assert_eq!(count_cast_failures(&conn, "t", "code", "INTEGER").unwrap(), 1);
+        conn.execute_batch("UPDATE t SET code = NULL WHERE code = 'x'").unwrap();
+        assert_eq!(count_cast_failures(&conn, "t", "code", "INTEGER").unwrap(), 0);
+
+        let changes = [("code", "type INTEGER"), ("code", "default 0"), ("code", "rename id"), ("id", "add note VARCHAR default 'n/a'")];
+        for (column, input) in changes {
+            let change = ColumnChange::parse(input).unwrap();
+            conn.execute_batch(&change.alter_sql("t", column)).unwrap();
+        }
+        let (data_type, note): (String, String) = conn.query_row(
+            "SELECT typeof(id), note FROM t LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!((data_type.as_str(), note.as_str()), ("INTEGER", "n/a"));
+    }
+
+    #[test]
+    fn test_rename_duplicate_and_truncate() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (id INTEGER); INSERT INTO t VALUES (1), (2); CREATE VIEW v AS SELECT * FROM t;").unwrap();
+
+        conn.execute_batch(&rename_relation_sql("v", "items_view", true)).unwrap();
+        conn.execute_batch(&duplicate_table_sql("items_view", "full_copy", true)).unwrap();
+        conn.execute_batch(&rename_relation_sql("t", "items", false)).unwrap();
+        conn.execute_batch(&duplicate_table_sql("items", "empty_copy", false)).unwrap();
+        conn.execute_batch(&truncate_table_sql("items")).unwrap();
+
+        let count = |table: &str| conn.query_row(&format!("SELECT count(*) FROM {table}"), [], |row| row.get::<_, i64>(0)).unwrap();
+        assert_eq!((count("items"), count("empty_copy"), count("full_copy")), (0, 0, 2));
+
+        conn.execute_batch("CREATE SCHEMA sales; CREATE TABLE sales.\"Order Items\" (id INTEGER); INSERT INTO sales.\"Order Items\" VALUES (1);").unwrap();
+        conn.execute_batch(&duplicate_table_sql("sales.Order Items", "sales.items_copy", true)).unwrap();
+        conn.execute_batch(&rename_relation_sql("sales.items_copy", "sales.archived", false)).unwrap();
+        assert_eq!(count("sales.archived"), 1);
+
+        conn.execute_batch(&create_schema_sql("Staging Area")).unwrap();
+        conn.execute_batch("CREATE TABLE \"Staging Area\".t (id INTEGER)").unwrap();
+        conn.execute_batch(&drop_schema_sql("Staging Area")).unwrap();
+        assert!(conn.execute_batch("SELECT * FROM \"Staging Area\".t").is_err());
+    }
+
+    #[test]
+    fn test_table_and_column_comments() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE SCHEMA sales; CREATE TABLE sales.orders (total DOUBLE); CREATE VIEW v AS SELECT 1 AS one;").unwrap();
+
+        conn.execute_batch(&comment_on_table_sql("sales.orders", false, Some("Customer's orders"))).unwrap();
+        conn.execute_batch(&comment_on_table_sql("v", true, Some("Just one"))).unwrap();
+        let change = ColumnChange::parse("comment Net of tax").unwrap();
+        conn.execute_batch(&change.alter_sql("sales.orders", "total")).unwrap();
+
+        let tables = crate::db::get_table_list(&conn).unwrap();
+        let comment = |name: &str| tables.iter().find(|table| table.name == name).unwrap().comment.clone();
+        assert_eq!(comment("sales.orders").as_deref(), Some("Customer's orders"));
+        assert_eq!(comment("v").as_deref(), Some("Just one"));
+        let column_comment = || conn.query_row(
+            "SELECT comment FROM duckdb_columns() WHERE table_name = 'orders' AND column_name = 'total'",
+            [],
+            |row| row.get::<_, Option<String>>(0),
+        ).unwrap();
+        assert_eq!(column_comment().as_deref(), Some("Net of tax"));
+
+        conn.execute_batch(&comment_on_table_sql("sales.orders", false, None)).unwrap();
+        conn.execute_batch(&ColumnChange::Comment(None).alter_sql("sales.orders", "total")).unwrap();
+        let tables = crate::db::get_table_list(&conn).unwrap();
+        assert_eq!(tables.iter().find(|table| table.name == "sales.orders").unwrap().comment, None);
+        assert_eq!(column_comment(), None);
+    }
+}
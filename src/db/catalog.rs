@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use duckdb::types::Value;
+use duckdb::Connection;
+
+use super::schema::{qualified_schema_name_sql, qualified_table_name};
+
+const USER_CATALOGS_SQL: &str =
+    "database_name NOT IN ('system', 'temp') AND schema_name NOT IN ('information_schema', 'pg_catalog')";
+
+/// Catalog objects listed in the sidebar tree besides tables and views
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CatalogObjectKind {
+    Sequence,
+    Macro,
+    Type,
+    Function,
+}
+
+impl CatalogObjectKind {
+    pub const ALL: [Self; 4] = [Self::Sequence, Self::Macro, Self::Type, Self::Function];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sequence => "Sequences",
+            Self::Macro => "Macros",
+            Self::Type => "Types",
+            Self::Function => "Functions",
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::Sequence => "[s]",
+            Self::Macro => "[m]",
+            Self::Type => "[y]",
+            Self::Function => "[f]",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogObject {
+    pub kind: CatalogObjectKind,
+    pub schema: String,
+    pub name: String,
+    // Shown after the name, e.g. a macro's parameters or a type's base type
+    pub detail: String,
+    // Shown when the object is expanded: parameters, enum labels or sequence settings
+    pub children: Vec<String>,
+}
+
+impl CatalogObject {
+    pub fn get_display_name(&self) -> String {
+        format!("{} {}{}", self.kind.icon(), self.name, self.detail)
+    }
+}
+
+fn list_strings(value: Value) -> Vec<String> {
+    match value {
+        Value::List(values) => values.into_iter()
+            .map(|value| match value {
+                Value::Text(text) => text,
+                Value::Null => "?".to_string(),
+                other => format!("{other:?}"),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Sequences, macros, user types and user functions of the current and attached databases
+pub fn load_catalog_objects(connection: &Connection) -> Result<Vec<CatalogObject>> {
+    // Schemas are named as in the sidebar: bare for the connection's own database, "alias.schema" for attached ones
+    let schema_sql = qualified_schema_name_sql();
+    let mut objects = Vec::new();
+
+    let mut stmt = connection.prepare(&format!(
+        "SELECT {schema_sql}, sequence_name, start_value, increment_by, min_value, max_value, cycle
+         FROM duckdb_sequences() WHERE {USER_CATALOGS_SQL} ORDER BY 1, 2"
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok(CatalogObject {
+            kind: CatalogObjectKind::Sequence,
+            schema: row.get(0)?,
+            name: row.get(1)?,
+            detail: String::new(),
+            children: vec![
+                format!("start {}", row.get::<_, i64>(2)?),
+                format!("increment {}", row.get::<_, i64>(3)?),
+                format!("min {}, max {}", row.get::<_, i64>(4)?, row.get::<_, i64>(5)?),
+                format!("cycle {}", if row.get::<_, bool>(6)? { "yes" } else { "no" }),
+            ],
+        })
+    })?;
+    for row in rows {
+        objects.push(row?);
+    }
+
+    let mut stmt = connection.prepare(&format!(
+        "SELECT {schema_sql}, function_name, function_type, parameters, parameter_types, return_type, macro_definition
+         FROM duckdb_functions() WHERE NOT internal AND {USER_CATALOGS_SQL} ORDER BY 1, 2"
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        let function_type: String = row.get(2)?;
+        let parameters = list_strings(row.get(3)?);
+        let parameter_types = list_strings(row.get(4)?);
+        let return_type: Option<String> = row.get(5)?;
+        let definition: Option<String> = row.get(6)?;
+
+        let is_macro = function_type.ends_with("macro");
+        let mut children: Vec<String> = if is_macro {
+            parameters.clone()
+        } else {
+            parameters.iter().zip(&parameter_types)
+                .map(|(name, data_type)| format!("{name} {data_type}"))
+                .collect()
+        };
+        if function_type == "table_macro" {
+            children.push("returns a table".to_string());
+        }
+        children.extend(definition.map(|definition| format!("AS {definition}")));
+
+        let returns = return_type.map(|return_type| format!(" → {return_type}")).unwrap_or_default();
+        Ok(CatalogObject {
+            kind: if is_macro { CatalogObjectKind::Macro } else { CatalogObjectKind::Function },
+            schema: row.get(0)?,
+            name: row.get(1)?,
+            detail: format!("({}){returns}", parameters.join(", ")),
+            children,
+        })
+    })?;
+    for row in rows {
+        objects.push(row?);
+    }
+
+    let mut stmt = connection.prepare(&format!(
+        "SELECT {schema_sql}, type_name, logical_type, labels
+         FROM duckdb_types() WHERE NOT internal AND {USER_CATALOGS_SQL} ORDER BY 1, 2"
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok(CatalogObject {
+            kind: CatalogObjectKind::Type,
+            schema: row.get(0)?,
+            name: row.get(1)?,
+            detail: format!(": {}", row.get::<_, String>(2)?),
+            children: list_strings(row.get(3)?),
+        })
+    })?;
+    for row in rows {
+        objects.push(row?);
+    }
+
+    Ok(objects)
+}
+
+/// "name TYPE" of the columns of every table and view, keyed by the table's name in the app
+pub fn load_columns(connection: &Connection) -> Result<HashMap<String, Vec<String>>> {
+    let schema_sql = qualified_schema_name_sql();
+    let mut stmt = connection.prepare(&format!(
+        "SELECT {schema_sql}, table_name, column_name, data_type
+         FROM duckdb_columns() WHERE {USER_CATALOGS_SQL} ORDER BY database_name, schema_name, table_name, column_index"
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    let mut columns: HashMap<String, Vec<String>> = HashMap::new();
+    for row in rows {
+        let (schema, table, column, data_type) = row?;
+        columns.entry(qualified_table_name(&schema, &table))
+            .or_default()
+            .push(format!("{column} {data_type}"));
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_catalog_objects_and_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE SCHEMA sales;
+             CREATE SEQUENCE sales.order_ids START 10 INCREMENT 2;
+             CREATE MACRO add_tax(amount, rate := 0.2) AS amount * (1 + rate);
+             CREATE MACRO sales.recent(n) AS TABLE SELECT * FROM range(n);
+             CREATE TYPE mood AS ENUM ('sad', 'ok, fine');
+             CREATE TABLE sales.orders (id INTEGER, mood mood);
+             CREATE VIEW big_orders AS SELECT id FROM sales.orders;",
+        ).unwrap();
+
+        let objects = load_catalog_objects(&conn).unwrap();
+        let find = |name: &str| objects.iter().find(|object| object.name == name).unwrap();
+
+        let sequence = find("order_ids");
+        assert_eq!((sequence.kind, sequence.schema.as_str()), (CatalogObjectKind::Sequence, "sales"));
+        assert_eq!(sequence.children[0], "start 10");
+        assert_eq!(find("add_tax").get_display_name(), "[m] add_tax(amount, rate)");
+        assert!(find("recent").children.contains(&"returns a table".to_string()));
+        let mood = find("mood");
+        assert_eq!(mood.kind, CatalogObjectKind::Type);
+        assert_eq!(mood.children, vec!["sad".to_string(), "ok, fine".to_string()]);
+
+        let columns = load_columns(&conn).unwrap();
+        assert_eq!(columns["sales.orders"], vec!["id INTEGER".to_string(), "mood ENUM('sad', 'ok, fine')".to_string()]);
+        assert_eq!(columns["big_orders"], vec!["id INTEGER".to_string()]);
+    }
+}
@@ -3,7 +3,10 @@ use std::path::Path;
 use anyhow::{Result, Context};
 use duckdb::Connection;
 
-use super::{DatabaseInfo, test_connection, get_table_list};
+use super::{DatabaseInfo, test_connection, get_attached_databases, get_schema_list, get_table_list};
+use super::catalog::{load_catalog_objects, load_columns};
+use super::schema::{quote_identifier, quote_table_name, split_schema_name, DEFAULT_SCHEMA};
+use super::storage::{load_table_storage, TableStorage};
 
 pub struct DatabaseManager {
     connections: HashMap<String, Connection>,
@@ -36,9 +39,16 @@ impl DatabaseManager {
         // Get table information - propagate errors to user
         let tables = get_table_list(&conn)
             .with_context(|| format!("Failed to get table list for database '{name}'"))?;
+        let schemas = get_schema_list(&conn)
+            .with_context(|| format!("Failed to get schema list for database '{name}'"))?;
         
         let mut db_info = DatabaseInfo::new(name.clone(), path.clone());
+        db_info.schemas = schemas;
         db_info.tables = tables;
+        db_info.objects = load_catalog_objects(&conn)
+            .with_context(|| format!("Failed to get catalog objects for database '{name}'"))?;
+        db_info.columns = load_columns(&conn)
+            .with_context(|| format!("Failed to get columns for database '{name}'"))?;
         
         // Store connection and database info
         self.connections.insert(name.clone(), conn);
@@ -76,7 +86,6 @@ impl DatabaseManager {
             .and_then(|name| self.connections.get(name))
     }
 
-    #[allow(dead_code)]
     pub fn get_connection(&self, name: &str) -> Option<&Connection> {
         self.connections.get(name)
     }
@@ -92,9 +101,26 @@ impl DatabaseManager {
     #[allow(dead_code)]
     pub fn refresh_database(&mut self, name: &str) -> Result<()> {
         if let Some(conn) = self.connections.get(name) {
-            let tables = get_table_list(conn)?;
+            let mut tables = get_table_list(conn)?;
+            // Sizes that were loaded before are loaded again, the rest stay unknown until asked for
+            if let Some(db_info) = self.databases.iter().find(|db| db.name == name) {
+                for table in &mut tables {
+                    if db_info.tables.iter().any(|old| old.name == table.name && old.estimated_size.is_some()) {
+                        table.estimated_size = load_table_storage(conn, &table.name).ok()
+                            .and_then(|storage| storage.estimated_bytes);
+                    }
+                }
+            }
+            let schemas = get_schema_list(conn)?;
+            let attached = get_attached_databases(conn)?;
+            let objects = load_catalog_objects(conn)?;
+            let columns = load_columns(conn)?;
             
             if let Some(db_info) = self.databases.iter_mut().find(|db| db.name == name) {
+                db_info.schemas = schemas;
+                db_info.attached = attached;
+                db_info.objects = objects;
+                db_info.columns = columns;
                 db_info.tables = tables;
             }
             
@@ -104,6 +130,48 @@ impl DatabaseManager {
         }
     }
 
+    /// Storage layout of a base table in the current database; its estimated size is kept in the
+    /// table list, which leaves sizes out
+    pub fn load_table_storage(&mut self, table_name: &str) -> Result<TableStorage> {
+        let current_db = self.current_database.clone()
+            .ok_or_else(|| anyhow::anyhow!("No current database selected"))?;
+        let conn = self.connections.get(&current_db)
+            .ok_or_else(|| anyhow::anyhow!("No connection to current database"))?;
+        let table = self.databases.iter_mut()
+            .find(|db| db.name == current_db)
+            .and_then(|db| db.tables.iter_mut().find(|table| table.name == table_name))
+            .filter(|table| !table.is_view())
+            .ok_or_else(|| anyhow::anyhow!("'{table_name}' is not a table"))?;
+        let storage = load_table_storage(conn, table_name)?;
+        table.estimated_size = storage.estimated_bytes;
+        Ok(storage)
+    }
+
+    /// ATTACH a database file into the current database's connection, so queries can join across both
+    pub fn attach_database(&mut self, path: &str, alias: &str, read_only: bool) -> Result<()> {
+        let current_db = self.current_database.clone()
+            .ok_or_else(|| anyhow::anyhow!("No current database selected"))?;
+        let conn = self.connections.get(&current_db)
+            .ok_or_else(|| anyhow::anyhow!("No connection to current database"))?;
+        let options = if read_only { " (READ_ONLY)" } else { "" };
+        conn.execute_batch(&format!(
+            "ATTACH '{}' AS {}{options}",
+            path.replace('\'', "''"),
+            quote_identifier(alias)
+        )).with_context(|| format!("Failed to attach '{path}' as '{alias}'"))?;
+        self.refresh_database(&current_db)
+    }
+
+    pub fn detach_database(&mut self, alias: &str) -> Result<()> {
+        let current_db = self.current_database.clone()
+            .ok_or_else(|| anyhow::anyhow!("No current database selected"))?;
+        let conn = self.connections.get(&current_db)
+            .ok_or_else(|| anyhow::anyhow!("No connection to current database"))?;
+        conn.execute_batch(&format!("DETACH {}", quote_identifier(alias)))
+            .with_context(|| format!("Failed to detach '{alias}'"))?;
+        self.refresh_database(&current_db)
+    }
+
     pub fn get_database_info(&self, name: &str) -> Option<&DatabaseInfo> {
         self.databases.iter().find(|db| db.name == name)
     }
@@ -141,7 +209,7 @@ impl DatabaseManager {
             let current_db_name = current_db.clone();
             if let Some(conn) = self.connections.get(&current_db_name) {
                 // Execute DROP TABLE command
-                let sql = format!("DROP TABLE IF EXISTS {table_name}");
+                let sql = format!("DROP TABLE IF EXISTS {}", quote_table_name(table_name));
                 conn.execute(&sql, [])
                     .with_context(|| format!("Failed to drop table '{table_name}'"))?;
                 
@@ -160,16 +228,11 @@ impl DatabaseManager {
     pub fn save_database_to_file(&self, database_name: &str, file_path: &str) -> Result<()> {
         if let Some(source_conn) = self.connections.get(database_name) {
             // Use DuckDB's COPY TO command which is much simpler and more efficient
-            // First get all table names using DuckDB system tables
-            let tables_query = "SHOW TABLES";
-            let mut stmt = source_conn.prepare(tables_query)?;
-            let mut rows = stmt.query([])?;
-            
-            let mut table_names = Vec::new();
-            while let Some(row) = rows.next()? {
-                let table_name: String = row.get(0)?;
-                table_names.push(table_name);
-            }
+            // First get all table names, in every schema, using DuckDB system tables
+            let table_names: Vec<String> = get_table_list(source_conn)?.into_iter()
+                .filter(|table| split_schema_name(&table.schema).0.is_none())
+                .map(|table| table.name)
+                .collect();
             
             if table_names.is_empty() {
                 return Err(anyhow::anyhow!("No tables found in database '{}'", database_name));
@@ -179,10 +242,17 @@ impl DatabaseManager {
             let target_conn = Connection::open(file_path)
                 .with_context(|| format!("Failed to create target database file '{file_path}'"))?;
             
+            let own_schemas = get_schema_list(source_conn)?.into_iter()
+                .filter(|schema| schema != DEFAULT_SCHEMA && split_schema_name(schema).0.is_none());
+            for schema in own_schemas {
+                target_conn.execute(&format!("CREATE SCHEMA IF NOT EXISTS {}", quote_identifier(&schema)), [])?;
+            }
+            
             // For each table, copy schema and data using DuckDB-specific approach
-            for table_name in table_names {
+            for (table_index, table_name) in table_names.iter().enumerate() {
+                let quoted_table = quote_table_name(table_name);
                 // Get table schema using DuckDB DESCRIBE
-                let describe_sql = format!("DESCRIBE {table_name}");
+                let describe_sql = format!("DESCRIBE {quoted_table}");
                 let mut describe_stmt = source_conn.prepare(&describe_sql)?;
                 let mut describe_rows = describe_stmt.query([])?;
                 
@@ -191,7 +261,7 @@ impl DatabaseManager {
                     let col_name: String = row.get(0)?; // column_name
                     let col_type: String = row.get(1)?; // column_type
                     // DuckDB DESCRIBE gives us: column_name, column_type, null, key, default, extra
-                    let col_def = format!("{col_name} {col_type}");
+                    let col_def = format!("{} {col_type}", quote_identifier(&col_name));
                     columns.push(col_def);
                 }
                 
@@ -199,21 +269,21 @@ impl DatabaseManager {
                     // Create table in target database
                     let create_table_sql = format!(
                         "CREATE TABLE {} ({})", 
-                        table_name, 
+                        quoted_table, 
                         columns.join(", ")
                     );
                     target_conn.execute(&create_table_sql, [])?;
                     
                     // Copy data using DuckDB's efficient bulk copy
                     // Export to CSV temporarily then import
-                    let temp_csv = format!("/tmp/ducky_export_{table_name}.csv");
+                    let temp_csv = format!("/tmp/ducky_export_{}_{table_index}.csv", std::process::id());
                     
                     // Export table to CSV
-                    let copy_to_sql = format!("COPY {table_name} TO '{temp_csv}' (FORMAT CSV, HEADER)");
+                    let copy_to_sql = format!("COPY {quoted_table} TO '{temp_csv}' (FORMAT CSV, HEADER)");
                     source_conn.execute(&copy_to_sql, [])?;
                     
                     // Import CSV to target database
-                    let copy_from_sql = format!("COPY {table_name} FROM '{temp_csv}' (FORMAT CSV, HEADER)");
+                    let copy_from_sql = format!("COPY {quoted_table} FROM '{temp_csv}' (FORMAT CSV, HEADER)");
                     target_conn.execute(&copy_from_sql, [])?;
                     
                     // Clean up temporary file
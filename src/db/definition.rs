@@ -0,0 +1,259 @@
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::catalog::CatalogObjectKind;
+use super::schema::{quote_table_name, split_schema_name, split_table_name};
+
+/// Catalog objects whose CREATE statement can be shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionKind {
+    Table,
+    View,
+    Macro,
+    Sequence,
+}
+
+impl DefinitionKind {
+    pub fn from_catalog_kind(kind: CatalogObjectKind) -> Option<Self> {
+        match kind {
+            CatalogObjectKind::Macro => Some(Self::Macro),
+            CatalogObjectKind::Sequence => Some(Self::Sequence),
+            CatalogObjectKind::Type | CatalogObjectKind::Function => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Table => "table",
+            Self::View => "view",
+            Self::Macro => "macro",
+            Self::Sequence => "sequence",
+        }
+    }
+}
+
+/// The CREATE statement of a table, view, macro or sequence named as in the app ("schema.name").
+/// Tables and views come from the catalog's `sql` column; macros and sequences are rebuilt from
+/// their catalog entries, so macro parameter defaults are not included.
+pub fn load_definition(conn: &Connection, kind: DefinitionKind, name: &str) -> Result<String> {
+    let (schema, object_name) = split_table_name(name);
+    let (catalog, schema_name) = split_schema_name(schema);
+    let params = duckdb::params![catalog, schema_name, object_name];
+    let filter = "database_name = coalesce(?, current_database()) AND schema_name = ?";
+
+    let sql = match kind {
+        DefinitionKind::Table => conn.query_row(
+            &format!("SELECT sql FROM duckdb_tables() WHERE {filter} AND table_name = ?"),
+            params,
+            |row| row.get::<_, String>(0),
+        )?,
+        DefinitionKind::View => conn.query_row(
+            &format!("SELECT sql FROM duckdb_views() WHERE {filter} AND view_name = ?"),
+            params,
+            |row| row.get::<_, String>(0),
+        )?,
+        DefinitionKind::Macro => {
+            let (function_type, parameters, definition): (String, String, String) = conn.query_row(
+                &format!(
+                    "SELECT function_type, array_to_string(parameters, ', '), macro_definition
+                     FROM duckdb_functions() WHERE {filter} AND function_name = ?"
+                ),
+                params,
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?;
+            let table = if function_type == "table_macro" { "TABLE " } else { "" };
+            format!("CREATE MACRO {}({parameters}) AS {table}{definition};", quote_table_name(name))
+        }
+        DefinitionKind::Sequence => {
+            let (start, increment, min, max, cycle): (i64, i64, i64, i64, bool) = conn.query_row(
+                &format!(
+                    "SELECT start_value, increment_by, min_value, max_value, cycle
+                     FROM duckdb_sequences() WHERE {filter} AND sequence_name = ?"
+                ),
+                params,
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            )?;
+            let cycle = if cycle { "CYCLE" } else { "NO CYCLE" };
+            format!(
+                "CREATE SEQUENCE {} INCREMENT BY {increment} MINVALUE {min} MAXVALUE {max} START {start} {cycle};",
+                quote_table_name(name)
+            )
+        }
+    };
+    Ok(sql)
+}
+
+/// Turns a view's CREATE VIEW statement into a CREATE OR REPLACE VIEW of `view_name`, for editing.
+/// A column alias list (`CREATE VIEW v (x, y) AS ...`) is kept so the columns keep their names
+pub fn replace_view_sql(view_name: &str, create_sql: &str) -> String {
+    let view_name = quote_table_name(view_name);
+    let mut column_list = None;
+    for (position, c, depth) in unquoted_chars(create_sql) {
+        if depth > 0 {
+            continue;
+        }
+        if c == '(' && column_list.is_none() {
+            column_list = Some(position);
+        }
+        // The AS keyword, in any case, separating the header from the query
+        let rest = &create_sql[position..];
+        let starts_word = create_sql[..position].ends_with(|c: char| c.is_whitespace() || c == ')');
+        let is_as = rest.get(..2).is_some_and(|word| word.eq_ignore_ascii_case("AS"))
+            && rest[2..].starts_with(char::is_whitespace);
+        if starts_word && is_as {
+            let columns = column_list.map(|start| format!(" {}", create_sql[start..position].trim())).unwrap_or_default();
+            return format!("CREATE OR REPLACE VIEW {view_name}{columns} AS {}", rest[2..].trim());
+        }
+    }
+    format!("CREATE OR REPLACE VIEW {view_name} AS {}", create_sql.trim())
+}
+
+/// The statement on one line for the single-line query editor. Line breaks become spaces outside
+/// string literals and quoted names, whose text is kept as is, and `--` comments become `/* */`
+/// comments so they don't swallow the rest of the statement
+pub fn single_line_sql(sql: &str) -> String {
+    let mut line = String::new();
+    let mut in_comment = false;
+    let mut chars = sql.chars().peekable();
+    let mut quote: Option<char> = None;
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(open), _) => {
+                line.push(c);
+                if c == open {
+                    quote = None;
+                }
+            }
+            (None, '\n' | '\r') if in_comment => {
+                line.push_str(" */ ");
+                in_comment = false;
+            }
+            (None, '\n' | '\r') => line.push(' '),
+            (None, '*') if in_comment && chars.peek() == Some(&'/') => line.push_str("* "),
+            (None, _) if in_comment => line.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                line.push(c);
+            }
+            (None, '-') if chars.peek() == Some(&'-') => {
+                chars.next();
+                line.push_str("/*");
+                in_comment = true;
+            }
+            (None, _) => line.push(c),
+        }
+    }
+    if in_comment {
+        line.push_str(" */");
+    }
+    line
+}
+
+// Characters of `sql` outside string literals and quoted names, with their byte offset and
+// parenthesis depth
+fn unquoted_chars(sql: &str) -> impl Iterator<Item = (usize, char, usize)> + '_ {
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    sql.char_indices().filter_map(move |(position, c)| {
+        if let Some(open) = quote {
+            if c == open {
+                quote = None;
+            }
+            return None;
+        }
+        match c {
+            '\'' | '"' => {
+                quote = Some(c);
+                None
+            }
+            '(' => {
+                depth += 1;
+                Some((position, c, depth - 1))
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                Some((position, c, depth))
+            }
+            _ => Some((position, c, depth)),
+        }
+    })
+}
+
+/// Whether a statement changes the catalog rather than returning rows
+pub fn is_ddl_statement(sql: &str) -> bool {
+    let first_word = sql.split_whitespace().next().unwrap_or_default().to_uppercase();
+    matches!(first_word.as_str(), "CREATE" | "ALTER" | "DROP" | "COMMENT")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_definitions_and_recreate_them() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE SCHEMA sales;
+             CREATE TABLE sales.\"Order Items\" (id INTEGER PRIMARY KEY, note VARCHAR DEFAULT 'n/a');
+             CREATE VIEW sales.big AS SELECT id FROM sales.\"Order Items\" WHERE id > 10;
+             CREATE MACRO sales.add_tax(amount, rate) AS amount * (1 + rate);
+             CREATE MACRO first_n(n) AS TABLE SELECT * FROM range(n);
+             CREATE SEQUENCE sales.ids START 5 INCREMENT 2;",
+        ).unwrap();
+
+        let table = load_definition(&conn, DefinitionKind::Table, "sales.Order Items").unwrap();
+        assert!(table.starts_with("CREATE TABLE sales.\"Order Items\""));
+        let view = load_definition(&conn, DefinitionKind::View, "sales.big").unwrap();
+        assert!(view.starts_with("CREATE VIEW"));
+        let add_tax = load_definition(&conn, DefinitionKind::Macro, "sales.add_tax").unwrap();
+        assert_eq!(add_tax, "CREATE MACRO \"sales\".\"add_tax\"(amount, rate) AS (amount * (1 + rate));");
+        let first_n = load_definition(&conn, DefinitionKind::Macro, "first_n").unwrap();
+        assert!(first_n.contains("AS TABLE SELECT"));
+        let ids = load_definition(&conn, DefinitionKind::Sequence, "sales.ids").unwrap();
+        assert!(ids.contains("INCREMENT BY 2") && ids.contains("START 5"));
+
+        // Each definition recreates its object in a fresh database
+        let copy = Connection::open_in_memory().unwrap();
+        copy.execute_batch("CREATE SCHEMA sales;").unwrap();
+        for sql in [&table, &view, &add_tax, &first_n, &ids] {
+            copy.execute_batch(sql).unwrap();
+        }
+
+        let edited = replace_view_sql("sales.big", &view).replace("id > 10", "id > 20");
+        assert!(edited.starts_with("CREATE OR REPLACE VIEW \"sales\".\"big\" AS SELECT"));
+        assert!(is_ddl_statement(&edited));
+        assert!(!is_ddl_statement("SELECT 1"));
+        conn.execute_batch(&edited).unwrap();
+        assert!(load_definition(&conn, DefinitionKind::View, "sales.big").unwrap().contains("20"));
+    }
+
+    #[test]
+    fn test_replace_view_keeps_column_aliases() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("create view \"a as b\" (x, y) as select 1, 'as' as z;").unwrap();
+        let view = load_definition(&conn, DefinitionKind::View, "a as b").unwrap();
+
+        let replaced = replace_view_sql("a as b", &view);
+        assert!(replaced.starts_with("CREATE OR REPLACE VIEW \"a as b\" (x, y) AS"), "{replaced}");
+        conn.execute_batch(&replaced).unwrap();
+        let names: String = conn.query_row(
+            "SELECT string_agg(column_name, ',') FROM duckdb_columns() WHERE table_name = 'a as b'",
+            [],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(names, "x,y");
+        assert_eq!(replace_view_sql("v", "create view v as select 1"), "CREATE OR REPLACE VIEW \"v\" AS select 1");
+    }
+
+    #[test]
+    fn test_single_line_sql_keeps_literals() {
+        let sql = "SELECT * -- all columns\nFROM t\nWHERE note = 'a  b\nc' AND \"x  y\" > 1 -- last */ one";
+        let line = single_line_sql(sql);
+        assert_eq!(line, "SELECT * /* all columns */ FROM t WHERE note = 'a  b\nc' AND \"x  y\" > 1 /* last * / one */");
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (note VARCHAR, \"x  y\" INTEGER); INSERT INTO t VALUES ('a  b\nc', 2);").unwrap();
+        let count: i64 = conn.query_row(&format!("SELECT count(*) FROM ({})", line), [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+    }
+}
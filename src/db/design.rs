@@ -0,0 +1,208 @@
+use anyhow::Result;
+
+use super::schema::{quote_identifier, quote_table_name};
+
+/// Types offered by the table designer, cycled with left/right
+pub const COLUMN_TYPES: &[&str] = &[
+    "INTEGER", "BIGINT", "SMALLINT", "TINYINT", "HUGEINT", "DOUBLE", "FLOAT", "DECIMAL",
+    "BOOLEAN", "VARCHAR", "DATE", "TIME", "TIMESTAMP", "TIMESTAMPTZ", "INTERVAL", "UUID",
+    "BLOB", "ENUM", "LIST", "STRUCT",
+];
+
+/// One column of a table being designed
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ColumnDesign {
+    pub name: String,
+    pub type_index: usize,
+    /// Precision/scale for DECIMAL, values for ENUM, element type for LIST, fields for STRUCT
+    pub type_argument: String,
+    pub not_null: bool,
+    pub default: String,
+    pub primary_key: bool,
+    pub unique: bool,
+}
+
+impl ColumnDesign {
+    pub fn type_name(&self) -> &'static str {
+        COLUMN_TYPES[self.type_index % COLUMN_TYPES.len()]
+    }
+
+    pub fn cycle_type(&mut self, forward: bool) {
+        let len = COLUMN_TYPES.len();
+        self.type_index = if forward {
+            (self.type_index + 1) % len
+        } else {
+            (self.type_index + len - 1) % len
+        };
+    }
+
+    /// Hint for the type argument field, if the type takes one
+    pub fn type_argument_hint(&self) -> Option<&'static str> {
+        match self.type_name() {
+            "DECIMAL" => Some("precision, scale (default 18, 3)"),
+            "ENUM" => Some("comma-separated values"),
+            "LIST" => Some("element type, e.g. INTEGER"),
+            "STRUCT" => Some("fields, e.g. x DOUBLE, y DOUBLE"),
+            _ => None,
+        }
+    }
+
+    /// The full DuckDB type, built from the chosen type and its argument
+    pub fn data_type(&self) -> Result<String> {
+        let argument = self.type_argument.trim();
+        let type_name = self.type_name();
+        let data_type = match type_name {
+            "DECIMAL" if argument.is_empty() => "DECIMAL(18, 3)".to_string(),
+            "DECIMAL" => {
+                let parts = argument.split(',')
+                    .map(|part| part.trim().parse::<u8>())
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|_| anyhow::anyhow!("DECIMAL expects 'precision' or 'precision, scale'"))?;
+                let (precision, scale) = match parts[..] {
+                    [precision] => (precision, 0),
+                    [precision, scale] => (precision, scale),
+                    _ => return Err(anyhow::anyhow!("DECIMAL expects 'precision' or 'precision, scale'")),
+                };
+                // DuckDB's limits, checked here so the designer reports them before CREATE TABLE
+                if !(1..=38).contains(&precision) {
+                    return Err(anyhow::anyhow!("DECIMAL precision must be between 1 and 38"));
+                }
+                if scale > precision {
+                    return Err(anyhow::anyhow!("DECIMAL scale can't exceed the precision"));
+                }
+                format!("DECIMAL({precision}, {scale})")
+            }
+            "ENUM" => {
+                let values: Vec<String> = argument.split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| format!("'{}'", value.replace('\'', "''")))
+                    .collect();
+                if values.is_empty() {
+                    return Err(anyhow::anyhow!("ENUM needs at least one value"));
+                }
+                format!("ENUM({})", values.join(", "))
+            }
+            "LIST" if argument.is_empty() => return Err(anyhow::anyhow!("LIST needs an element type")),
+            "LIST" => format!("{argument}[]"),
+            "STRUCT" => {
+                let fields = argument.split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(|field| field.split_once(char::is_whitespace)
+                        .map(|(name, field_type)| format!("{} {}", quote_identifier(name), field_type.trim()))
+                        .ok_or_else(|| anyhow::anyhow!("STRUCT field '{field}' needs a name and a type")))
+                    .collect::<Result<Vec<_>>>()?;
+                if fields.is_empty() {
+                    return Err(anyhow::anyhow!("STRUCT needs at least one field"));
+                }
+                format!("STRUCT({})", fields.join(", "))
+            }
+            _ => type_name.to_string(),
+        };
+        Ok(data_type)
+    }
+
+    /// Column definition for CREATE TABLE; primary keys are added as a table constraint
+    pub fn definition(&self) -> Result<String> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Every column needs a name"));
+        }
+        let mut definition = format!("{} {}", quote_identifier(self.name.trim()), self.data_type()?);
+        if self.not_null && !self.primary_key {
+            definition.push_str(" NOT NULL");
+        }
+        if !self.default.trim().is_empty() {
+            definition.push_str(&format!(" DEFAULT {}", self.default.trim()));
+        }
+        if self.unique && !self.primary_key {
+            definition.push_str(" UNIQUE");
+        }
+        Ok(definition)
+    }
+}
+
+/// The CREATE TABLE statement for a designed table
+pub fn create_table_sql(table_name: &str, columns: &[ColumnDesign]) -> Result<String> {
+    if columns.is_empty() {
+        return Err(anyhow::anyhow!("Add at least one column"));
+    }
+    let mut names = std::collections::HashSet::new();
+    if let Some(column) = columns.iter().find(|column| !names.insert(column.name.trim().to_lowercase())) {
+        return Err(anyhow::anyhow!("Column '{}' is defined twice", column.name.trim()));
+    }
+
+    let mut lines = columns.iter()
+        .map(ColumnDesign::definition)
+        .collect::<Result<Vec<_>>>()?;
+    let primary_key: Vec<String> = columns.iter()
+        .filter(|column| column.primary_key)
+        .map(|column| quote_identifier(column.name.trim()))
+        .collect();
+    if !primary_key.is_empty() {
+        lines.push(format!("PRIMARY KEY ({})", primary_key.join(", ")));
+    }
+
+    Ok(format!("CREATE TABLE {} (\n    {}\n)", quote_table_name(table_name.trim()), lines.join(",\n    ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::Connection;
+
+    fn column(name: &str, type_name: &str, type_argument: &str) -> ColumnDesign {
+        ColumnDesign {
+            name: name.to_string(),
+            type_index: COLUMN_TYPES.iter().position(|t| *t == type_name).unwrap(),
+            type_argument: type_argument.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_designed_table_is_created() {
+        let mut id = column("id", "INTEGER", "");
+        id.primary_key = true;
+        let mut price = column("price", "DECIMAL", "10, 2");
+        price.not_null = true;
+        price.default = "0".to_string();
+        let mut sku = column("sku", "VARCHAR", "");
+        sku.unique = true;
+        let columns = vec![
+            id,
+            price,
+            sku,
+            column("status", "ENUM", "new, it's done"),
+            column("tags", "LIST", "VARCHAR"),
+            column("point", "STRUCT", "x DOUBLE, y DOUBLE"),
+        ];
+
+        let sql = create_table_sql("products", &columns).unwrap();
+        assert!(sql.starts_with("CREATE TABLE \"products\" ("));
+        assert!(sql.contains("\"price\" DECIMAL(10, 2) NOT NULL DEFAULT 0"));
+        assert!(sql.contains("ENUM('new', 'it''s done')"));
+        assert!(sql.contains("PRIMARY KEY (\"id\")"));
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&sql).unwrap();
+        conn.execute_batch("INSERT INTO products (id, sku, status, tags, point) VALUES (1, 'a', 'new', ['x'], {'x': 1, 'y': 2})").unwrap();
+        assert!(conn.execute_batch("INSERT INTO products (id, sku) VALUES (2, 'a')").is_err());
+        let price: String = conn.query_row("SELECT CAST(price AS VARCHAR) FROM products", [], |row| row.get(0)).unwrap();
+        assert_eq!(price, "0.00");
+    }
+
+    #[test]
+    fn test_invalid_designs_are_rejected() {
+        assert!(create_table_sql("t", &[]).is_err());
+        assert!(create_table_sql("t", &[column("a", "INTEGER", ""), column("A", "VARCHAR", "")]).is_err());
+        assert!(column("d", "DECIMAL", "ten").data_type().is_err());
+        assert!(column("d", "DECIMAL", "0").data_type().is_err());
+        assert!(column("d", "DECIMAL", "39, 2").data_type().is_err());
+        assert!(column("d", "DECIMAL", "4, 5").data_type().is_err());
+        assert_eq!(column("d", "DECIMAL", "38").data_type().unwrap(), "DECIMAL(38, 0)");
+        assert!(column("e", "ENUM", " , ").data_type().is_err());
+        assert!(column("s", "STRUCT", "x").data_type().is_err());
+        assert!(column("", "INTEGER", "").definition().is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::schema::quote_identifier;
+
+/// Row counts shown above a diff result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiffSummary {
+    pub only_left: usize,
+    pub only_right: usize,
+    pub changed: usize,
+}
+
+impl DiffSummary {
+    pub fn describe(&self) -> String {
+        format!(
+            "only in left: {} | only in right: {} | changed: {}",
+            self.only_left, self.only_right, self.changed
+        )
+    }
+}
+
+/// A diff shown in a viewer tab, with the names it was requested under
+#[derive(Debug, Clone)]
+pub struct DiffResult {
+    pub left: String,
+    pub right: String,
+    pub summary: DiffSummary,
+}
+
+/// Row-level comparison of two relations matched on key columns.
+///
+/// Everything runs as SQL on the DuckDB side, so only the differing rows
+/// are ever loaded into the viewer.
+#[derive(Debug, Clone)]
+pub struct TableDiff {
+    pub left: String,
+    pub right: String,
+    pub keys: Vec<String>,
+    // Non-key columns present in both relations
+    pub compare_columns: Vec<String>,
+}
+
+// Column names added to the diff result
+pub const DIFF_STATUS_COLUMN: &str = "diff_status";
+pub const CHANGED_COLUMNS_COLUMN: &str = "changed_columns";
+pub const ONLY_LEFT: &str = "only in left";
+pub const ONLY_RIGHT: &str = "only in right";
+pub const CHANGED: &str = "changed";
+
+fn relation_columns(connection: &Connection, relation: &str) -> Result<Vec<String>> {
+    let mut stmt = connection.prepare(&format!("SELECT * FROM {relation} LIMIT 0"))?;
+    let _rows = stmt.query([])?;
+    Ok((0..stmt.column_count())
+        .map(|i| stmt.column_name(i).map(|name| name.to_string()).unwrap_or_default())
+        .collect())
+}
+
+impl TableDiff {
+    /// Prepare a diff, checking that every key column exists on both sides
+    pub fn new(connection: &Connection, left: &str, right: &str, keys: Vec<String>) -> Result<Self> {
+        if keys.is_empty() {
+            return Err(anyhow::anyhow!("At least one key column is required"));
+        }
+
+        let left_columns = relation_columns(connection, left)
+            .map_err(|e| anyhow::anyhow!("Cannot read '{left}': {e}"))?;
+        let right_columns = relation_columns(connection, right)
+            .map_err(|e| anyhow::anyhow!("Cannot read '{right}': {e}"))?;
+
+        for key in &keys {
+            if !left_columns.contains(key) || !right_columns.contains(key) {
+                return Err(anyhow::anyhow!("Key column '{key}' must exist in both relations"));
+            }
+        }
+
+        let compare_columns = left_columns.into_iter()
+            .filter(|name| right_columns.contains(name) && !keys.contains(name))
+            .collect();
+
+        Ok(Self {
+            left: left.to_string(),
+            right: right.to_string(),
+            keys,
+            compare_columns,
+        })
+    }
+
+    /// Split "left right key1,key2" into its parts
+    pub fn parse_input(input: &str) -> Result<(String, String, Vec<String>)> {
+        let mut parts = input.split_whitespace();
+        let (Some(left), Some(right)) = (parts.next(), parts.next()) else {
+            return Err(anyhow::anyhow!("Expected: <left> <right> <key columns>"));
+        };
+        let keys: Vec<String> = parts
+            .flat_map(|part| part.split(','))
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect();
+        if keys.is_empty() {
+            return Err(anyhow::anyhow!("At least one key column is required"));
+        }
+        Ok((left.to_string(), right.to_string(), keys))
+    }
+
+    fn key_list(&self) -> String {
+        self.keys.iter().map(|key| quote_identifier(key)).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Query returning every row that is missing on one side or has differing values.
+    ///
+    /// Columns: diff_status, the keys, changed_columns, then `<column>_left` and
+    /// `<column>_right` for every compared column.
+    pub fn diff_sql(&self) -> String {
+        let mut select = vec![format!(
+            "CASE WHEN r.__diff_in_right IS NULL THEN '{ONLY_LEFT}' \
+             WHEN l.__diff_in_left IS NULL THEN '{ONLY_RIGHT}' \
+             ELSE '{CHANGED}' END AS {DIFF_STATUS_COLUMN}"
+        )];
+
+        for key in &self.keys {
+            let key = quote_identifier(key);
+            select.push(format!("COALESCE(l.{key}, r.{key}) AS {key}"));
+        }
+
+        // Only rows present on both sides can have changed cells
+        let changed_checks: Vec<String> = self.compare_columns.iter()
+            .map(|column| {
+                let quoted = quote_identifier(column);
+                format!(
+                    "CASE WHEN l.__diff_in_left AND r.__diff_in_right AND l.{quoted} IS DISTINCT FROM r.{quoted} THEN '{}' END",
+                    column.replace('\'', "''")
+                )
+            })
+            .collect();
+        if changed_checks.is_empty() {
+            select.push(format!("'' AS {CHANGED_COLUMNS_COLUMN}"));
+        } else {
+            select.push(format!("concat_ws(', ', {}) AS {CHANGED_COLUMNS_COLUMN}", changed_checks.join(", ")));
+        }
+
+        for column in &self.compare_columns {
+            let quoted = quote_identifier(column);
+            select.push(format!("l.{quoted} AS {}", quote_identifier(&format!("{column}_left"))));
+            select.push(format!("r.{quoted} AS {}", quote_identifier(&format!("{column}_right"))));
+        }
+
+        let join_condition = self.keys.iter()
+            .map(|key| {
+                let key = quote_identifier(key);
+                format!("l.{key} IS NOT DISTINCT FROM r.{key}")
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let mut differs = vec![
+            "r.__diff_in_right IS NULL".to_string(),
+            "l.__diff_in_left IS NULL".to_string(),
+        ];
+        differs.extend(self.compare_columns.iter().map(|column| {
+            let quoted = quote_identifier(column);
+            format!("l.{quoted} IS DISTINCT FROM r.{quoted}")
+        }));
+
+        format!(
+            "WITH l AS (SELECT *, true AS __diff_in_left FROM {}), \
+             r AS (SELECT *, true AS __diff_in_right FROM {}) \
+             SELECT {} FROM l FULL OUTER JOIN r ON {} WHERE {} ORDER BY {DIFF_STATUS_COLUMN}, {}",
+            self.left,
+            self.right,
+            select.join(", "),
+            join_condition,
+            differs.join(" OR "),
+            self.key_list()
+        )
+    }
+
+    /// Count missing keys with EXCEPT and changed rows from the diff query
+    pub fn summary(&self, connection: &Connection) -> Result<DiffSummary> {
+        let keys = self.key_list();
+        let sql = format!(
+            "SELECT \
+             (SELECT count(*) FROM (SELECT {keys} FROM {left} EXCEPT SELECT {keys} FROM {right})), \
+             (SELECT count(*) FROM (SELECT {keys} FROM {right} EXCEPT SELECT {keys} FROM {left})), \
+             (SELECT count(*) FROM ({diff}) WHERE {DIFF_STATUS_COLUMN} = '{CHANGED}')",
+            left = self.left,
+            right = self.right,
+            diff = self.diff_sql(),
+        );
+
+        let (only_left, only_right, changed) = connection.query_row(&sql, [], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?))
+        })?;
+
+        Ok(DiffSummary {
+            only_left: only_left as usize,
+            only_right: only_right as usize,
+            changed: changed as usize,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE prod (id INTEGER, name VARCHAR, qty INTEGER);
+             CREATE TABLE staging (id INTEGER, name VARCHAR, qty INTEGER, note VARCHAR);
+             INSERT INTO prod VALUES (1, 'a', 10), (2, 'b', 20), (3, 'c', NULL), (4, 'd', 40);
+             INSERT INTO staging VALUES (1, 'a', 10, 'x'), (2, 'B', 21, 'y'), (3, 'c', NULL, 'z'), (5, 'e', 50, 'w');",
+        ).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_diff_summary_and_rows() {
+        let conn = setup();
+        let diff = TableDiff::new(&conn, "prod", "staging", vec!["id".to_string()]).unwrap();
+        assert_eq!(diff.compare_columns, vec!["name", "qty"]);
+
+        let summary = diff.summary(&conn).unwrap();
+        assert_eq!(summary, DiffSummary { only_left: 1, only_right: 1, changed: 1 });
+
+        let mut stmt = conn.prepare(&diff.diff_sql()).unwrap();
+        let rows: Vec<(String, i32, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+        assert_eq!(rows, vec![
+            (CHANGED.to_string(), 2, "name, qty".to_string()),
+            (ONLY_LEFT.to_string(), 4, String::new()),
+            (ONLY_RIGHT.to_string(), 5, String::new()),
+        ]);
+    }
+
+    #[test]
+    fn test_diff_rejects_missing_key() {
+        let conn = setup();
+        assert!(TableDiff::new(&conn, "prod", "staging", vec!["note".to_string()]).is_err());
+        assert!(TableDiff::parse_input("prod staging").is_err());
+        let (left, right, keys) = TableDiff::parse_input("prod staging id, name").unwrap();
+        assert_eq!((left.as_str(), right.as_str()), ("prod", "staging"));
+        assert_eq!(keys, vec!["id", "name"]);
+    }
+}
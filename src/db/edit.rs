@@ -0,0 +1,351 @@
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::query::{ColumnInfo, QueryResult};
+use super::schema::{quote_identifier, quote_table_name, split_schema_name, split_table_name};
+
+// Prefix for the hidden key columns appended to viewer queries
+const KEY_COLUMN_PREFIX: &str = "__ducky_key_";
+
+/// How rows of a table are addressed for updates: its primary key, or DuckDB's `rowid`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowKey {
+    pub columns: Vec<String>,
+    pub types: Vec<String>,
+}
+
+impl RowKey {
+    pub fn for_table(connection: &Connection, table_name: &str) -> Result<Self> {
+        let (schema, table_name) = split_table_name(table_name);
+        let (catalog, schema) = split_schema_name(schema);
+        let mut stmt = connection.prepare(
+            "SELECT c.column_name, c.data_type
+             FROM (SELECT unnest(constraint_column_names) AS column_name
+                   FROM duckdb_constraints()
+                   WHERE database_name = coalesce(?, current_database()) AND schema_name = ? AND table_name = ?
+                     AND constraint_type = 'PRIMARY KEY') k
+             JOIN duckdb_columns() c
+               ON c.database_name = coalesce(?, current_database()) AND c.schema_name = ? AND c.table_name = ?
+              AND c.column_name = k.column_name",
+        )?;
+        let key_columns: Vec<(String, String)> = stmt
+            .query_map(
+                duckdb::params![catalog, schema, table_name, catalog, schema, table_name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?
+            .collect::<std::result::Result<_, _>>()?;
+
+        if key_columns.is_empty() {
+            return Ok(Self::rowid());
+        }
+        let (columns, types) = key_columns.into_iter().unzip();
+        Ok(Self { columns, types })
+    }
+
+    pub fn rowid() -> Self {
+        Self {
+            columns: vec!["rowid".to_string()],
+            types: vec!["BIGINT".to_string()],
+        }
+    }
+
+    pub fn is_rowid(&self) -> bool {
+        self.columns == ["rowid"]
+    }
+
+    /// Extra select items that load the key of every row as text
+    pub fn select_sql(&self) -> String {
+        self.columns.iter().enumerate()
+            .map(|(i, column)| {
+                let column = if self.is_rowid() { column.clone() } else { quote_identifier(column) };
+                format!(", CAST({column} AS VARCHAR) AS {KEY_COLUMN_PREFIX}{i}")
+            })
+            .collect()
+    }
+
+    /// Remove the key columns added by `select_sql` from a result, returning them per row
+    pub fn split_from(&self, data: &mut QueryResult) -> Vec<Vec<String>> {
+        let visible = data.columns.len().saturating_sub(self.columns.len());
+        data.columns.truncate(visible);
+        data.rows.iter_mut()
+            .map(|row| row.split_off(visible.min(row.len())))
+            .collect()
+    }
+
+    fn conditions(&self, mut value_sql: impl FnMut(usize) -> String) -> String {
+        self.columns.iter().zip(&self.types).enumerate()
+            .map(|(i, (column, data_type))| {
+                let column = if self.is_rowid() { column.clone() } else { quote_identifier(column) };
+                format!("{column} = CAST({} AS {data_type})", value_sql(i))
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+
+    /// WHERE condition matching one row; binds one parameter per key column
+    pub fn where_sql(&self) -> String {
+        self.conditions(|_| "?".to_string())
+    }
+
+    /// Same condition as `where_sql` with the values inlined as string literals
+    pub fn literal_where_sql(&self, values: &[String]) -> String {
+        self.conditions(|i| {
+            let value = values.get(i).map(String::as_str).unwrap_or_default();
+            format!("'{}'", value.replace('\'', "''"))
+        })
+    }
+
+    /// RETURNING clause giving the key of an inserted row; rowid can't be returned
+    pub fn returning_sql(&self) -> Option<String> {
+        if self.is_rowid() {
+            return None;
+        }
+        let columns = self.columns.iter()
+            .map(|column| format!("CAST({} AS VARCHAR)", quote_identifier(column)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(format!(" RETURNING {columns}"))
+    }
+
+    pub fn describe(&self, values: &[String]) -> String {
+        self.columns.iter().zip(values)
+            .map(|(column, value)| format!("{column}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Whether the connection's current database was opened read-only
+pub fn is_read_only(connection: &Connection) -> Result<bool> {
+    Ok(connection.query_row(
+        "SELECT readonly FROM duckdb_databases() WHERE database_name = current_database()",
+        [],
+        |row| row.get::<_, bool>(0),
+    )?)
+}
+
+pub fn column_type(connection: &Connection, table_name: &str, column_name: &str) -> Result<String> {
+    let (schema, table) = split_table_name(table_name);
+    let (catalog, schema) = split_schema_name(schema);
+    connection.query_row(
+        "SELECT data_type FROM duckdb_columns()
+         WHERE database_name = coalesce(?, current_database()) AND schema_name = ? AND table_name = ? AND column_name = ?",
+        duckdb::params![catalog, schema, table, column_name],
+        |row| row.get::<_, String>(0),
+    ).map_err(|_| anyhow::anyhow!("Column '{column_name}' not found in '{table_name}'"))
+}
+
+/// Check that a value (None meaning NULL) can be stored in a column of the given type
+pub fn validate_value(connection: &Connection, value: Option<&str>, data_type: &str) -> Result<()> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    let valid = connection.query_row(
+        &format!("SELECT TRY_CAST(? AS {data_type}) IS NOT NULL"),
+        [value],
+        |row| row.get::<_, bool>(0),
+    )?;
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("'{value}' is not a valid {data_type}"))
+    }
+}
+
+/// Parse what was typed into an edit prompt; `NULL` (any case) clears the cell
+pub fn parse_input_value(input: &str) -> Option<String> {
+    if input.eq_ignore_ascii_case("null") {
+        None
+    } else {
+        Some(input.to_string())
+    }
+}
+
+/// SQL and parameters for setting one cell of the row identified by `key_values`
+pub fn update_cell_sql(
+    table_name: &str,
+    column_name: &str,
+    data_type: &str,
+    key: &RowKey,
+    key_values: &[String],
+    value: Option<&str>,
+) -> (String, Vec<Option<String>>) {
+    let sql = format!(
+        "UPDATE {} SET {} = CAST(? AS {data_type}) WHERE {}",
+        quote_table_name(table_name),
+        quote_identifier(column_name),
+        key.where_sql()
+    );
+    let mut params = vec![value.map(str::to_string)];
+    params.extend(key_values.iter().cloned().map(Some));
+    (sql, params)
+}
+
+/// SQL and parameters inserting one row; columns without a value keep their default
+pub fn insert_row_sql(table_name: &str, values: &[(&ColumnInfo, Option<String>)]) -> (String, Vec<Option<String>>) {
+    if values.is_empty() {
+        return (format!("INSERT INTO {} DEFAULT VALUES", quote_table_name(table_name)), Vec::new());
+    }
+    let columns = values.iter()
+        .map(|(column, _)| quote_identifier(&column.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let placeholders = values.iter()
+        .map(|(column, _)| format!("CAST(? AS {})", column.data_type))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!("INSERT INTO {} ({columns}) VALUES ({placeholders})", quote_table_name(table_name));
+    (sql, values.iter().map(|(_, value)| value.clone()).collect())
+}
+
+/// SQL deleting the row identified by one set of key values (bound as parameters)
+pub fn delete_row_sql(table_name: &str, key: &RowKey) -> String {
+    format!("DELETE FROM {} WHERE {}", quote_table_name(table_name), key.where_sql())
+}
+
+/// Count of the rows a bulk change with this filter (a `WHERE ...` clause) would touch
+pub fn count_matching_sql(table_name: &str, filter_clause: &str) -> String {
+    format!("SELECT count(*) FROM {} {filter_clause}", quote_table_name(table_name))
+}
+
+/// Set a column to an SQL expression on every row matching the filter
+pub fn bulk_update_sql(table_name: &str, column_name: &str, expression: &str, filter_clause: &str) -> String {
+    format!(
+        "UPDATE {} SET {} = ({expression}) {filter_clause}",
+        quote_table_name(table_name),
+        quote_identifier(column_name)
+    )
+}
+
+pub fn bulk_delete_sql(table_name: &str, filter_clause: &str) -> String {
+    format!("DELETE FROM {} {filter_clause}", quote_table_name(table_name))
+}
+
+/// Replace the `?` placeholders of a statement with literals, for showing staged SQL
+pub fn inline_params(sql: &str, params: &[Option<String>]) -> String {
+    let mut result = String::with_capacity(sql.len());
+    let mut params = params.iter();
+    let mut quote: Option<char> = None;
+    for c in sql.chars() {
+        match (c, quote) {
+            ('?', None) => match params.next() {
+                Some(Some(value)) => result.push_str(&format!("'{}'", value.replace('\'', "''"))),
+                _ => result.push_str("NULL"),
+            },
+            ('\'' | '"', None) => {
+                quote = Some(c);
+                result.push(c);
+            }
+            (c, Some(open)) if c == open => {
+                quote = None;
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_by_primary_key_and_rowid() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE keyed (id INTEGER PRIMARY KEY, name VARCHAR, qty INTEGER);
+             CREATE TABLE plain (name VARCHAR, qty INTEGER);
+             INSERT INTO keyed VALUES (1, 'a', 10), (2, 'b', 20);
+             INSERT INTO plain VALUES ('a', 10), ('b', 20);",
+        ).unwrap();
+
+        let key = RowKey::for_table(&conn, "keyed").unwrap();
+        assert_eq!(key.columns, vec!["id"]);
+        let data_type = column_type(&conn, "keyed", "qty").unwrap();
+        assert!(validate_value(&conn, Some("abc"), &data_type).is_err());
+        validate_value(&conn, Some("25"), &data_type).unwrap();
+
+        let (sql, params) = update_cell_sql("keyed", "qty", &data_type, &key, &["2".to_string()], Some("25"));
+        assert_eq!(conn.execute(&sql, duckdb::params_from_iter(params)).unwrap(), 1);
+        let qty: i32 = conn.query_row("SELECT qty FROM keyed WHERE id = 2", [], |row| row.get(0)).unwrap();
+        assert_eq!(qty, 25);
+
+        // Tables without a primary key are addressed by rowid, loaded alongside the rows
+        let key = RowKey::for_table(&conn, "plain").unwrap();
+        assert!(key.is_rowid());
+        let mut stmt = conn.prepare(&format!("SELECT name{} FROM plain ORDER BY name DESC", key.select_sql())).unwrap();
+        let mut data = QueryResult::new();
+        data.columns = vec!["name".to_string(), "__ducky_key_0".to_string()];
+        data.rows = stmt.query_map([], |row| Ok(vec![row.get(0)?, row.get(1)?])).unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+        let keys = key.split_from(&mut data);
+        assert_eq!(data.columns, vec!["name"]);
+        assert_eq!(data.rows[0], vec!["b"]);
+
+        let (sql, params) = update_cell_sql("plain", "name", "VARCHAR", &key, &keys[0], parse_input_value("NULL").as_deref());
+        assert_eq!(conn.execute(&sql, duckdb::params_from_iter(params)).unwrap(), 1);
+        let nulls: i64 = conn.query_row("SELECT count(*) FROM plain WHERE name IS NULL AND qty = 20", [], |row| row.get(0)).unwrap();
+        assert_eq!(nulls, 1);
+    }
+
+    #[test]
+    fn test_insert_with_defaults_and_delete_by_key() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE items (id INTEGER PRIMARY KEY, name VARCHAR NOT NULL, qty INTEGER DEFAULT 7)").unwrap();
+        let columns = crate::db::query::QueryExecutor::new(conn.try_clone().unwrap())
+            .get_table_columns("items")
+            .unwrap();
+        assert!(!columns[1].is_nullable);
+        assert_eq!(columns[2].default_value.as_deref(), Some("7"));
+
+        // qty is left out so its default applies
+        let values = vec![(&columns[0], Some("1".to_string())), (&columns[1], Some("bolt".to_string()))];
+        let (sql, params) = insert_row_sql("items", &values);
+        conn.execute(&sql, duckdb::params_from_iter(params)).unwrap();
+        let qty: i32 = conn.query_row("SELECT qty FROM items WHERE id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(qty, 7);
+
+        let key = RowKey::for_table(&conn, "items").unwrap();
+        assert_eq!(conn.execute(&delete_row_sql("items", &key), ["1"]).unwrap(), 1);
+        let count: i64 = conn.query_row("SELECT count(*) FROM items", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_bulk_changes_only_touch_filtered_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE items (id INTEGER, qty INTEGER);
+             INSERT INTO items VALUES (1, 5), (2, 15), (3, 25);",
+        ).unwrap();
+        let filter = "WHERE qty > 10";
+
+        let matching: i64 = conn.query_row(&count_matching_sql("items", filter), [], |row| row.get(0)).unwrap();
+        assert_eq!(matching, 2);
+        assert_eq!(conn.execute(&bulk_update_sql("items", "qty", "qty * 2", filter), []).unwrap(), 2);
+        let total: i64 = conn.query_row("SELECT sum(qty) FROM items", [], |row| row.get(0)).unwrap();
+        assert_eq!(total, 5 + 30 + 50);
+
+        assert_eq!(conn.execute(&bulk_delete_sql("items", filter), []).unwrap(), 2);
+        let remaining: i64 = conn.query_row("SELECT count(*) FROM items", [], |row| row.get(0)).unwrap();
+        assert_eq!(remaining, 1);
+    }
+
+    #[test]
+    fn test_inline_params_skips_quoted_text() {
+        let sql = "UPDATE \"what?\" SET \"a\" = CAST(? AS VARCHAR) WHERE \"id\" = CAST(? AS INTEGER)";
+        assert_eq!(
+            inline_params(sql, &[Some("it's".to_string()), Some("3".to_string())]),
+            "UPDATE \"what?\" SET \"a\" = CAST('it''s' AS VARCHAR) WHERE \"id\" = CAST('3' AS INTEGER)"
+        );
+        assert_eq!(inline_params("SET x = ?", &[None]), "SET x = NULL");
+    }
+
+    #[test]
+    fn test_in_memory_database_is_writable() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert!(!is_read_only(&conn).unwrap());
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::schema::{qualified_table_name, quote_identifier, quote_table_name, split_table_name, IndexInfo};
+
+pub fn create_index_sql(index_name: &str, table_name: &str, columns: &[String], unique: bool) -> String {
+    let unique = if unique { "UNIQUE " } else { "" };
+    let columns: Vec<String> = columns.iter().map(|column| quote_identifier(column)).collect();
+    format!(
+        "CREATE {unique}INDEX {} ON {} ({})",
+        quote_identifier(index_name),
+        quote_table_name(table_name),
+        columns.join(", ")
+    )
+}
+
+/// Indexes live in their table's schema, so the name is qualified with it
+pub fn drop_index_sql(index_name: &str, table_name: &str) -> String {
+    let (schema, _) = split_table_name(table_name);
+    format!("DROP INDEX {}", quote_table_name(&qualified_table_name(schema, index_name)))
+}
+
+/// Split the comma-separated column list typed into the create-index form
+pub fn parse_index_columns(input: &str) -> Vec<String> {
+    input.split(',')
+        .map(str::trim)
+        .filter(|column| !column.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Columns an index is built on, from duckdb_indexes().expressions (e.g. `[id, "Name"]`)
+pub fn index_columns(index: &IndexInfo) -> Vec<String> {
+    index.expressions.trim_start_matches('[').trim_end_matches(']')
+        .split(',')
+        .map(|column| column.trim().trim_matches('"').to_string())
+        .filter(|column| !column.is_empty())
+        .collect()
+}
+
+/// EXPLAIN the query and describe whether its filters can use the table's indexes
+pub fn index_usage_hints(connection: &Connection, select_sql: &str, filtered_columns: &[String], indexes: &[IndexInfo]) -> Result<Vec<String>> {
+    let mut stmt = connection.prepare(&format!("EXPLAIN {select_sql}"))?;
+    let plan = stmt.query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<Vec<_>, _>>()?
+        .join("\n");
+
+    let mut hints = Vec::new();
+    if plan.contains("Index Scan") {
+        hints.push("The current filters use an index scan".to_string());
+        return Ok(hints);
+    }
+    if filtered_columns.is_empty() {
+        hints.push("No filters applied; a full scan is expected".to_string());
+        return Ok(hints);
+    }
+    for column in filtered_columns {
+        let covering: Vec<&str> = indexes.iter()
+            .filter(|index| index_columns(index).first() == Some(column))
+            .map(|index| index.name.as_str())
+            .collect();
+        if covering.is_empty() {
+            hints.push(format!("'{column}' is filtered but not indexed; a = create an index on it"));
+        } else {
+            hints.push(format!(
+                "'{column}' is indexed by {} but the plan scans sequentially; ART indexes only serve selective equality/IN filters",
+                covering.join(", ")
+            ));
+        }
+    }
+    Ok(hints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::{TableInfo, TableSchema};
+
+    #[test]
+    fn test_create_drop_and_hint_indexes() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (id INTEGER, \"Code\" VARCHAR, n INTEGER)").unwrap();
+
+        conn.execute_batch(&create_index_sql("t_code", "t", &parse_index_columns("Code, id"), true)).unwrap();
+        conn.execute_batch(&create_index_sql("t_n", "t", &parse_index_columns("n"), false)).unwrap();
+        let indexes = TableSchema::load(&conn, TableInfo::new("t".to_string())).unwrap().indexes;
+        assert_eq!(indexes.len(), 2);
+        assert!(indexes[0].is_unique);
+        assert_eq!(index_columns(&indexes[0]), vec!["Code".to_string(), "id".to_string()]);
+
+        let hints = index_usage_hints(&conn, "SELECT * FROM t WHERE id > 1", &["id".to_string()], &indexes).unwrap();
+        assert!(hints[0].contains("not indexed"));
+
+        conn.execute_batch(&drop_index_sql("t_n", "t")).unwrap();
+        let indexes = TableSchema::load(&conn, TableInfo::new("t".to_string())).unwrap().indexes;
+        assert_eq!(indexes.len(), 1);
+
+        // Indexes outside the default schema are dropped from their table's schema
+        conn.execute_batch("CREATE SCHEMA s; CREATE TABLE s.\"v1.2\" (id INTEGER);").unwrap();
+        let table = qualified_table_name("s", "v1.2");
+        conn.execute_batch(&create_index_sql("s_id", &table, &parse_index_columns("id"), false)).unwrap();
+        conn.execute_batch(&drop_index_sql("s_id", &table)).unwrap();
+    }
+}
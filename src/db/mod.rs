@@ -1,12 +1,24 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
 use duckdb::Connection;
 
+pub mod alter;
+pub mod catalog;
 pub mod connection;
+pub mod definition;
+pub mod design;
+pub mod diff;
+pub mod edit;
+pub mod index;
 pub mod query;
+pub mod relations;
 pub mod schema;
+pub mod schema_diff;
+pub mod storage;
 
 pub use connection::DatabaseManager;
-pub use schema::TableInfo;
+pub use schema::{TableInfo, TableSchema};
 
 #[derive(Debug, Clone)]
 pub struct DatabaseInfo {
@@ -14,7 +26,12 @@ pub struct DatabaseInfo {
     #[allow(dead_code)]
     pub path: String,
     pub is_memory: bool,
+    pub schemas: Vec<String>,
+    pub attached: Vec<AttachedDatabase>,
     pub tables: Vec<TableInfo>,
+    pub objects: Vec<catalog::CatalogObject>,
+    // Columns of every table and view, for expanding them in the catalog tree
+    pub columns: HashMap<String, Vec<String>>,
 }
 
 impl DatabaseInfo {
@@ -24,7 +41,11 @@ impl DatabaseInfo {
             name,
             path,
             is_memory,
+            schemas: Vec::new(),
+            attached: Vec::new(),
             tables: Vec::new(),
+            objects: Vec::new(),
+            columns: HashMap::new(),
         }
     }
 }
@@ -73,29 +94,45 @@ pub fn get_database_version(conn: &Connection) -> Result<String> {
     Ok(version)
 }
 
-// TODO: Add support for views in addition to tables
-// This will be a key feature for virtual data exploration and should integrate
-// seamlessly with the column reordering system since it works at the query level
+/// Tables and views of the current database, then of attached databases
 pub fn get_table_list(conn: &Connection) -> Result<Vec<TableInfo>> {
+    // Tables of attached databases follow, with their schema prefixed by the catalog alias
     let mut stmt = conn.prepare(
-        "SELECT table_name, table_type 
-         FROM information_schema.tables 
-         WHERE table_schema = 'main'
-         ORDER BY table_name"
+        "SELECT t.table_catalog, t.table_catalog = current_database() AS is_own, t.table_schema, t.table_name, t.table_type, d.estimated_size,
+                coalesce(d.comment, v.comment)
+         FROM information_schema.tables t
+         LEFT JOIN duckdb_tables() d
+           ON d.database_name = t.table_catalog AND d.schema_name = t.table_schema AND d.table_name = t.table_name
+         LEFT JOIN duckdb_views() v
+           ON v.database_name = t.table_catalog AND v.schema_name = t.table_schema AND v.view_name = t.table_name
+         WHERE t.table_catalog NOT IN ('system', 'temp')
+           AND t.table_schema NOT IN ('information_schema', 'pg_catalog')
+         ORDER BY NOT is_own, t.table_catalog, t.table_schema <> 'main', t.table_schema, t.table_name"
     )?;
     
     let rows = stmt.query_map([], |row| {
-        let table_name: String = row.get(0)?;
-        let table_type: String = row.get(1)?;
+        let catalog: String = row.get(0)?;
+        let is_own: bool = row.get(1)?;
+        let table_schema: String = row.get(2)?;
+        let table_name: String = row.get(3)?;
+        let table_type: String = row.get(4)?;
+        // duckdb_tables() counts rows for base tables only
+        let estimated_rows: Option<i64> = row.get(5)?;
+        let comment = row.get::<_, Option<String>>(6)?.filter(|comment| !comment.is_empty());
         
         // Get column count from information_schema.columns
-        let column_count = get_column_count(conn, &table_name).unwrap_or(0);
+        let column_count = get_column_count(conn, &catalog, &table_schema, &table_name).unwrap_or(0);
+        let schema = schema::qualified_schema_name((!is_own).then_some(catalog.as_str()), &table_schema);
         
         Ok(TableInfo {
-            name: table_name,
+            name: schema::qualified_table_name(&schema, &table_name),
+            schema,
             table_type,
             column_count,
-            estimated_size: None, // DuckDB doesn't provide this in information_schema
+            // Reading storage segments is slow, so sizes are loaded per table on demand
+            estimated_size: None,
+            estimated_rows,
+            comment,
         })
     })?;
     
@@ -107,12 +144,52 @@ pub fn get_table_list(conn: &Connection) -> Result<Vec<TableInfo>> {
     Ok(tables)
 }
 
-fn get_column_count(conn: &Connection, table_name: &str) -> Result<i32> {
+/// Schemas of the current database, the default schema first, then those of attached databases
+/// as "alias.schema"
+pub fn get_schema_list(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
+         FROM duckdb_schemas()
+         WHERE database_name NOT IN ('system', 'temp') AND schema_name NOT IN ('information_schema', 'pg_catalog')
+         ORDER BY database_name <> current_database(), database_name, schema_name <> 'main', schema_name",
+        schema::qualified_schema_name_sql()
+    ))?;
+    let schemas = stmt.query_map([], |row| row.get::<_, String>(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(schemas)
+}
+
+/// A database file ATTACHed into another database's connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachedDatabase {
+    pub alias: String,
+    pub path: String,
+    pub read_only: bool,
+}
+
+pub fn get_attached_databases(conn: &Connection) -> Result<Vec<AttachedDatabase>> {
     let mut stmt = conn.prepare(
-        "SELECT COUNT(*) FROM information_schema.columns WHERE table_name = ? AND table_schema = 'main'"
+        "SELECT database_name, coalesce(path, ''), readonly FROM duckdb_databases()
+         WHERE NOT internal AND database_name <> current_database()
+         ORDER BY database_name"
+    )?;
+    let attached = stmt.query_map([], |row| {
+        Ok(AttachedDatabase {
+            alias: row.get(0)?,
+            path: row.get(1)?,
+            read_only: row.get(2)?,
+        })
+    })?.collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(attached)
+}
+
+fn get_column_count(conn: &Connection, catalog: &str, schema: &str, table_name: &str) -> Result<i32> {
+    let mut stmt = conn.prepare(
+        "SELECT COUNT(*) FROM information_schema.columns
+         WHERE table_catalog = ? AND table_schema = ? AND table_name = ?"
     )?;
     
-    let count = stmt.query_row([table_name], |row| {
+    let count = stmt.query_row([catalog, schema, table_name], |row| {
         Ok(row.get::<_, i64>(0)? as i32)
     })?;
     
@@ -154,6 +231,104 @@ mod tests {
         assert_eq!(tables[0].name, "test_table");
         assert_eq!(tables[0].table_type, "BASE TABLE");
         assert_eq!(tables[0].column_count, 2, "Should have 2 columns");
+        assert_eq!(tables[0].estimated_rows, Some(0));
+        assert_eq!(tables[0].get_storage_summary().as_deref(), Some("0 rows"));
+    }
+
+    #[test]
+    fn test_get_table_list_across_schemas() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE SCHEMA sales;
+             CREATE SCHEMA empty;
+             CREATE TABLE sales.orders (id INTEGER, total DOUBLE, note VARCHAR);
+             CREATE TABLE users (id INTEGER);",
+        ).unwrap();
+
+        let tables = get_table_list(&conn).unwrap();
+        let names: Vec<&str> = tables.iter().map(|table| table.name.as_str()).collect();
+        assert_eq!(names, vec!["users", "sales.orders"]);
+        assert_eq!(tables[1].schema, "sales");
+        assert_eq!(tables[1].table_name(), "orders");
+        assert_eq!(tables[1].column_count, 3);
+
+        assert_eq!(get_schema_list(&conn).unwrap(), vec!["main", "empty", "sales"]);
+    }
+
+    #[test]
+    fn test_attach_and_detach_database() {
+        let path = std::env::temp_dir().join(format!("ducky_attach_test_{}.duckdb", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let file_conn = Connection::open(&path).unwrap();
+            file_conn.execute_batch(
+                "CREATE TABLE items (id INTEGER PRIMARY KEY, name VARCHAR);
+                 INSERT INTO items VALUES (1, 'bolt'), (2, 'nut');",
+            ).unwrap();
+        }
+
+        let mut db_manager = DatabaseManager::new();
+        db_manager.add_database("memory".to_string(), ":memory:".to_string()).unwrap();
+        db_manager.set_current_database("memory").unwrap();
+        db_manager.get_current_connection().unwrap()
+            .execute_batch("CREATE TABLE orders (item_id INTEGER, qty INTEGER); INSERT INTO orders VALUES (1, 5)")
+            .unwrap();
+
+        db_manager.attach_database(&path.to_string_lossy(), "ref", true).unwrap();
+        let info = db_manager.get_database_info("memory").unwrap();
+        let names: Vec<&str> = info.tables.iter().map(|table| table.name.as_str()).collect();
+        assert_eq!(names, vec!["orders", "ref.main.items"]);
+        assert_eq!(info.schemas, vec!["main", "ref.main"]);
+        assert!(info.attached[0].read_only);
+
+        let conn = db_manager.get_current_connection().unwrap();
+        let items = TableSchema::load(conn, info.tables[1].clone()).unwrap();
+        assert!(items.column("id").unwrap().is_primary_key);
+        let name: String = conn.query_row(
+            &format!("SELECT name FROM orders JOIN {} ON id = item_id", schema::quote_table_name("ref.main.items")),
+            [],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(name, "bolt");
+        assert!(conn.execute_batch("INSERT INTO ref.items VALUES (3, 'washer')").is_err());
+
+        db_manager.detach_database("ref").unwrap();
+        assert_eq!(db_manager.get_database_info("memory").unwrap().tables.len(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_table_sizes_load_on_demand() {
+        let path = std::env::temp_dir().join(format!("ducky_size_test_{}.duckdb", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let file_conn = Connection::open(&path).unwrap();
+            file_conn.execute_batch(
+                "CREATE TABLE big AS SELECT range AS id FROM range(100000);
+                 CREATE TABLE small (id INTEGER);
+                 CREATE VIEW v AS SELECT * FROM big;
+                 CHECKPOINT;",
+            ).unwrap();
+        }
+
+        let mut db_manager = DatabaseManager::new();
+        db_manager.add_database("file".to_string(), path.to_string_lossy().to_string()).unwrap();
+        db_manager.set_current_database("file").unwrap();
+        let size = |db_manager: &DatabaseManager, name: &str| db_manager.get_database_info("file").unwrap()
+            .tables.iter().find(|table| table.name == name).unwrap().estimated_size;
+        assert_eq!(size(&db_manager, "big"), None);
+
+        db_manager.load_table_storage("big").unwrap();
+        assert!(size(&db_manager, "big").is_some_and(|bytes| bytes > 0));
+        assert!(db_manager.load_table_storage("v").is_err());
+
+        // A refresh keeps the sizes that were asked for, and only those
+        db_manager.refresh_database("file").unwrap();
+        assert!(size(&db_manager, "big").is_some());
+        assert_eq!(size(&db_manager, "small"), None);
+
+        drop(db_manager);
+        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
@@ -208,6 +383,50 @@ mod tests {
         assert_eq!(databases.len(), 1, "Should have one database after creation");
         assert!(databases[0].is_memory, "Created database should be in-memory");
     }
+
+    #[test]
+    fn test_transaction_mode_stages_edits() {
+        use crate::app::state::ApplicationState;
+        use crate::db::edit::RowKey;
+        use crate::workflows::DatabaseWorkflows;
+
+        let mut db_manager = DatabaseManager::new();
+        db_manager.add_database("staging".to_string(), ":memory:".to_string()).unwrap();
+        db_manager.set_current_database("staging").unwrap();
+        db_manager.get_current_connection().unwrap()
+            .execute_batch("CREATE TABLE items (id INTEGER PRIMARY KEY, qty INTEGER); INSERT INTO items VALUES (1, 10)")
+            .unwrap();
+
+        let mut action_logger = ActionLogger::new().unwrap();
+        let mut state = ApplicationState::new();
+        state.selected_database = Some("staging".to_string());
+        state.selected_table = Some("items".to_string());
+        state.row_key = Some(RowKey::for_table(db_manager.get_current_connection().unwrap(), "items").unwrap());
+        state.row_key_values = vec![vec!["1".to_string()]];
+
+        let mut workflows = DatabaseWorkflows::new(&mut db_manager, &mut action_logger, &mut state);
+        workflows.begin_transaction().unwrap();
+        workflows.update_cell(0, "qty".to_string(), Some("11".to_string())).unwrap();
+        let transaction = workflows.state.transaction.clone().unwrap();
+        assert_eq!(transaction.statements, vec![
+            "UPDATE \"items\" SET \"qty\" = CAST('11' AS INTEGER) WHERE \"id\" = CAST('1' AS INTEGER)".to_string()
+        ]);
+        assert!(workflows.state.is_cell_modified(0, "qty"));
+
+        workflows.rollback_transaction().unwrap();
+        let qty: i32 = workflows.database_manager.get_current_connection().unwrap()
+            .query_row("SELECT qty FROM items WHERE id = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(qty, 10);
+
+        // A failing change aborts the transaction, which then refuses to commit
+        workflows.begin_transaction().unwrap();
+        workflows.update_cell(0, "id".to_string(), None).unwrap_err();
+        assert!(workflows.state.transaction.as_ref().unwrap().aborted);
+        assert!(workflows.commit_transaction().is_err());
+        workflows.rollback_transaction().unwrap();
+        assert!(workflows.state.transaction.is_none());
+    }
 }
 
 #[allow(dead_code)]
@@ -3,7 +3,9 @@
 use anyhow::Result;
 use duckdb::{Connection, Row};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use super::schema::{quote_identifier, quote_table_name, split_schema_name, split_table_name};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryResult {
@@ -11,6 +13,9 @@ pub struct QueryResult {
     pub rows: Vec<Vec<String>>,
     pub row_count: usize,
     pub execution_time_ms: u64,
+    // (row, column) of NULL cells, whose text is only a display spelling
+    #[serde(default)]
+    pub null_cells: HashSet<(usize, usize)>,
 }
 
 impl QueryResult {
@@ -20,8 +25,13 @@ impl QueryResult {
             rows: Vec::new(),
             row_count: 0,
             execution_time_ms: 0,
+            null_cells: HashSet::new(),
         }
     }
+
+    pub fn is_null(&self, row: usize, column: usize) -> bool {
+        self.null_cells.contains(&(row, column))
+    }
 }
 
 pub struct QueryExecutor {
@@ -69,6 +79,7 @@ impl QueryExecutor {
             row_count: result_rows.len(),
             rows: result_rows,
             execution_time_ms: execution_time.as_millis() as u64,
+            null_cells: HashSet::new(),
         })
     }
 
@@ -83,7 +94,7 @@ impl QueryExecutor {
     }
 
     pub fn get_table_preview(&self, table_name: &str, limit: usize) -> Result<QueryResult> {
-        let sql = format!("SELECT * FROM {table_name} LIMIT {limit}");
+        let sql = format!("SELECT * FROM {} LIMIT {limit}", quote_table_name(table_name));
         self.execute_query(&sql)
     }
 
@@ -106,28 +117,31 @@ impl QueryExecutor {
             return self.get_table_preview(table_name, limit);
         }
 
-        let columns_sql = ordered_columns.join(", ");
-        let sql = format!("SELECT {columns_sql} FROM {table_name} LIMIT {limit}");
+        let columns_sql = ordered_columns.iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!("SELECT {columns_sql} FROM {} LIMIT {limit}", quote_table_name(table_name));
         self.execute_query(&sql)
     }
 
     pub fn get_table_count(&self, table_name: &str) -> Result<i64> {
-        let sql = format!("SELECT COUNT(*) FROM {table_name}");
+        let sql = format!("SELECT COUNT(*) FROM {}", quote_table_name(table_name));
         let mut stmt = self.connection.prepare(&sql)?;
         let count = stmt.query_row([], |row| row.get::<_, i64>(0))?;
         Ok(count)
     }
 
     pub fn get_table_columns(&self, table_name: &str) -> Result<Vec<ColumnInfo>> {
-        let sql = format!(
-            "SELECT column_name, data_type, is_nullable, column_default 
+        let (schema, table_name) = split_table_name(table_name);
+        let (catalog, schema) = split_schema_name(schema);
+        let sql = "SELECT column_name, data_type, is_nullable, column_default 
              FROM information_schema.columns 
-             WHERE table_name = '{table_name}' 
-             ORDER BY ordinal_position"
-        );
+             WHERE table_catalog = coalesce(?, current_database()) AND table_schema = ? AND table_name = ? 
+             ORDER BY ordinal_position";
         
-        let mut stmt = self.connection.prepare(&sql)?;
-        let rows = stmt.query_map([], |row| {
+        let mut stmt = self.connection.prepare(sql)?;
+        let rows = stmt.query_map(duckdb::params![catalog, schema, table_name], |row| {
             Ok(ColumnInfo {
                 name: row.get::<_, String>(0)?,
                 data_type: row.get::<_, String>(1)?,
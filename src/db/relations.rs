@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::schema::{qualified_table_name, quote_identifier, quote_table_name, split_schema_name, split_table_name, ForeignKey};
+
+/// A foreign key of another table pointing at the inspected table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingReference {
+    pub table_name: String,
+    pub foreign_key: ForeignKey,
+}
+
+/// Foreign keys in the table's schema that reference `table_name`
+pub fn incoming_references(connection: &Connection, table_name: &str) -> Result<Vec<IncomingReference>> {
+    let (schema, referenced_table) = split_table_name(table_name);
+    let (catalog, schema_name) = split_schema_name(schema);
+    let mut stmt = connection.prepare(
+        "SELECT table_name, constraint_index, unnest(constraint_column_names), unnest(referenced_column_names)
+         FROM duckdb_constraints()
+         WHERE database_name = coalesce(?, current_database()) AND schema_name = ?
+           AND constraint_type = 'FOREIGN KEY' AND referenced_table = ?
+         ORDER BY table_name, constraint_index",
+    )?;
+    let rows = stmt.query_map(duckdb::params![catalog, schema_name, referenced_table], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    let mut references: Vec<IncomingReference> = Vec::new();
+    let mut last_constraint = None;
+    for row in rows {
+        let (child_table, index, column, referenced_column) = row?;
+        let constraint = (child_table.clone(), index);
+        if last_constraint.as_ref() != Some(&constraint) {
+            references.push(IncomingReference {
+                table_name: qualified_table_name(schema, &child_table),
+                foreign_key: ForeignKey {
+                    columns: Vec::new(),
+                    referenced_table: table_name.to_string(),
+                    referenced_columns: Vec::new(),
+                },
+            });
+            last_constraint = Some(constraint);
+        }
+        if let Some(reference) = references.last_mut() {
+            reference.foreign_key.columns.push(column);
+            reference.foreign_key.referenced_columns.push(referenced_column);
+        }
+    }
+    Ok(references)
+}
+
+/// Viewer column filters selecting the rows whose `columns` equal `values`;
+/// None when any value is NULL, as NULL never matches a key
+pub fn key_filters(columns: &[String], values: &[Option<String>]) -> Option<HashMap<String, String>> {
+    columns.iter().zip(values)
+        .map(|(column, value)| {
+            value.as_ref().map(|value| (column.clone(), format!("= '{}'", value.replace('\'', "''"))))
+        })
+        .collect()
+}
+
+/// Rows of `table_name` matching the key filters
+pub fn count_matching_rows(connection: &Connection, table_name: &str, filters: &HashMap<String, String>) -> Result<usize> {
+    let conditions: Vec<String> = filters.iter()
+        .map(|(column, filter)| format!("{} {filter}", quote_identifier(column)))
+        .collect();
+    let count = connection.query_row(
+        &format!("SELECT count(*) FROM {} WHERE {}", quote_table_name(table_name), conditions.join(" AND ")),
+        [],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(count as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incoming_references_and_key_filters() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, \"Full Name\" VARCHAR);
+             CREATE TABLE orders (id INTEGER, user_id INTEGER REFERENCES users(id));
+             INSERT INTO users VALUES (1, 'ann'), (2, 'o''hara');
+             INSERT INTO orders VALUES (10, 1), (11, 1), (12, 2), (13, NULL);",
+        ).unwrap();
+
+        let references = incoming_references(&conn, "users").unwrap();
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].table_name, "orders");
+        assert_eq!(references[0].foreign_key.columns, vec!["user_id".to_string()]);
+        assert!(incoming_references(&conn, "orders").unwrap().is_empty());
+
+        let filters = key_filters(&references[0].foreign_key.columns, &[Some("1".to_string())]).unwrap();
+        assert_eq!(count_matching_rows(&conn, "orders", &filters).unwrap(), 2);
+        let filters = key_filters(&["Full Name".to_string()], &[Some("o'hara".to_string())]).unwrap();
+        assert_eq!(count_matching_rows(&conn, "users", &filters).unwrap(), 1);
+
+        // A NULL foreign key references no row
+        assert!(key_filters(&["user_id".to_string()], &[None]).is_none());
+        // The text "NULL" is an ordinary value
+        assert!(key_filters(&["name".to_string()], &[Some("NULL".to_string())]).is_some());
+    }
+}
@@ -1,22 +1,136 @@
 #![allow(dead_code)] // Phase 2+ features - Visual Data Explorer
 
+use std::borrow::Cow;
+
+use anyhow::Result;
+use duckdb::Connection;
 use serde::{Deserialize, Serialize};
 
+/// Schema whose tables are listed by their bare names
+pub const DEFAULT_SCHEMA: &str = "main";
+
+/// Split a table name as used across the app ("orders", "sales.orders" or, for an attached
+/// database, "ref.main.items") into schema and table. Parts containing dots are double-quoted
+/// in app names (see `qualified_table_name`), so only dots outside quotes separate parts
+pub fn split_table_name(name: &str) -> (&str, Cow<'_, str>) {
+    match separator_positions(name).last() {
+        Some(&position) => (&name[..position], unquote_part(&name[position + 1..])),
+        None => (DEFAULT_SCHEMA, unquote_part(name)),
+    }
+}
+
+/// Split a schema into the attached catalog it belongs to, None for the connection's own
+/// database, and the schema name within that catalog
+pub fn split_schema_name(schema: &str) -> (Option<Cow<'_, str>>, Cow<'_, str>) {
+    match separator_positions(schema).first() {
+        Some(&position) => (Some(unquote_part(&schema[..position])), unquote_part(&schema[position + 1..])),
+        None => (None, unquote_part(schema)),
+    }
+}
+
+/// The app's name for a table: bare in the default schema, schema-qualified elsewhere
+pub fn qualified_table_name(schema: &str, table: &str) -> String {
+    if schema == DEFAULT_SCHEMA {
+        name_part(table).into_owned()
+    } else {
+        format!("{schema}.{}", name_part(table))
+    }
+}
+
+/// The app's name for a schema: bare in the connection's own database, "alias.schema" in attached ones
+pub fn qualified_schema_name(catalog: Option<&str>, schema: &str) -> String {
+    match catalog {
+        Some(catalog) => format!("{}.{}", name_part(catalog), name_part(schema)),
+        None => name_part(schema).into_owned(),
+    }
+}
+
+/// SQL computing `qualified_schema_name` from the `database_name` and `schema_name` columns of
+/// DuckDB's catalog functions
+pub fn qualified_schema_name_sql() -> String {
+    let part = |column: &str| format!(
+        "CASE WHEN contains({column}, '.') OR contains({column}, '\"') \
+         THEN '\"' || replace({column}, '\"', '\"\"') || '\"' ELSE {column} END"
+    );
+    format!(
+        "CASE WHEN database_name = current_database() THEN {} ELSE {} || '.' || {} END",
+        part("schema_name"), part("database_name"), part("schema_name")
+    )
+}
+
+// A part of an app name, double-quoted when it contains a dot or quote so it can be split again
+fn name_part(part: &str) -> Cow<'_, str> {
+    if part.contains(['.', '"']) {
+        Cow::Owned(quote_identifier(part))
+    } else {
+        Cow::Borrowed(part)
+    }
+}
+
+// A part of an app name without the quotes added by `name_part`
+fn unquote_part(part: &str) -> Cow<'_, str> {
+    match part.strip_prefix('"').and_then(|inner| inner.strip_suffix('"')) {
+        Some(inner) => Cow::Owned(inner.replace("\"\"", "\"")),
+        None => Cow::Borrowed(part),
+    }
+}
+
+// Byte offsets of the dots separating the parts of an app name, skipping dots inside quotes
+fn separator_positions(name: &str) -> Vec<usize> {
+    let mut in_quotes = false;
+    name.char_indices()
+        .filter_map(|(position, c)| {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            (c == '.' && !in_quotes).then_some(position)
+        })
+        .collect()
+}
+
+/// A single identifier quoted for use in SQL, with embedded quotes doubled
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// A table or schema name as used across the app, quoted part by part for use in SQL,
+/// e.g. `"sales"."Order Items"`. Dots inside quoted parts stay within their part
+pub fn quote_table_name(name: &str) -> String {
+    let mut start = 0;
+    let mut parts = Vec::new();
+    for position in separator_positions(name) {
+        parts.push(quote_identifier(&unquote_part(&name[start..position])));
+        start = position + 1;
+    }
+    parts.push(quote_identifier(&unquote_part(&name[start..])));
+    parts.join(".")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableInfo {
+    // Schema-qualified outside the default schema, see `qualified_table_name`
     pub name: String,
+    pub schema: String,
     pub table_type: String,
     pub column_count: i32,
+    // Bytes on disk, estimated from the table's storage segments
     pub estimated_size: Option<i64>,
+    #[serde(default)]
+    pub estimated_rows: Option<i64>,
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
 impl TableInfo {
     pub fn new(name: String) -> Self {
         Self {
+            schema: split_table_name(&name).0.to_string(),
             name,
             table_type: "BASE TABLE".to_string(),
             column_count: 0,
             estimated_size: None,
+            estimated_rows: None,
+            comment: None,
         }
     }
 
@@ -24,10 +138,20 @@ impl TableInfo {
         self.table_type == "VIEW"
     }
 
+    /// Name of the table within its schema
+    pub fn table_name(&self) -> Cow<'_, str> {
+        split_table_name(&self.name).1
+    }
+
     pub fn is_table(&self) -> bool {
         self.table_type == "BASE TABLE"
     }
 
+    /// Sidebar icon, matching the icons of `CatalogObjectKind`
+    pub fn icon(&self) -> &'static str {
+        if self.is_view() { "[v]" } else { "[t]" }
+    }
+
     pub fn get_display_name(&self) -> String {
         if self.is_view() {
             format!("📊 {}", self.name)
@@ -42,6 +166,16 @@ impl TableInfo {
             None => "Unknown".to_string(),
         }
     }
+
+    /// Row estimate and on-disk size, e.g. "~300.0K rows, 512.0 KB"; None for views
+    pub fn get_storage_summary(&self) -> Option<String> {
+        let rows = self.estimated_rows?;
+        let rows = if rows < 10_000 { rows.to_string() } else { format!("~{}", format_count(rows)) };
+        Some(match self.estimated_size {
+            Some(size) => format!("{rows} rows, {}", format_size(size)),
+            None => format!("{rows} rows"),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,7 +222,18 @@ impl DatabaseSchema {
     }
 }
 
-fn format_size(bytes: i64) -> String {
+fn format_count(count: i64) -> String {
+    const UNITS: &[&str] = &["", "K", "M", "B"];
+    let mut value = count as f64;
+    let mut unit_index = 0;
+    while value >= 1000.0 && unit_index < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit_index += 1;
+    }
+    format!("{:.1}{}", value, UNITS[unit_index])
+}
+
+pub fn format_size(bytes: i64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
     let mut unit_index = 0;
@@ -112,6 +257,10 @@ pub struct ColumnSchema {
     pub is_nullable: bool,
     pub is_primary_key: bool,
     pub is_foreign_key: bool,
+    pub is_unique: bool,
+    pub has_check: bool,
+    // "table.column" this column refers to, for foreign keys
+    pub references: Option<String>,
     pub default_value: Option<String>,
     pub comment: Option<String>,
 }
@@ -124,11 +273,35 @@ impl ColumnSchema {
             is_nullable: true,
             is_primary_key: false,
             is_foreign_key: false,
+            is_unique: false,
+            has_check: false,
+            references: None,
             default_value: None,
             comment: None,
         }
     }
 
+    /// Constraint badges shown next to the column, e.g. "PK UNIQUE" or "FK → users.id"
+    pub fn get_badges(&self) -> String {
+        let mut badges = Vec::new();
+        if self.is_primary_key {
+            badges.push("PK".to_string());
+        }
+        if self.is_foreign_key {
+            match &self.references {
+                Some(target) => badges.push(format!("FK → {target}")),
+                None => badges.push("FK".to_string()),
+            }
+        }
+        if self.is_unique {
+            badges.push("UNIQUE".to_string());
+        }
+        if self.has_check {
+            badges.push("CHECK".to_string());
+        }
+        badges.join(" ")
+    }
+
     pub fn get_display_type(&self) -> String {
         let mut display = self.data_type.clone();
         
@@ -158,12 +331,37 @@ impl ColumnSchema {
     }
 }
 
+/// A foreign key of a table, possibly spanning several columns
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForeignKey {
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+}
+
+impl ForeignKey {
+    pub fn describe(&self) -> String {
+        format!("({}) → {}({})", self.columns.join(", "), self.referenced_table, self.referenced_columns.join(", "))
+    }
+}
+
+/// An ART index from duckdb_indexes()
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexInfo {
+    pub name: String,
+    pub is_unique: bool,
+    pub expressions: String,
+    pub sql: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableSchema {
     pub table_info: TableInfo,
     pub columns: Vec<ColumnSchema>,
-    pub indexes: Vec<String>,
-    pub foreign_keys: Vec<String>,
+    pub indexes: Vec<IndexInfo>,
+    pub foreign_keys: Vec<ForeignKey>,
+    // Text of the PRIMARY KEY, UNIQUE and CHECK constraints
+    pub constraints: Vec<String>,
 }
 
 impl TableSchema {
@@ -173,9 +371,121 @@ impl TableSchema {
             columns: Vec::new(),
             indexes: Vec::new(),
             foreign_keys: Vec::new(),
+            constraints: Vec::new(),
         }
     }
 
+    /// Columns, keys, constraints and indexes of a table or view
+    pub fn load(connection: &Connection, table_info: TableInfo) -> Result<Self> {
+        let qualified_schema = table_info.schema.clone();
+        let (catalog, schema_name) = split_schema_name(&qualified_schema);
+        let table_name = table_info.table_name().to_string();
+        let mut schema = Self::new(table_info);
+
+        let mut stmt = connection.prepare(
+            "SELECT column_name, data_type, is_nullable, column_default, comment
+             FROM duckdb_columns()
+             WHERE database_name = coalesce(?, current_database()) AND schema_name = ? AND table_name = ?
+             ORDER BY column_index",
+        )?;
+        let rows = stmt.query_map(duckdb::params![catalog, schema_name, table_name], |row| {
+            let mut column = ColumnSchema::new(row.get(0)?, row.get(1)?);
+            column.is_nullable = row.get(2)?;
+            column.default_value = row.get(3)?;
+            column.comment = row.get::<_, Option<String>>(4)?.filter(|comment| !comment.is_empty());
+            Ok(column)
+        })?;
+        for row in rows {
+            schema.add_column(row?);
+        }
+
+        // Multi-column constraints come back as one row per column, zipped with the referenced columns
+        let mut stmt = connection.prepare(
+            "SELECT constraint_index, constraint_type, constraint_text, referenced_table,
+                    unnest(constraint_column_names), unnest(referenced_column_names)
+             FROM duckdb_constraints()
+             WHERE database_name = coalesce(?, current_database()) AND schema_name = ? AND table_name = ?
+               AND constraint_type <> 'NOT NULL'
+             ORDER BY constraint_index",
+        )?;
+        let rows = stmt.query_map(duckdb::params![catalog, schema_name, table_name], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?;
+        let mut last_constraint = None;
+        for row in rows {
+            let (index, constraint_type, text, referenced_table, column_name, referenced_column) = row?;
+            let is_new = last_constraint != Some(index);
+            last_constraint = Some(index);
+
+            if constraint_type == "FOREIGN KEY" {
+                let referenced_table = qualified_table_name(&qualified_schema, &referenced_table.unwrap_or_default());
+                if is_new {
+                    schema.foreign_keys.push(ForeignKey {
+                        columns: Vec::new(),
+                        referenced_table: referenced_table.clone(),
+                        referenced_columns: Vec::new(),
+                    });
+                }
+                if let Some(foreign_key) = schema.foreign_keys.last_mut() {
+                    foreign_key.columns.extend(column_name.clone());
+                    foreign_key.referenced_columns.extend(referenced_column.clone());
+                }
+                if let Some(column) = column_name.and_then(|name| schema.column_mut(&name)) {
+                    column.is_foreign_key = true;
+                    column.references = referenced_column.map(|referenced| format!("{referenced_table}.{referenced}"));
+                }
+                continue;
+            }
+
+            if is_new {
+                schema.constraints.push(text);
+            }
+            if let Some(column) = column_name.and_then(|name| schema.column_mut(&name)) {
+                match constraint_type.as_str() {
+                    "PRIMARY KEY" => column.is_primary_key = true,
+                    "UNIQUE" => column.is_unique = true,
+                    "CHECK" => column.has_check = true,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut stmt = connection.prepare(
+            "SELECT index_name, is_unique, CAST(expressions AS VARCHAR), sql
+             FROM duckdb_indexes()
+             WHERE database_name = coalesce(?, current_database()) AND schema_name = ? AND table_name = ?
+             ORDER BY index_name",
+        )?;
+        let rows = stmt.query_map(duckdb::params![catalog, schema_name, table_name], |row| {
+            Ok(IndexInfo {
+                name: row.get(0)?,
+                is_unique: row.get(1)?,
+                expressions: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                sql: row.get(3)?,
+            })
+        })?;
+        for row in rows {
+            schema.indexes.push(row?);
+        }
+
+        Ok(schema)
+    }
+
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    fn column_mut(&mut self, name: &str) -> Option<&mut ColumnSchema> {
+        self.columns.iter_mut().find(|column| column.name == name)
+    }
+
     pub fn add_column(&mut self, column: ColumnSchema) {
         self.columns.push(column);
     }
@@ -187,4 +497,68 @@ impl TableSchema {
     pub fn get_foreign_key_columns(&self) -> Vec<&ColumnSchema> {
         self.columns.iter().filter(|col| col.is_foreign_key).collect()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_keys_constraints_and_indexes() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, email VARCHAR UNIQUE, age INTEGER CHECK (age >= 0));
+             CREATE TABLE orders (id INTEGER, user_id INTEGER REFERENCES users(id), total DECIMAL(10, 2) DEFAULT 0);
+             CREATE INDEX orders_user ON orders(user_id);",
+        ).unwrap();
+
+        let users = TableSchema::load(&conn, TableInfo::new("users".to_string())).unwrap();
+        assert_eq!(users.column("id").unwrap().get_badges(), "PK");
+        assert_eq!(users.column("email").unwrap().get_badges(), "UNIQUE");
+        assert_eq!(users.column("age").unwrap().get_badges(), "CHECK");
+        assert_eq!(users.constraints.len(), 3);
+
+        let orders = TableSchema::load(&conn, TableInfo::new("orders".to_string())).unwrap();
+        assert_eq!(orders.column("user_id").unwrap().get_badges(), "FK → users.id");
+        assert_eq!(orders.foreign_keys[0].describe(), "(user_id) → users(id)");
+        assert_eq!(orders.column("total").unwrap().default_value.as_deref(), Some("0"));
+        assert_eq!(orders.indexes.len(), 1);
+        assert_eq!(orders.indexes[0].name, "orders_user");
+        assert!(!orders.indexes[0].is_unique);
+    }
+
+    #[test]
+    fn test_tables_outside_the_default_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE SCHEMA sales;
+             CREATE TABLE sales.customers (id INTEGER PRIMARY KEY);
+             CREATE TABLE sales.\"Order Items\" (id INTEGER, customer_id INTEGER REFERENCES sales.customers(id));
+             CREATE TABLE customers (name VARCHAR);",
+        ).unwrap();
+
+        assert_eq!(split_table_name("orders"), ("main", "orders".into()));
+        assert_eq!(qualified_table_name("main", "orders"), "orders");
+        assert_eq!(quote_table_name("sales.Order Items"), "\"sales\".\"Order Items\"");
+
+        // Dots inside a name part don't split it
+        let dotted = qualified_table_name("sales", "v1.2");
+        assert_eq!(dotted, "sales.\"v1.2\"");
+        assert_eq!(split_table_name(&dotted), ("sales", "v1.2".into()));
+        assert_eq!(quote_table_name(&dotted), "\"sales\".\"v1.2\"");
+        assert_eq!(quote_table_name(&qualified_table_name("main", "a\"b.c")), "\"a\"\"b.c\"");
+        conn.execute_batch("CREATE TABLE sales.\"v1.2\" (id INTEGER);").unwrap();
+        let listed = crate::db::get_table_list(&conn).unwrap();
+        assert!(listed.iter().any(|table| table.name == dotted));
+        assert_eq!(TableSchema::load(&conn, TableInfo::new(dotted)).unwrap().columns.len(), 1);
+        let schema = qualified_schema_name(Some("ref"), "x.y");
+        assert_eq!(split_schema_name(&schema), (Some("ref".into()), "x.y".into()));
+
+        let items = TableSchema::load(&conn, TableInfo::new("sales.Order Items".to_string())).unwrap();
+        assert_eq!(items.table_info.schema, "sales");
+        assert_eq!(items.columns.len(), 2);
+        assert_eq!(items.foreign_keys[0].referenced_table, "sales.customers");
+        let customers = TableSchema::load(&conn, TableInfo::new("customers".to_string())).unwrap();
+        assert_eq!(customers.column("name").unwrap().data_type, "VARCHAR");
+    }
+}
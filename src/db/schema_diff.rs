@@ -0,0 +1,466 @@
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::alter::{create_schema_sql, ColumnChange};
+use super::schema::{qualified_table_name, quote_identifier, quote_table_name, split_table_name};
+
+// Restricts catalog functions to one database: the connection's own one unless a catalog is given
+const CATALOG_FILTER: &str = "database_name = coalesce(?, current_database())";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSnapshot {
+    pub name: String,
+    pub data_type: String,
+    pub is_nullable: bool,
+    pub default_value: Option<String>,
+}
+
+/// A table or view as needed to compare and recreate it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationSnapshot {
+    // Schema-qualified outside the default schema, see `qualified_table_name`
+    pub name: String,
+    pub is_view: bool,
+    pub sql: String,
+    pub columns: Vec<ColumnSnapshot>,
+    // Constraint texts other than NOT NULL, which is compared per column
+    pub constraints: Vec<String>,
+    // Relations that must exist first: tables referenced by foreign keys, and for views the
+    // relations their query mentions
+    pub depends_on: Vec<String>,
+}
+
+impl RelationSnapshot {
+    fn kind(&self) -> &'static str {
+        if self.is_view { "view" } else { "table" }
+    }
+}
+
+/// Schemas, tables and views of one database or attached catalog
+#[derive(Debug, Clone, Default)]
+pub struct SchemaSnapshot {
+    pub schemas: Vec<String>,
+    pub relations: BTreeMap<String, RelationSnapshot>,
+}
+
+impl SchemaSnapshot {
+    pub fn load(connection: &Connection, catalog: Option<&str>) -> Result<Self> {
+        let mut snapshot = Self::default();
+
+        let mut stmt = connection.prepare(&format!(
+            "SELECT schema_name FROM duckdb_schemas()
+             WHERE {CATALOG_FILTER} AND schema_name NOT IN ('information_schema', 'pg_catalog')
+             ORDER BY schema_name"
+        ))?;
+        snapshot.schemas = stmt.query_map([catalog], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut stmt = connection.prepare(&format!(
+            "SELECT schema_name, table_name, sql, false FROM duckdb_tables() WHERE {CATALOG_FILTER} AND NOT internal AND NOT temporary
+             UNION ALL
+             SELECT schema_name, view_name, sql, true FROM duckdb_views() WHERE {CATALOG_FILTER} AND NOT internal AND NOT temporary"
+        ))?;
+        let rows = stmt.query_map([catalog, catalog], |row| {
+            let name = qualified_table_name(&row.get::<_, String>(0)?, &row.get::<_, String>(1)?);
+            Ok(RelationSnapshot {
+                name,
+                sql: row.get(2)?,
+                is_view: row.get(3)?,
+                columns: Vec::new(),
+                constraints: Vec::new(),
+                depends_on: Vec::new(),
+            })
+        })?;
+        for row in rows {
+            let relation = row?;
+            snapshot.relations.insert(relation.name.clone(), relation);
+        }
+
+        let mut stmt = connection.prepare(&format!(
+            "SELECT schema_name, table_name, column_name, data_type, is_nullable, column_default
+             FROM duckdb_columns() WHERE {CATALOG_FILTER} ORDER BY schema_name, table_name, column_index"
+        ))?;
+        let rows = stmt.query_map([catalog], |row| {
+            Ok((
+                qualified_table_name(&row.get::<_, String>(0)?, &row.get::<_, String>(1)?),
+                ColumnSnapshot {
+                    name: row.get(2)?,
+                    data_type: row.get(3)?,
+                    is_nullable: row.get(4)?,
+                    default_value: row.get(5)?,
+                },
+            ))
+        })?;
+        for row in rows {
+            let (table, column) = row?;
+            if let Some(relation) = snapshot.relations.get_mut(&table) {
+                relation.columns.push(column);
+            }
+        }
+
+        let mut stmt = connection.prepare(&format!(
+            "SELECT schema_name, table_name, constraint_text, referenced_table FROM duckdb_constraints()
+             WHERE {CATALOG_FILTER} AND constraint_type <> 'NOT NULL'
+             ORDER BY schema_name, table_name, constraint_index"
+        ))?;
+        let rows = stmt.query_map([catalog], |row| {
+            let schema: String = row.get(0)?;
+            Ok((
+                qualified_table_name(&schema, &row.get::<_, String>(1)?),
+                row.get::<_, String>(2)?,
+                // Foreign keys reference tables in their own schema
+                row.get::<_, Option<String>>(3)?.map(|referenced| qualified_table_name(&schema, &referenced)),
+            ))
+        })?;
+        for row in rows {
+            let (table, constraint, referenced_table) = row?;
+            if let Some(relation) = snapshot.relations.get_mut(&table) {
+                relation.constraints.push(constraint);
+                relation.depends_on.extend(referenced_table.filter(|referenced| *referenced != table));
+            }
+        }
+
+        // Views don't record what they read from, so look for relation names in their queries
+        let names: Vec<String> = snapshot.relations.keys().cloned().collect();
+        for relation in snapshot.relations.values_mut().filter(|relation| relation.is_view) {
+            let sql = relation.sql.to_lowercase();
+            relation.depends_on = names.iter()
+                .filter(|name| **name != relation.name && mentions(&sql, &split_table_name(name).1.to_lowercase()))
+                .cloned()
+                .collect();
+        }
+
+        Ok(snapshot)
+    }
+}
+
+/// One difference between a source and a target schema, from the point of view of migrating the
+/// source to the target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    AddedSchema(String),
+    AddedRelation(RelationSnapshot),
+    RemovedRelation(RelationSnapshot),
+    ChangedView { name: String, sql: String },
+    AddedColumn { table: String, column: ColumnSnapshot },
+    RemovedColumn { table: String, column: String },
+    ChangedColumn { table: String, from: ColumnSnapshot, to: ColumnSnapshot },
+    ChangedConstraints { table: String, removed: Vec<String>, added: Vec<String> },
+}
+
+impl SchemaChange {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::AddedSchema(schema) => format!("+ schema {schema}"),
+            Self::AddedRelation(relation) => format!("+ {} {}", relation.kind(), relation.name),
+            Self::RemovedRelation(relation) => format!("- {} {}", relation.kind(), relation.name),
+            Self::ChangedView { name, .. } => format!("~ view {name}: definition changed"),
+            Self::AddedColumn { table, column } => format!("+ column {table}.{} {}", column.name, column.data_type),
+            Self::RemovedColumn { table, column } => format!("- column {table}.{column}"),
+            Self::ChangedColumn { table, from, to } => {
+                let mut changes = Vec::new();
+                if from.data_type != to.data_type {
+                    changes.push(format!("type {} → {}", from.data_type, to.data_type));
+                }
+                if from.is_nullable != to.is_nullable {
+                    changes.push(if to.is_nullable { "now nullable" } else { "now NOT NULL" }.to_string());
+                }
+                if from.default_value != to.default_value {
+                    let describe = |default: &Option<String>| default.clone().unwrap_or_else(|| "none".to_string());
+                    changes.push(format!("default {} → {}", describe(&from.default_value), describe(&to.default_value)));
+                }
+                format!("~ column {table}.{}: {}", to.name, changes.join(", "))
+            }
+            Self::ChangedConstraints { table, removed, added } => {
+                format!("~ table {table}: {} constraint(s) removed, {} added", removed.len(), added.len())
+            }
+        }
+    }
+
+    /// Statements applying this change to the source
+    pub fn migration_sql(&self) -> Vec<String> {
+        let column_change = |table: &str, column: &str, change: ColumnChange| format!("{};", change.alter_sql(table, column));
+        match self {
+            Self::AddedSchema(schema) => vec![format!("{};", create_schema_sql(schema))],
+            Self::AddedRelation(relation) => vec![relation.sql.clone()],
+            Self::RemovedRelation(relation) => {
+                vec![format!("DROP {} {};", relation.kind().to_uppercase(), quote_table_name(&relation.name))]
+            }
+            Self::ChangedView { name, sql } => vec![super::definition::replace_view_sql(name, sql)],
+            Self::AddedColumn { table, column } => {
+                // DuckDB can't add a column with a NOT NULL constraint in one step
+                let mut statements = vec![column_change(table, &column.name, ColumnChange::Add {
+                    name: column.name.clone(),
+                    data_type: column.data_type.clone(),
+                    default: column.default_value.clone(),
+                })];
+                if !column.is_nullable {
+                    statements.push(column_change(table, &column.name, ColumnChange::SetNotNull));
+                }
+                statements
+            }
+            Self::RemovedColumn { table, column } => vec![column_change(table, column, ColumnChange::Drop)],
+            Self::ChangedColumn { table, from, to } => {
+                let mut statements = Vec::new();
+                if from.data_type != to.data_type {
+                    statements.push(column_change(table, &to.name, ColumnChange::ChangeType(to.data_type.clone())));
+                }
+                if from.default_value != to.default_value {
+                    statements.push(column_change(table, &to.name, match &to.default_value {
+                        Some(default) => ColumnChange::SetDefault(default.clone()),
+                        None => ColumnChange::DropDefault,
+                    }));
+                }
+                if from.is_nullable != to.is_nullable {
+                    let change = if to.is_nullable { ColumnChange::DropNotNull } else { ColumnChange::SetNotNull };
+                    statements.push(column_change(table, &to.name, change));
+                }
+                statements
+            }
+            Self::ChangedConstraints { table, removed, added } => {
+                // DuckDB can't add or drop constraints on an existing table
+                let mut statements = vec![format!("-- {table}: constraints differ; recreate the table to apply them")];
+                statements.extend(removed.iter().map(|constraint| format!("--   remove {constraint}")));
+                statements.extend(added.iter().map(|constraint| format!("--   add {constraint}")));
+                statements
+            }
+        }
+    }
+}
+
+// Whether `word` appears in `sql` as a whole identifier
+fn mentions(sql: &str, word: &str) -> bool {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    sql.match_indices(word).any(|(position, _)| {
+        !sql[..position].ends_with(is_identifier) && !sql[position + word.len()..].starts_with(is_identifier)
+    })
+}
+
+// `relations` with every relation after the ones it depends on, keeping the given order otherwise
+fn dependency_order<'a>(relations: &[&'a RelationSnapshot]) -> Vec<&'a RelationSnapshot> {
+    fn visit<'a>(
+        relation: &'a RelationSnapshot,
+        relations: &[&'a RelationSnapshot],
+        visited: &mut HashSet<&'a str>,
+        ordered: &mut Vec<&'a RelationSnapshot>,
+    ) {
+        // Marked before its dependencies so cycles end instead of recursing forever
+        if !visited.insert(&relation.name) {
+            return;
+        }
+        for dependency in &relation.depends_on {
+            if let Some(other) = relations.iter().find(|other| other.name == *dependency) {
+                visit(other, relations, visited, ordered);
+            }
+        }
+        ordered.push(relation);
+    }
+
+    let mut visited = HashSet::new();
+    let mut ordered = Vec::new();
+    for relation in relations {
+        visit(relation, relations, &mut visited, &mut ordered);
+    }
+    ordered
+}
+
+fn normalize_sql(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ").trim_end_matches(';').to_string()
+}
+
+/// Changes turning `source` into `target`, in the order their statements must run:
+/// new schemas, drops (dependents first), new relations (dependencies first), column changes,
+/// then view changes
+pub fn diff_schemas(source: &SchemaSnapshot, target: &SchemaSnapshot) -> Vec<SchemaChange> {
+    let mut changes: Vec<SchemaChange> = target.schemas.iter()
+        .filter(|schema| !source.schemas.contains(schema))
+        .map(|schema| SchemaChange::AddedSchema(schema.clone()))
+        .collect();
+
+    let mut removed: Vec<&RelationSnapshot> = source.relations.values()
+        .filter(|relation| !target.relations.contains_key(&relation.name))
+        .collect();
+    removed.sort_by_key(|relation| relation.is_view);
+    changes.extend(dependency_order(&removed).into_iter().rev().cloned().map(SchemaChange::RemovedRelation));
+
+    let mut added: Vec<&RelationSnapshot> = target.relations.values()
+        .filter(|relation| !source.relations.contains_key(&relation.name))
+        .collect();
+    added.sort_by_key(|relation| relation.is_view);
+    changes.extend(dependency_order(&added).into_iter().cloned().map(SchemaChange::AddedRelation));
+
+    let mut view_changes = Vec::new();
+    for (name, to) in &target.relations {
+        let Some(from) = source.relations.get(name) else {
+            continue;
+        };
+        if from.is_view || to.is_view {
+            if from.is_view != to.is_view {
+                // A table replaced by a view or the other way round is recreated
+                changes.push(SchemaChange::RemovedRelation(from.clone()));
+                view_changes.push(SchemaChange::AddedRelation(to.clone()));
+            } else if normalize_sql(&from.sql) != normalize_sql(&to.sql) {
+                view_changes.push(SchemaChange::ChangedView { name: name.clone(), sql: to.sql.clone() });
+            }
+            continue;
+        }
+
+        for column in &from.columns {
+            if !to.columns.iter().any(|other| other.name == column.name) {
+                changes.push(SchemaChange::RemovedColumn { table: name.clone(), column: column.name.clone() });
+            }
+        }
+        for column in &to.columns {
+            match from.columns.iter().find(|other| other.name == column.name) {
+                None => changes.push(SchemaChange::AddedColumn { table: name.clone(), column: column.clone() }),
+                Some(previous) if previous != column => changes.push(SchemaChange::ChangedColumn {
+                    table: name.clone(),
+                    from: previous.clone(),
+                    to: column.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        let removed: Vec<String> = from.constraints.iter().filter(|c| !to.constraints.contains(c)).cloned().collect();
+        let added: Vec<String> = to.constraints.iter().filter(|c| !from.constraints.contains(c)).cloned().collect();
+        if !removed.is_empty() || !added.is_empty() {
+            changes.push(SchemaChange::ChangedConstraints { table: name.clone(), removed, added });
+        }
+    }
+    changes.extend(view_changes);
+    changes
+}
+
+/// The migration script for `changes`, with a header naming both sides. The statements name
+/// relations as the catalog does, so a source in an attached catalog is selected with USE first
+pub fn migration_script(changes: &[SchemaChange], source: &str, target: &str) -> String {
+    let mut lines = vec![format!("-- Migrate {source} to match {target}")];
+    if let (_, Some(catalog)) = split_schema_source(source) {
+        lines.push(format!("USE {};", quote_identifier(catalog)));
+    }
+    if changes.is_empty() {
+        lines.push("-- The schemas are identical".to_string());
+    }
+    for change in changes {
+        lines.push(String::new());
+        lines.push(format!("-- {}", change.describe()));
+        lines.extend(change.migration_sql());
+    }
+    lines.join("\n")
+}
+
+/// The two sides of a schema comparison, e.g. "dev prod" or "dev dev:archive"
+pub fn parse_schema_diff_input(input: &str) -> Result<(String, String)> {
+    match input.split_whitespace().collect::<Vec<_>>()[..] {
+        [source, target] => Ok((source.to_string(), target.to_string())),
+        _ => anyhow::bail!("expected <left database> <right database>"),
+    }
+}
+
+/// Splits "database:catalog" into the open database and an optional catalog attached to it
+pub fn split_schema_source(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once(':') {
+        Some((database, catalog)) if !catalog.is_empty() => (database, Some(catalog)),
+        Some((database, _)) => (database, None),
+        None => (spec, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_schemas_and_apply_migration() {
+        let dev = Connection::open_in_memory().unwrap();
+        dev.execute_batch(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name VARCHAR, email VARCHAR NOT NULL, age INTEGER);
+             CREATE TABLE legacy (id INTEGER PRIMARY KEY);
+             CREATE TABLE a_legacy_items (id INTEGER, legacy_id INTEGER REFERENCES legacy(id));
+             CREATE VIEW adults AS SELECT * FROM users WHERE age >= 18;
+             CREATE VIEW old_view AS SELECT 1 AS one;",
+        ).unwrap();
+        let prod = Connection::open_in_memory().unwrap();
+        prod.execute_batch(
+            "CREATE SCHEMA billing;
+             CREATE TABLE users (id INTEGER PRIMARY KEY, name VARCHAR NOT NULL, email VARCHAR, age BIGINT DEFAULT 0, created DATE);
+             CREATE TABLE billing.invoices (id INTEGER, total DECIMAL(10, 2));
+             CREATE TABLE b_customers (id INTEGER PRIMARY KEY);
+             CREATE TABLE a_orders (id INTEGER, customer_id INTEGER REFERENCES b_customers(id));
+             CREATE VIEW b_view AS SELECT * FROM b_customers;
+             CREATE VIEW a_view AS SELECT * FROM b_view;
+             CREATE VIEW adults AS SELECT * FROM users WHERE age >= 21;",
+        ).unwrap();
+
+        let source = SchemaSnapshot::load(&dev, None).unwrap();
+        let target = SchemaSnapshot::load(&prod, None).unwrap();
+        let changes = diff_schemas(&source, &target);
+        let described: Vec<String> = changes.iter().map(SchemaChange::describe).collect();
+        assert_eq!(described, vec![
+            "+ schema billing",
+            "- view old_view",
+            "- table a_legacy_items",
+            "- table legacy",
+            "+ table b_customers",
+            "+ table a_orders",
+            "+ table billing.invoices",
+            "+ view b_view",
+            "+ view a_view",
+            "~ column users.name: now NOT NULL",
+            "~ column users.email: now nullable",
+            "~ column users.age: type INTEGER → BIGINT, default none → 0",
+            "+ column users.created DATE",
+            "~ view adults: definition changed",
+        ]);
+
+        // Running the script on dev makes its schema match prod
+        dev.execute_batch(&migration_script(&changes, "dev", "prod")).unwrap();
+        let migrated = SchemaSnapshot::load(&dev, None).unwrap();
+        assert!(diff_schemas(&migrated, &target).is_empty());
+        assert_eq!(diff_schemas(&target, &target), Vec::new());
+    }
+
+    #[test]
+    fn test_snapshot_of_attached_catalog() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "ATTACH ':memory:' AS other;
+             CREATE TABLE other.t (id INTEGER UNIQUE);
+             CREATE TABLE t (id INTEGER);",
+        ).unwrap();
+
+        let own = SchemaSnapshot::load(&conn, None).unwrap();
+        let other = SchemaSnapshot::load(&conn, Some("other")).unwrap();
+        assert_eq!(other.relations["t"].constraints, vec!["UNIQUE(id)".to_string()]);
+        let changes = diff_schemas(&own, &other);
+        assert!(matches!(&changes[..], [SchemaChange::ChangedConstraints { added, .. }] if added.len() == 1));
+        assert!(migration_script(&changes, "memory", "other").contains("-- t: constraints differ"));
+
+        let (source, target) = parse_schema_diff_input(" dev  dev:other ").unwrap();
+        assert_eq!(split_schema_source(&source), ("dev", None));
+        assert_eq!(split_schema_source(&target), ("dev", Some("other")));
+        assert!(parse_schema_diff_input("dev").is_err());
+    }
+
+    #[test]
+    fn test_migration_script_targets_source_catalog() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "ATTACH ':memory:' AS other;
+             CREATE TABLE t (id INTEGER);",
+        ).unwrap();
+
+        let own = SchemaSnapshot::load(&conn, None).unwrap();
+        let other = SchemaSnapshot::load(&conn, Some("other")).unwrap();
+        let script = migration_script(&diff_schemas(&other, &own), "memory:other", "memory");
+        assert!(script.contains("USE \"other\";"));
+
+        // The table is created in the attached catalog, not in the one the connection started in
+        conn.execute_batch(&script).unwrap();
+        assert!(SchemaSnapshot::load(&conn, Some("other")).unwrap().relations.contains_key("t"));
+        assert!(!migration_script(&[], "memory", "memory:other").contains("USE"));
+    }
+}
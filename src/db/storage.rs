@@ -0,0 +1,174 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use duckdb::Connection;
+
+use super::schema::{quote_table_name, split_schema_name, split_table_name};
+
+/// Segments, compression and estimated on-disk size of one column, validity segments included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnStorage {
+    pub column_name: String,
+    pub segment_count: usize,
+    // Compression algorithms with the number of segments using each, most used first
+    pub compressions: Vec<(String, usize)>,
+    pub estimated_bytes: Option<i64>,
+}
+
+/// Storage layout of a table from `pragma_storage_info`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TableStorage {
+    pub row_group_count: usize,
+    pub segment_count: usize,
+    pub columns: Vec<ColumnStorage>,
+    // None until the table has been written to disk, e.g. in in-memory databases
+    pub estimated_bytes: Option<i64>,
+}
+
+impl TableStorage {
+    /// Compression algorithms used by the table's segments, most used first
+    pub fn compression_summary(&self) -> String {
+        let mut totals: HashMap<&str, usize> = HashMap::new();
+        for column in &self.columns {
+            for (compression, count) in &column.compressions {
+                *totals.entry(compression.as_str()).or_default() += count;
+            }
+        }
+        let mut totals: Vec<_> = totals.into_iter().collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        totals.into_iter().map(|(compression, _)| compression).collect::<Vec<_>>().join(", ")
+    }
+}
+
+struct Segment {
+    row_group_id: i64,
+    column_name: String,
+    compression: String,
+    // Block and offset of persisted segments; constant segments are not stored in a block
+    block: Option<(i64, i64)>,
+}
+
+/// Reads the segments of a table and estimates their sizes from the gaps between block offsets.
+/// The last segment of a block is counted up to the end of the block, so sizes are upper bounds.
+pub fn load_table_storage(conn: &Connection, table_name: &str) -> Result<TableStorage> {
+    let catalog = split_schema_name(split_table_name(table_name).0).0;
+    let block_size: i64 = conn.query_row(
+        "SELECT block_size FROM pragma_database_size() WHERE database_name = coalesce(?, current_database())",
+        [catalog],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT row_group_id, column_name, compression, persistent, block_id, block_offset
+         FROM pragma_storage_info(?) ORDER BY column_id, row_group_id, segment_id"
+    )?;
+    let rows = stmt.query_map([quote_table_name(table_name)], |row| {
+        let persistent: bool = row.get(3)?;
+        let block_id: Option<i64> = row.get(4)?;
+        let block_offset: Option<i64> = row.get(5)?;
+        Ok((persistent, Segment {
+            row_group_id: row.get(0)?,
+            column_name: row.get(1)?,
+            compression: row.get(2)?,
+            block: block_id.filter(|id| *id >= 0).zip(block_offset),
+        }))
+    })?;
+    let mut segments = Vec::new();
+    let mut persistent = true;
+    for row in rows {
+        let (is_persistent, segment) = row?;
+        persistent &= is_persistent;
+        segments.push(segment);
+    }
+
+    let mut offsets_by_block: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (block_id, offset) in segments.iter().filter_map(|segment| segment.block) {
+        offsets_by_block.entry(block_id).or_default().push(offset);
+    }
+    for offsets in offsets_by_block.values_mut() {
+        offsets.sort_unstable();
+    }
+    let segment_bytes = |segment: &Segment| match segment.block {
+        Some((block_id, offset)) => {
+            let next = offsets_by_block[&block_id].iter().find(|other| **other > offset);
+            next.unwrap_or(&block_size) - offset
+        }
+        None => 0,
+    };
+
+    // Columns keep the table's column order
+    let mut columns: Vec<ColumnStorage> = Vec::new();
+    let mut compressions: Vec<BTreeMap<String, usize>> = Vec::new();
+    for segment in &segments {
+        let index = match columns.iter().position(|column| column.column_name == segment.column_name) {
+            Some(index) => index,
+            None => {
+                columns.push(ColumnStorage {
+                    column_name: segment.column_name.clone(),
+                    segment_count: 0,
+                    compressions: Vec::new(),
+                    estimated_bytes: persistent.then_some(0),
+                });
+                compressions.push(BTreeMap::new());
+                columns.len() - 1
+            }
+        };
+        let column = &mut columns[index];
+        column.segment_count += 1;
+        if let Some(bytes) = &mut column.estimated_bytes {
+            *bytes += segment_bytes(segment);
+        }
+        *compressions[index].entry(segment.compression.clone()).or_default() += 1;
+    }
+    for (column, counts) in columns.iter_mut().zip(compressions) {
+        column.compressions = counts.into_iter().collect();
+        column.compressions.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    }
+
+    let mut row_groups: Vec<i64> = segments.iter().map(|segment| segment.row_group_id).collect();
+    row_groups.sort_unstable();
+    row_groups.dedup();
+
+    Ok(TableStorage {
+        row_group_count: row_groups.len(),
+        segment_count: segments.len(),
+        estimated_bytes: persistent.then(|| columns.iter().filter_map(|column| column.estimated_bytes).sum()),
+        columns,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_table_storage() {
+        let path = std::env::temp_dir().join(format!("ducky_storage_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t AS SELECT range AS id, 'x' || (range % 7) AS label FROM range(300000);
+             CHECKPOINT;",
+        ).unwrap();
+
+        let storage = load_table_storage(&conn, "t").unwrap();
+        assert_eq!(storage.row_group_count, 3);
+        let names: Vec<&str> = storage.columns.iter().map(|column| column.column_name.as_str()).collect();
+        assert_eq!(names, vec!["id", "label"]);
+        // Each row group holds a data and a validity segment per column
+        assert_eq!(storage.columns[0].segment_count, 6);
+        assert!(storage.columns[1].compressions.iter().any(|(compression, _)| compression == "Dictionary"));
+        assert!(storage.estimated_bytes.is_some_and(|bytes| bytes > 0));
+        assert!(!storage.compression_summary().is_empty());
+
+        drop(conn);
+        let _ = std::fs::remove_file(&path);
+
+        // Nothing is written to disk for in-memory databases
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE SCHEMA s; CREATE TABLE s.t AS SELECT range AS id FROM range(10);").unwrap();
+        let storage = load_table_storage(&conn, "s.t").unwrap();
+        assert_eq!(storage.columns.len(), 1);
+        assert_eq!(storage.estimated_bytes, None);
+    }
+}
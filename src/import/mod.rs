@@ -3,6 +3,7 @@ use anyhow::{Result, Context};
 use duckdb::Connection;
 
 use crate::actions::{Action, ActionLogger};
+use crate::db::schema::quote_table_name;
 
 /// Supported file formats for import
 #[derive(Debug, Clone, PartialEq)]
@@ -76,26 +77,26 @@ impl<'a> ImportWorkflows<'a> {
         table_name: &str,
         format: &FileFormat,
     ) -> Result<()> {
-        let file_path_str = file_path.to_string_lossy();
+        let file_path_str = file_path.to_string_lossy().replace('\'', "''");
         
         // Build the CREATE TABLE AS SELECT query using DuckDB's auto-detection
         let query = match format {
             FileFormat::Csv => {
                 format!(
                     "CREATE TABLE {} AS SELECT * FROM read_csv_auto('{}')",
-                    table_name, file_path_str
+                    quote_table_name(table_name), file_path_str
                 )
             }
             FileFormat::Json => {
                 format!(
                     "CREATE TABLE {} AS SELECT * FROM read_json_auto('{}')",
-                    table_name, file_path_str
+                    quote_table_name(table_name), file_path_str
                 )
             }
             FileFormat::Parquet => {
                 format!(
                     "CREATE TABLE {} AS SELECT * FROM read_parquet('{}')",
-                    table_name, file_path_str
+                    quote_table_name(table_name), file_path_str
                 )
             }
         };
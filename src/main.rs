@@ -198,7 +198,10 @@ fn test_database_info(path: &str) -> Result<DatabaseInfo, anyhow::Error> {
     
     // Get table list
     let mut stmt = conn.prepare(
-        "SELECT table_name FROM information_schema.tables WHERE table_schema = 'main' ORDER BY table_name"
+        "SELECT CASE WHEN table_schema = 'main' THEN table_name ELSE table_schema || '.' || table_name END
+         FROM information_schema.tables
+         WHERE table_catalog = current_database() AND table_schema NOT IN ('information_schema', 'pg_catalog')
+         ORDER BY table_schema <> 'main', table_schema, table_name"
     )?;
     
     let rows = stmt.query_map([], |row| {
@@ -0,0 +1,331 @@
+use anyhow::Result;
+use duckdb::Connection;
+
+use crate::db::{TableInfo, TableSchema};
+
+// Columns listed per box before the rest are summarized
+const MAX_BOX_COLUMNS: usize = 12;
+const MAX_BOX_WIDTH: usize = 36;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BoxRect {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+/// A foreign key from a column of one table to a column of another
+#[derive(Debug, Clone)]
+struct Edge {
+    from: usize,
+    from_column: usize,
+    to: usize,
+    to_column: usize,
+}
+
+/// ASCII entity-relationship diagram of a database, laid out in layers so
+/// tables sit to the right of the tables they reference
+#[derive(Debug, Clone)]
+pub struct ErDiagram {
+    names: Vec<String>,
+    layers: Vec<Vec<usize>>,
+    boxes: Vec<BoxRect>,
+    canvas: Vec<Vec<char>>,
+    // Relationships that can't be drawn left to right (self references and cycles)
+    pub other_relationships: Vec<String>,
+    selected: usize,
+}
+
+impl ErDiagram {
+    pub fn load(connection: &Connection, tables: &[TableInfo]) -> Result<Self> {
+        let schemas = tables.iter()
+            .map(|table| TableSchema::load(connection, table.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(&schemas))
+    }
+
+    pub fn new(schemas: &[TableSchema]) -> Self {
+        let names: Vec<String> = schemas.iter().map(|schema| schema.table_info.name.clone()).collect();
+        let box_lines: Vec<Vec<String>> = schemas.iter().map(box_lines).collect();
+
+        let mut edges = Vec::new();
+        let mut other_relationships = Vec::new();
+        for (from, schema) in schemas.iter().enumerate() {
+            for foreign_key in &schema.foreign_keys {
+                let Some(to) = names.iter().position(|name| *name == foreign_key.referenced_table) else {
+                    continue;
+                };
+                let column_row = |schema: &TableSchema, column: Option<&String>| column
+                    .and_then(|column| schema.columns.iter().position(|c| c.name == *column))
+                    .unwrap_or(0)
+                    .min(MAX_BOX_COLUMNS);
+                edges.push(Edge {
+                    from,
+                    from_column: column_row(schema, foreign_key.columns.first()),
+                    to,
+                    to_column: column_row(&schemas[to], foreign_key.referenced_columns.first()),
+                });
+                if from == to {
+                    other_relationships.push(format!("{} {} (self reference)", names[from], foreign_key.describe()));
+                }
+            }
+        }
+
+        // Referencing tables go one layer right of everything they reference
+        let mut layer = vec![0usize; names.len()];
+        for _ in 0..names.len() {
+            let mut changed = false;
+            for edge in edges.iter().filter(|edge| edge.from != edge.to) {
+                if layer[edge.from] <= layer[edge.to] && layer[edge.to] < names.len() {
+                    layer[edge.from] = layer[edge.to] + 1;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        let mut used_layers: Vec<usize> = layer.clone();
+        used_layers.sort_unstable();
+        used_layers.dedup();
+        let layer: Vec<usize> = layer.iter()
+            .map(|l| used_layers.iter().position(|used| used == l).unwrap_or(0))
+            .collect();
+        let mut layers = vec![Vec::new(); used_layers.len()];
+        for (table, &l) in layer.iter().enumerate() {
+            layers[l].push(table);
+        }
+        for tables in &mut layers {
+            tables.sort_by(|a, b| names[*a].cmp(&names[*b]));
+        }
+
+        // Edges that would point right or stay in a layer (cycles) are listed instead of drawn
+        let (drawn, skipped): (Vec<Edge>, Vec<Edge>) = edges.into_iter()
+            .filter(|edge| edge.from != edge.to)
+            .partition(|edge| layer[edge.from] > layer[edge.to]);
+        for edge in skipped {
+            other_relationships.push(format!("{} → {} (cycle)", names[edge.from], names[edge.to]));
+        }
+
+        // Each edge gets its own vertical lane in the gap right of the referenced table's layer
+        let mut lanes_per_gap = vec![0usize; layers.len()];
+        let edge_lanes: Vec<usize> = drawn.iter()
+            .map(|edge| {
+                let gap = layer[edge.to];
+                lanes_per_gap[gap] += 1;
+                lanes_per_gap[gap] - 1
+            })
+            .collect();
+
+        let mut boxes = vec![BoxRect { x: 0, y: 0, width: 0, height: 0 }; names.len()];
+        let mut x = 0;
+        let mut gap_starts = Vec::new();
+        for (l, tables) in layers.iter().enumerate() {
+            let width = tables.iter()
+                .map(|&table| box_lines[table].iter().map(|line| line.chars().count()).max().unwrap_or(0) + 4)
+                .max()
+                .unwrap_or(0)
+                .min(MAX_BOX_WIDTH);
+            let mut y = 0;
+            for &table in tables {
+                let height = box_lines[table].len() + 2;
+                boxes[table] = BoxRect { x, y, width, height };
+                y += height + 1;
+            }
+            x += width;
+            gap_starts.push(x);
+            x += 4 + 2 * lanes_per_gap[l];
+        }
+
+        let canvas_width = x;
+        let canvas_height = boxes.iter().map(|rect| rect.y + rect.height).max().unwrap_or(0);
+        let mut canvas = vec![vec![' '; canvas_width]; canvas_height];
+
+        for (edge, lane) in drawn.iter().zip(edge_lanes) {
+            let (from, to) = (boxes[edge.from], boxes[edge.to]);
+            let from_y = from.y + 1 + edge.from_column.min(from.height - 3);
+            let to_y = to.y + 1 + edge.to_column.min(to.height - 3);
+            let lane_x = gap_starts[layer[edge.to]] + 2 + 2 * lane;
+
+            draw_horizontal(&mut canvas, from_y, lane_x + 1, from.x.saturating_sub(1));
+            draw_horizontal(&mut canvas, to_y, to.x + to.width + 1, lane_x.saturating_sub(1));
+            if from_y != to_y {
+                draw_vertical(&mut canvas, lane_x, from_y.min(to_y) + 1, from_y.max(to_y).saturating_sub(1));
+            }
+            canvas[from_y][lane_x] = match from_y.cmp(&to_y) {
+                std::cmp::Ordering::Greater => '└',
+                std::cmp::Ordering::Less => '┌',
+                std::cmp::Ordering::Equal => '─',
+            };
+            canvas[to_y][lane_x] = match from_y.cmp(&to_y) {
+                std::cmp::Ordering::Greater => '┐',
+                std::cmp::Ordering::Less => '┘',
+                std::cmp::Ordering::Equal => '─',
+            };
+            canvas[to_y][to.x + to.width] = '◄';
+        }
+
+        for (table, rect) in boxes.iter().enumerate() {
+            draw_box(&mut canvas, *rect, &names[table], &box_lines[table]);
+        }
+
+        let selected = layers.first().and_then(|tables| tables.first()).copied().unwrap_or(0);
+        Self {
+            names,
+            layers,
+            boxes,
+            canvas,
+            other_relationships,
+            selected,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn selected_table(&self) -> Option<&str> {
+        self.names.get(self.selected).map(String::as_str)
+    }
+
+    /// Select the box next to the current one: left/right across layers, up/down within one
+    pub fn move_selection(&mut self, dx: i32, dy: i32) {
+        let Some((layer, position)) = self.layers.iter().enumerate()
+            .find_map(|(l, tables)| tables.iter().position(|&t| t == self.selected).map(|p| (l, p))) else {
+            return;
+        };
+        if dy != 0 {
+            let tables = &self.layers[layer];
+            let position = (position as i32 + dy).clamp(0, tables.len() as i32 - 1) as usize;
+            self.selected = tables[position];
+        } else if dx != 0 {
+            let target = (layer as i32 + dx).clamp(0, self.layers.len() as i32 - 1) as usize;
+            // Keep roughly the same height when changing layers
+            let y = self.boxes[self.selected].y;
+            if let Some(&table) = self.layers[target].iter()
+                .min_by_key(|&&table| self.boxes[table].y.abs_diff(y)) {
+                self.selected = table;
+            }
+        }
+    }
+
+    /// Canvas rows visible in a `width` x `height` viewport that keeps the selected box in view,
+    /// with the column range of the selected box within each row (if it crosses it)
+    pub fn viewport(&self, width: usize, height: usize) -> Vec<(String, Option<(usize, usize)>)> {
+        let Some(selected) = self.boxes.get(self.selected) else {
+            return Vec::new();
+        };
+        let canvas_width = self.canvas.first().map(Vec::len).unwrap_or(0);
+        let offset = |start: usize, size: usize, view: usize, total: usize| {
+            (start + size / 2).saturating_sub(view / 2).min(total.saturating_sub(view))
+        };
+        let offset_x = offset(selected.x, selected.width, width, canvas_width);
+        let offset_y = offset(selected.y, selected.height, height, self.canvas.len());
+
+        self.canvas.iter()
+            .enumerate()
+            .skip(offset_y)
+            .take(height)
+            .map(|(y, row)| {
+                let line: String = row.iter().skip(offset_x).take(width).collect();
+                let highlight = (y >= selected.y && y < selected.y + selected.height).then(|| (
+                    selected.x.saturating_sub(offset_x),
+                    (selected.x + selected.width).saturating_sub(offset_x),
+                ));
+                (line, highlight)
+            })
+            .collect()
+    }
+}
+
+/// Lines inside a table's box: one per column, with its type and key badges
+fn box_lines(schema: &TableSchema) -> Vec<String> {
+    let mut lines: Vec<String> = schema.columns.iter()
+        .take(MAX_BOX_COLUMNS)
+        .map(|column| {
+            let badges = if column.is_primary_key { " PK" } else if column.is_foreign_key { " FK" } else { "" };
+            format!("{} {}{}", column.name, column.data_type, badges)
+        })
+        .collect();
+    if schema.columns.len() > MAX_BOX_COLUMNS {
+        lines.push(format!("… {} more", schema.columns.len() - MAX_BOX_COLUMNS));
+    }
+    if schema.table_info.is_view() {
+        lines.push("(view)".to_string());
+    }
+    if lines.is_empty() {
+        lines.push("(no columns)".to_string());
+    }
+    lines
+}
+
+fn draw_box(canvas: &mut [Vec<char>], rect: BoxRect, title: &str, lines: &[String]) {
+    let inner = rect.width - 2;
+    let title: String = format!(" {title} ").chars().take(inner).collect();
+    let top = format!("┌{title:─<inner$}┐");
+    put(canvas, rect.x, rect.y, &top);
+    for (i, line) in lines.iter().enumerate() {
+        let line: String = line.chars().take(inner - 2).collect();
+        put(canvas, rect.x, rect.y + 1 + i, &format!("│ {:<width$} │", line, width = inner - 2));
+    }
+    put(canvas, rect.x, rect.y + rect.height - 1, &format!("└{}┘", "─".repeat(inner)));
+}
+
+fn put(canvas: &mut [Vec<char>], x: usize, y: usize, text: &str) {
+    if let Some(row) = canvas.get_mut(y) {
+        for (i, c) in text.chars().enumerate() {
+            if let Some(cell) = row.get_mut(x + i) {
+                *cell = c;
+            }
+        }
+    }
+}
+
+// Lines cross rather than overwrite each other
+fn draw_horizontal(canvas: &mut [Vec<char>], y: usize, x0: usize, x1: usize) {
+    for cell in &mut canvas[y][x0..=x1] {
+        *cell = if *cell == '│' { '┼' } else { '─' };
+    }
+}
+
+fn draw_vertical(canvas: &mut [Vec<char>], x: usize, y0: usize, y1: usize) {
+    for row in canvas.iter_mut().take(y1 + 1).skip(y0) {
+        let cell = &mut row[x];
+        *cell = if *cell == '─' { '┼' } else { '│' };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referencing_tables_are_laid_out_right_of_referenced_ones() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name VARCHAR);
+             CREATE TABLE products (id INTEGER PRIMARY KEY);
+             CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id), product_id INTEGER REFERENCES products(id));",
+        ).unwrap();
+        let tables: Vec<TableInfo> = ["orders", "products", "users"].iter()
+            .map(|name| TableInfo::new(name.to_string()))
+            .collect();
+
+        let mut diagram = ErDiagram::load(&conn, &tables).unwrap();
+        assert!(diagram.other_relationships.is_empty());
+        assert_eq!(diagram.layers.len(), 2);
+        assert_eq!(diagram.selected_table(), Some("products"));
+
+        let text: Vec<String> = diagram.viewport(200, 50).into_iter().map(|(line, _)| line).collect();
+        assert!(text[0].starts_with("┌ products "));
+        assert!(text.iter().any(|line| line.contains("│ user_id INTEGER FK")));
+        assert_eq!(text.iter().filter(|line| line.contains('◄')).count(), 2);
+
+        diagram.move_selection(1, 0);
+        assert_eq!(diagram.selected_table(), Some("orders"));
+        diagram.move_selection(-1, 0);
+        diagram.move_selection(0, 1);
+        assert_eq!(diagram.selected_table(), Some("users"));
+    }
+}
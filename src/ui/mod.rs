@@ -1,27 +1,105 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap, Table, Row, Cell, TableState},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap, Table, Row, Cell, TableState, Tabs},
     Frame,
 };
 
 use crate::actions::ActionLogger;
+use crate::app::layouts::LayoutStore;
 use crate::app::state::{ApplicationState, NavigationPanel, AppState, TableCreationStep};
 use crate::db::DatabaseManager;
+use crate::db::catalog::{CatalogObject, CatalogObjectKind};
+use crate::db::edit::RowKey;
 use crate::workflows::DatabaseWorkflows;
 
+mod er_diagram;
+use er_diagram::ErDiagram;
+
+mod clipboard;
+use clipboard::copy_to_clipboard;
+
 mod file_browser;
 use file_browser::{render_file_browser_popup, FileBrowser, detect_file_type, FileType};
 
+mod mouse;
+use mouse::{ColumnDrag, GridColumn, MouseRegions, TableGridGeometry};
+
+mod sql_highlight;
+use sql_highlight::highlight_sql;
+
+/// A visible row of the sidebar catalog tree: database → schema → group → table or object → details
+struct SidebarRow {
+    // Identifies the node for the cursor and for expansion
+    key: String,
+    depth: usize,
+    node: SidebarNode,
+    label: String,
+    // None for leaves
+    expanded: Option<bool>,
+}
+
+enum SidebarNode {
+    Database,
+    Schema,
+    Group,
+    Table(usize),
+    Object,
+    Detail,
+}
+
+fn database_node_key(alias: Option<&str>) -> String {
+    format!("db:{}", alias.unwrap_or_default())
+}
+
+fn schema_node_key(schema: &str) -> String {
+    format!("schema:{schema}")
+}
+
+fn group_node_key(schema: &str, label: &str) -> String {
+    format!("group:{schema}:{label}")
+}
+
+fn object_node_key(object: &CatalogObject) -> String {
+    format!("object:{}:{}", object.kind.label(), crate::db::schema::qualified_table_name(&object.schema, &object.name))
+}
+
+/// Leaf rows such as columns or parameters below an expanded node
+fn detail_rows(parent_key: &str, depth: usize, details: &[String]) -> Vec<SidebarRow> {
+    details.iter().enumerate()
+        .map(|(index, detail)| SidebarRow {
+            key: format!("detail:{parent_key}:{index}"),
+            depth,
+            node: SidebarNode::Detail,
+            label: detail.clone(),
+            expanded: None,
+        })
+        .collect()
+}
+
 pub struct App {
     state: ApplicationState,
     database_manager: DatabaseManager,
     selected_db_index: usize,
     selected_table_index: usize,
+    // Key of the sidebar node under the cursor, None when it is on the table at `selected_table_index`
+    selected_sidebar_node: Option<String>,
+    // Sidebar nodes whose expansion differs from their default
+    toggled_sidebar_nodes: HashSet<String>,
     file_browser: Option<FileBrowser>,
     show_file_browser: bool,
+    // The file browser picks a database to ATTACH rather than connect to
+    attaching_database: bool,
+    er_diagram: Option<ErDiagram>,
     action_logger: ActionLogger,
+    layout_store: LayoutStore,
+    mouse_regions: MouseRegions,
+    column_drag: Option<ColumnDrag>,
+    should_quit: bool,
 }
 
 impl App {
@@ -71,14 +149,28 @@ impl App {
             }
         }
 
+        // Load saved view layouts (kept outside the database files)
+        let layout_store = LayoutStore::load().unwrap_or_else(|e| {
+            action_logger.log_error(&format!("Failed to load saved layouts: {e}"));
+            LayoutStore::default()
+        });
+
         let mut app = Self {
             state: ApplicationState::new(),
             database_manager,
             selected_db_index: 0,
             selected_table_index: 0,
+            selected_sidebar_node: None,
+            toggled_sidebar_nodes: HashSet::new(),
             file_browser: None,
             show_file_browser: false,
+            attaching_database: false,
+            er_diagram: None,
             action_logger,
+            layout_store,
+            mouse_regions: MouseRegions::default(),
+            column_drag: None,
+            should_quit: false,
         };
         
         app.sync_selected_db_index();
@@ -94,6 +186,45 @@ impl App {
         app
     }
 
+    /// Whether keystrokes are currently being typed into an input field
+    pub fn is_text_input_active(&self) -> bool {
+        self.state.is_entering_database_name
+            || self.state.is_entering_save_filename
+            || self.state.is_entering_view_name
+            || self.state.is_entering_query
+            || self.state.is_entering_compare
+            || self.state.is_entering_export_path
+            || self.state.is_editing_cell
+            || self.state.row_form.is_some()
+            || self.state.is_entering_bulk_update
+            || self.state.is_entering_column_change
+            || self.state.table_operation.is_some()
+            || self.state.is_entering_schema_name
+            || self.state.pending_attach.is_some()
+            || self.state.index_manager.as_ref().is_some_and(|manager| manager.form.is_some())
+            || self.state.definition_view.as_ref().is_some_and(|view| view.save_path.is_some())
+            || self.state.is_entering_schema_diff
+            || self.state.schema_diff.as_ref().is_some_and(|view| view.save_path.is_some())
+            || self.state.is_entering_sample_spec
+            || self.state.is_entering_layout_name
+            || self.state.is_searching
+            || (self.state.is_creating_table && self.state.table_creation_step != TableCreationStep::SelectingFile)
+    }
+
+    /// Quit, or ask what to do with staged edits first
+    pub fn request_quit(&mut self) {
+        if self.state.transaction.as_ref().is_some_and(|transaction| transaction.has_changes()) {
+            self.state.show_pending_changes = false;
+            self.state.is_confirming_quit = true;
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) {
         // Handle file browser first if it's open
         if self.show_file_browser {
@@ -101,6 +232,7 @@ impl App {
                 KeyCode::Esc => {
                     self.show_file_browser = false;
                     self.file_browser = None;
+                    self.attaching_database = false;
                 }
                 _ => {
                     if let Some(ref mut browser) = self.file_browser {
@@ -108,6 +240,10 @@ impl App {
                             // Detect file type and route appropriately
                             if let Some(file_type) = detect_file_type(&selected_path) {
                                 match file_type {
+                                    FileType::Database if self.attaching_database => {
+                                        self.state.start_attach(selected_path);
+                                        self.attaching_database = false;
+                                    }
                                     FileType::Database => {
                                         // Handle database file connection
                                         let mut workflows = DatabaseWorkflows::new(
@@ -230,2221 +366,5971 @@ impl App {
             return;
         }
 
-        // Handle search mode input
-        if self.state.is_searching {
+        // Handle query input
+        if self.state.is_entering_query {
             match key.code {
                 KeyCode::Esc => {
-                    self.state.cancel_search();
+                    self.state.cancel_query_input();
                 }
                 KeyCode::Enter => {
-                    if self.state.finalize_search() {
-                        // Filter was applied, refresh data without limit to show all results
-                        self.fetch_table_data_preserve_column_with_limit(false);
-                    }
+                    self.open_query_in_new_tab();
                 }
                 KeyCode::Backspace => {
-                    self.state.remove_char_from_search();
-                    self.validate_search_syntax();
+                    self.state.remove_char_from_query();
                 }
                 KeyCode::Char(c) => {
-                    self.state.add_char_to_search(c);
-                    self.validate_search_syntax();
+                    self.state.add_char_to_query(c);
                 }
                 _ => {}
             }
             return;
         }
 
-        // Handle delete confirmation
-        if self.state.is_delete_confirmation_active() {
+        // Handle compare input
+        if self.state.is_entering_compare {
             match key.code {
                 KeyCode::Esc => {
-                    self.state.cancel_delete_confirmation();
+                    self.state.cancel_compare_input();
                 }
-                KeyCode::Char('d') => {
-                    self.confirm_delete();
-                    self.state.cancel_delete_confirmation();
+                KeyCode::Enter => {
+                    self.compare_relations_from_input();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_compare();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_compare(c);
                 }
                 _ => {}
             }
             return;
         }
 
-        // Handle table creation input
-        if self.state.is_creating_table && self.state.table_creation_step == TableCreationStep::EnteringTableName {
+        // Handle export path input
+        if self.state.is_entering_export_path {
             match key.code {
                 KeyCode::Esc => {
-                    self.state.cancel_table_creation();
+                    self.state.cancel_export_path_input();
                 }
                 KeyCode::Enter => {
-                    if !self.state.new_table_name.trim().is_empty() {
-                        self.state.confirm_table_name();
-                        self.open_file_browser();
-                    }
+                    self.export_current_view();
                 }
                 KeyCode::Backspace => {
-                    self.state.remove_char_from_table_name();
+                    self.state.remove_char_from_export_path();
                 }
                 KeyCode::Char(c) => {
-                    self.state.add_char_to_table_name(c);
+                    self.state.add_char_to_export_path(c);
                 }
                 _ => {}
             }
             return;
         }
 
-
-        // Normal key handling
-        match key.code {
-            KeyCode::Tab => {
-                if self.state.inspect_mode {
-                    // In inspect mode: cycle between schema and statistics sections
-                    self.state.inspect_cycle_section();
-                } else if self.state.database_dropdown_expanded {
-                    // Close dropdown without making changes when Tab is pressed
-                    self.state.collapse_database_dropdown();
-                    self.state.set_dropdown_to_current_database(self.selected_db_index);
-                } else {
-                    self.state.next_panel();
-                }
-            }
-            KeyCode::BackTab => self.state.prev_panel(),
-            KeyCode::Esc => {
-                if self.state.is_modifying {
-                    // Cancel modifying mode
-                    self.state.cancel_modifying();
-                    self.fetch_table_data_preserve_column();
-                    // No popup - will show in status bar instead
-                } else if self.state.inspect_mode {
-                    // Exit inspect mode
-                    self.state.exit_inspect_mode();
-                } else if self.state.database_dropdown_expanded {
-                    // Close dropdown without making changes when Escape is pressed
-                    self.state.collapse_database_dropdown();
-                    self.state.set_dropdown_to_current_database(self.selected_db_index);
+        // Handle cell edit input
+        if self.state.is_editing_cell {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_cell_edit();
                 }
-                // Note: Could add other escape behaviors here in the future
-            }
-            KeyCode::Up => self.handle_vim_up(),
-            KeyCode::Down => self.handle_vim_down(),
-            KeyCode::Left => self.handle_vim_left(),
-            KeyCode::Right => self.handle_vim_right(),
-            KeyCode::Enter => self.handle_enter(),
-            KeyCode::Char('?') => self.show_help(),
-            KeyCode::Char('k') => self.handle_vim_up(),
-            KeyCode::Char('j') => self.handle_vim_down(),
-            KeyCode::Char('h') => self.handle_vim_left(),
-            KeyCode::Char('l') => self.handle_vim_right(),
-            KeyCode::Char('K') => self.handle_extreme_up(),
-            KeyCode::Char('J') => self.handle_extreme_down(),
-            KeyCode::Char('H') => self.handle_extreme_left(),
-            KeyCode::Char('L') => self.handle_extreme_right(),
-            KeyCode::Char('m') => self.handle_modify_mode(),
-            KeyCode::Char('i') => {
-                match self.state.active_panel {
-                    NavigationPanel::TableList => {
-                        // In table list: start import/table creation
-                        self.start_table_creation();
-                    }
-                    NavigationPanel::MainContent => {
-                        // In table viewer: enter inspect mode
-                        if self.state.table_data.is_some() {
-                            self.state.enter_inspect_mode();
-                        }
-                    }
-                    _ => {}
+                KeyCode::Enter => {
+                    self.apply_cell_edit();
                 }
-            }
-            KeyCode::Char('o') => {
-                if self.state.is_modifying {
-                    // In modifying mode: toggle column visibility
-                    self.state.toggle_column_visibility();
-                    self.fetch_table_data_preserve_column();
-                } else {
-                    // Normal mode: open file browser
-                    self.open_file_browser();
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_cell_edit();
                 }
-            }
-            KeyCode::Char('n') => {
-                // Start database name input
-                self.state.start_database_name_input();
-            }
-            KeyCode::Char('s') => {
-                // Start save database to file
-                if self.database_manager.get_current_database().is_some() {
-                    self.state.start_save_filename_input();
-                } else {
-                    self.state.show_error("No database selected to save".to_string());
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_cell_edit(c);
                 }
+                _ => {}
             }
-            KeyCode::Char('v') => {
-                // Start save view (only in table viewer or inspector mode)
-                if (self.state.active_panel == NavigationPanel::MainContent || self.state.inspect_mode) && 
-                   self.state.table_data.is_some() && self.state.selected_table.is_some() {
-                    self.state.start_view_name_input();
-                } else {
-                    self.state.show_error("No table selected to create view from".to_string());
-                }
+            return;
+        }
+
+        // Handle the index manager and its create/drop forms
+        if self.state.index_manager.is_some() {
+            self.handle_index_manager_key(key.code);
+            return;
+        }
+
+        // Handle the schema diff input
+        if self.state.is_entering_schema_diff {
+            match key.code {
+                KeyCode::Esc => self.state.cancel_schema_diff_input(),
+                KeyCode::Enter => self.compare_schemas_from_input(),
+                KeyCode::Backspace => self.state.remove_char_from_schema_diff(),
+                KeyCode::Char(c) => self.state.add_char_to_schema_diff(c),
+                _ => {}
             }
-            KeyCode::Char('d') => {
-                // Start delete confirmation for current selection
-                self.start_delete_confirmation();
+            return;
+        }
+
+        // Handle the migration script popup and the path it is saved to
+        if self.state.schema_diff.is_some() {
+            self.handle_schema_diff_key(key.code);
+            return;
+        }
+
+        // Handle the definition popup and the path it is saved to
+        if self.state.definition_view.is_some() {
+            self.handle_definition_view_key(key.code);
+            return;
+        }
+
+        // Handle scrolling of the storage breakdown popup
+        if self.state.storage_breakdown.is_some() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('B') => self.state.storage_breakdown = None,
+                KeyCode::Char('j') | KeyCode::Down => self.state.scroll_storage_breakdown(true),
+                KeyCode::Char('k') | KeyCode::Up => self.state.scroll_storage_breakdown(false),
+                _ => {}
             }
-            KeyCode::Char('D') => {
-                // Immediate delete without confirmation
-                self.immediate_delete();
+            return;
+        }
+
+        // Handle navigation of the ER diagram shown in the main panel
+        if self.er_diagram.is_some() {
+            self.handle_er_diagram_key(key.code);
+            return;
+        }
+
+        // Handle the list of tables referencing the selected row
+        if self.state.reverse_references.is_some() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('G') => self.state.reverse_references = None,
+                KeyCode::Char('j') | KeyCode::Down => self.state.move_reverse_reference_selection(true),
+                KeyCode::Char('k') | KeyCode::Up => self.state.move_reverse_reference_selection(false),
+                KeyCode::Enter => self.follow_reverse_reference(),
+                _ => {}
             }
-            KeyCode::Char('q') => {
-                // Quit handled by main loop
+            return;
+        }
+
+        // Handle the alias and mode of a database being attached
+        if self.state.pending_attach.is_some() {
+            match key.code {
+                KeyCode::Esc => self.state.cancel_attach(),
+                KeyCode::Enter => self.attach_database(),
+                KeyCode::Tab => self.state.toggle_attach_read_only(),
+                KeyCode::Backspace => self.state.remove_char_from_attach_alias(),
+                KeyCode::Char(c) => self.state.add_char_to_attach_alias(c),
+                _ => {}
             }
-            KeyCode::Char('x') => {
-                // Disconnect current database (moved from 'd')
-                if self.database_manager.get_current_database().is_some() {
-                    let mut workflows = DatabaseWorkflows::new(
-                        &mut self.database_manager,
-                        &mut self.action_logger,
-                        &mut self.state,
-                    );
-                    let _ = workflows.disconnect_current_database();
-                    self.sync_selected_db_index();
-                    self.selected_table_index = 0;
+            return;
+        }
+
+        // Handle the name of a schema being created
+        if self.state.is_entering_schema_name {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_schema_name_input();
                 }
-            }
-            KeyCode::Char('a') => {
-                // Toggle column in sort chain as ascending (only in table viewer)
-                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
-                    self.state.toggle_in_sort_chain(true);
-                    self.fetch_table_data_preserve_column();
+                KeyCode::Enter => {
+                    self.create_schema();
                 }
-            }
-            KeyCode::Char('A') => {
-                // Toggle column in sort chain as descending (only in table viewer)
-                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
-                    self.state.toggle_in_sort_chain(false);
-                    self.fetch_table_data_preserve_column();
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_schema_name();
                 }
-            }
-            KeyCode::Char('c') => {
-                // Clear all sorting (only in table viewer)
-                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
-                    self.state.clear_sort();
-                    self.fetch_table_data_preserve_column();
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_schema_name(c);
                 }
+                _ => {}
             }
-            KeyCode::Char('f') => {
-                // Start column filter mode (only in table viewer)
-                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
-                    if let Some(selected_idx) = self.state.get_selected_column_index() {
-                        self.state.start_column_search(selected_idx);
-                    }
+            return;
+        }
+
+        // Handle new name input for renaming or duplicating a table
+        if self.state.table_operation.is_some() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_table_operation();
                 }
-            }
-            KeyCode::Char('F') => {
-                // Clear filter on selected column (only in table viewer)
-                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
-                    if let Some(selected_col) = self.state.selected_column.clone() {
-                        self.state.clear_column_filter(&selected_col);
-                        self.fetch_table_data_preserve_column_with_limit(false);
-                    }
+                KeyCode::Enter => {
+                    self.submit_table_operation();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_table_operation();
                 }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_table_operation(c);
+                }
+                _ => {}
             }
-            KeyCode::Char('1') => self.state.set_left_panel(NavigationPanel::DatabaseList),
-            KeyCode::Char('2') => self.state.set_left_panel(NavigationPanel::TableList),
-            KeyCode::Char('3') => self.state.set_active_panel(NavigationPanel::MainContent),
-            _ => {}
+            return;
         }
-    }
-
 
-    // Vim navigation keys for UI navigation and modal modification
-    fn handle_vim_up(&mut self) {
-        // Handle modifying mode for inspect view
-        if self.state.is_modifying && self.state.inspect_mode {
-            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
-                let selected_row = self.state.inspect_selected_row;
-                if selected_row > 0 {
-                    let target_row = selected_row - 1;
-                    if self.state.reorder_column(selected_row, target_row) {
-                        self.fetch_table_data_preserve_column();
-                        self.state.inspect_selected_row = target_row;
-                    }
+        // Handle column change input
+        if self.state.is_entering_column_change {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_column_change_input();
                 }
+                KeyCode::Enter => {
+                    self.submit_column_change();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_column_change();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_column_change(c);
+                }
+                _ => {}
             }
             return;
         }
-        
-        // Handle inspect mode navigation
-        if self.state.inspect_mode {
-            // Normal navigation in inspect mode
-            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
-                // Move selection up in columns view
-                self.state.inspect_move_selection_up();
-            } else {
-                // Scroll up in statistics view
-                self.state.inspect_scroll_up();
+
+        // Handle bulk update expression input
+        if self.state.is_entering_bulk_update {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_bulk_update_input();
+                }
+                KeyCode::Enter => {
+                    self.preview_bulk_update();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_bulk_update();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_bulk_update(c);
+                }
+                _ => {}
             }
             return;
         }
 
-        match self.state.active_panel {
-            NavigationPanel::DatabaseList => {
-                let databases = self.database_manager.get_databases();
-                if !self.state.database_dropdown_expanded {
-                    // Expand dropdown when first pressing up/down
-                    self.state.expand_database_dropdown(databases.len());
-                    self.state.set_dropdown_to_current_database(self.selected_db_index);
-                } else {
-                    // Navigate within dropdown
-                    self.state.dropdown_move_up();
+        // Handle insert-row form
+        if self.state.row_form.is_some() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_row_form();
                 }
-            }
-            NavigationPanel::TableList => {
-                if self.selected_table_index > 0 {
-                    self.selected_table_index -= 1;
+                KeyCode::Enter => {
+                    self.submit_row_form();
                 }
-            }
-            NavigationPanel::MainContent => {
-                // Move selected row up
-                if let Some(ref _data) = self.state.table_data {
-                    let _visible_rows = 10; // Approximate - could be calculated from area
-                    self.state.move_selected_up();
+                KeyCode::Down | KeyCode::Tab => {
+                    self.state.row_form_next_field();
+                }
+                KeyCode::Up | KeyCode::BackTab => {
+                    self.state.row_form_prev_field();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_row_form();
                 }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_row_form(c);
+                }
+                _ => {}
             }
-            _ => {}
+            return;
         }
-    }
 
-    fn handle_vim_down(&mut self) {
-        // Handle modifying mode for inspect view
-        if self.state.is_modifying && self.state.inspect_mode {
-            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
-                let total_cols = self.state.get_virtual_column_order().len();
-                let selected_row = self.state.inspect_selected_row;
-                
-                if selected_row + 1 < total_cols {
-                    let target_row = selected_row + 1;
-                    if self.state.reorder_column(selected_row, target_row) {
-                        self.fetch_table_data_preserve_column();
-                        self.state.inspect_selected_row = target_row;
-                    }
+        // Handle sample spec input
+        if self.state.is_entering_sample_spec {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_sample_spec_input();
+                }
+                KeyCode::Enter => {
+                    self.apply_sample_spec_input();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_sample_spec();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_sample_spec(c);
                 }
+                _ => {}
             }
             return;
         }
-        
-        // Handle inspect mode navigation
-        if self.state.inspect_mode {
-            // Normal navigation in inspect mode
-            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
-                // Move selection down in columns view (use total columns, not just visible)
-                let total_columns = self.state.get_virtual_column_order().len();
-                let (_max_rows, visible_rows) = self.calculate_inspect_scroll_bounds();
-                self.state.inspect_move_selection_down(total_columns, visible_rows);
-            } else {
-                // Scroll down in statistics view
-                let (max_rows, visible_rows) = self.calculate_inspect_scroll_bounds();
-                self.state.inspect_scroll_down(max_rows, visible_rows);
+
+        // Handle layout name input
+        if self.state.is_entering_layout_name {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_layout_name_input();
+                }
+                KeyCode::Enter if !self.state.new_layout_name.trim().is_empty() => {
+                    self.save_current_layout();
+                    self.state.cancel_layout_name_input();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_layout_name();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_layout_name(c);
+                }
+                _ => {}
             }
             return;
         }
 
-        let databases = self.database_manager.get_databases();
-        let current_tables = self.get_current_tables();
-
-        match self.state.active_panel {
-            NavigationPanel::DatabaseList => {
-                if !self.state.database_dropdown_expanded {
-                    // Expand dropdown when first pressing up/down
-                    self.state.expand_database_dropdown(databases.len());
-                    self.state.set_dropdown_to_current_database(self.selected_db_index);
-                } else {
-                    // Navigate within dropdown
-                    self.state.dropdown_move_down(databases.len());
+        // Handle layout picker
+        if self.state.is_picking_layout {
+            let num_layouts = self.get_current_layout_names().len();
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('b') => {
+                    self.state.close_layout_picker();
                 }
-            }
-            NavigationPanel::TableList => {
-                if self.selected_table_index < current_tables.len().saturating_sub(1) {
-                    self.selected_table_index += 1;
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.state.layout_picker_move_up();
                 }
-            }
-            NavigationPanel::MainContent => {
-                // Move selected row down
-                if let Some(ref data) = self.state.table_data {
-                    // Calculate visible rows accounting for word wrapping using cached area height
-                    let area_height = self.state.last_table_area_height;
-                    let visible_rows = self.calculate_visible_data_rows_from_height(area_height, data);
-                    self.state.move_selected_down(data.rows.len(), visible_rows);
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.state.layout_picker_move_down(num_layouts);
+                }
+                KeyCode::Enter => {
+                    self.apply_selected_layout();
+                }
+                KeyCode::Char('n') => {
+                    self.state.start_layout_name_input();
                 }
+                KeyCode::Char('d') => {
+                    self.delete_selected_layout();
+                }
+                _ => {}
             }
-            _ => {}
+            return;
         }
-    }
 
-    fn handle_vim_left(&mut self) {
-        // Handle modifying mode for table viewer - move selected column left
-        if self.state.is_modifying && matches!(self.state.active_panel, NavigationPanel::MainContent) && !self.state.inspect_mode {
-            if let Some(selected_column) = &self.state.selected_column {
-                if let Some(current_index) = self.state.get_column_index_by_name(selected_column) {
-                    if current_index > 0 {
-                        let target_index = current_index - 1;
-                        if self.state.reorder_column(current_index, target_index) {
-                            self.fetch_table_data_preserve_column();
-                        }
+        // Handle search mode input
+        if self.state.is_searching {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_search();
+                }
+                KeyCode::Enter => {
+                    self.state.begin_view_change();
+                    let applied = self.state.finalize_search();
+                    self.state.end_view_change();
+                    if applied {
+                        // Filter was applied, refresh data without limit to show all results
+                        self.fetch_table_data_preserve_column_with_limit(false);
                     }
                 }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_search();
+                    self.validate_search_syntax();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_search(c);
+                    self.validate_search_syntax();
+                }
+                _ => {}
             }
             return;
         }
-        
-        // Handle inspect mode navigation - treat left as page up
-        if self.state.inspect_mode {
-            // Page up: scroll up by multiple rows
-            for _ in 0..5 {
-                self.state.inspect_scroll_up();
+
+        // Handle the quit prompt shown while edits are uncommitted
+        if self.state.is_confirming_quit {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.is_confirming_quit = false;
+                }
+                KeyCode::Char('c') => {
+                    self.state.is_confirming_quit = false;
+                    self.should_quit = self.end_transaction(true);
+                }
+                KeyCode::Char('r') => {
+                    self.state.is_confirming_quit = false;
+                    self.should_quit = self.end_transaction(false);
+                }
+                _ => {}
             }
             return;
         }
 
-        match self.state.active_panel {
-            NavigationPanel::MainContent => {
-                // Move selected column left
-                if self.state.table_data.is_some() {
-                    self.state.move_selected_left();
+        // Handle the pending changes panel
+        if self.state.show_pending_changes {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('T') => {
+                    self.state.show_pending_changes = false;
                 }
+                KeyCode::Char('c') => {
+                    self.end_transaction(true);
+                }
+                KeyCode::Char('r') => {
+                    self.end_transaction(false);
+                }
+                _ => {}
             }
-            NavigationPanel::TableList => {
-                // From table list, go to database widget  
-                self.state.set_left_panel(NavigationPanel::DatabaseList);
-                self.state.collapse_database_dropdown(); // Close dropdown if open
-            }
-            NavigationPanel::DatabaseList => {
-                // From database list, underflow to table list
-                self.state.set_left_panel(NavigationPanel::TableList);
-                self.state.collapse_database_dropdown(); // Close dropdown if open
-            }
-            _ => {}
+            return;
         }
-    }
 
-    fn handle_vim_right(&mut self) {
-        // Handle modifying mode for table viewer - move selected column right
-        if self.state.is_modifying && matches!(self.state.active_panel, NavigationPanel::MainContent) && !self.state.inspect_mode {
-            if let Some(selected_column) = &self.state.selected_column {
-                if let Some(current_index) = self.state.get_column_index_by_name(selected_column) {
-                    let virtual_order = self.state.get_virtual_column_order();
-                    if current_index < virtual_order.len() - 1 {
-                        let target_index = current_index + 1;
-                        if self.state.reorder_column(current_index, target_index) {
-                            self.fetch_table_data_preserve_column();
-                        }
+        // Handle column change confirmation
+        if self.state.pending_column_change.is_some() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.pending_column_change = None;
+                }
+                KeyCode::Char('y') => {
+                    if let Some(pending) = self.state.pending_column_change.take() {
+                        self.alter_column(pending.table_name, pending.column_name, pending.change);
                     }
                 }
+                _ => {}
             }
             return;
         }
-        
-        // Handle inspect mode navigation - treat right as page down
-        if self.state.inspect_mode {
-            // Page down: scroll down by multiple rows
-            let (max_rows, visible_rows) = self.calculate_inspect_scroll_bounds();
-            for _ in 0..5 {
-                self.state.inspect_scroll_down(max_rows, visible_rows);
+
+        // Handle bulk change confirmation
+        if self.state.pending_bulk_change.is_some() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.pending_bulk_change = None;
+                }
+                KeyCode::Char('y') => {
+                    self.apply_bulk_change();
+                }
+                _ => {}
             }
             return;
         }
 
-        match self.state.active_panel {
-            NavigationPanel::DatabaseList => {
-                // From database widget, go to tables widget
-                self.state.set_left_panel(NavigationPanel::TableList);
-                self.state.collapse_database_dropdown(); // Close dropdown if open
-            }
-            NavigationPanel::MainContent => {
-                // Move selected column right
-                if let Some(ref data) = self.state.table_data {
-                    // Calculate visible columns to pass to method
-                    let visible_cols = 5; // Approximate - could be calculated from area
-                    self.state.move_selected_right(data.columns.len(), visible_cols);
-                }
-            }
-            NavigationPanel::TableList => {
-                // From table list, overflow to database list
-                self.state.set_left_panel(NavigationPanel::DatabaseList);
-            }
-            _ => {}
-        }
-    }
-
-    // Extreme movement handlers (capital letters)
-    fn handle_extreme_up(&mut self) {
-        if self.state.is_modifying {
-            if self.state.move_column_extreme_up() {
-                self.fetch_table_data_preserve_column();
+        // Handle delete confirmation
+        if self.state.is_delete_confirmation_active() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_delete_confirmation();
+                }
+                KeyCode::Char('d') => {
+                    self.confirm_delete();
+                    self.state.cancel_delete_confirmation();
+                }
+                _ => {}
             }
-        } else {
-            self.state.navigate_extreme_up();
+            return;
         }
-    }
 
-    fn handle_extreme_down(&mut self) {
-        if self.state.is_modifying {
-            if self.state.move_column_extreme_down() {
-                self.fetch_table_data_preserve_column();
+        // Handle table creation input
+        if self.state.is_creating_table && self.state.table_creation_step == TableCreationStep::EnteringTableName {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_table_creation();
+                }
+                KeyCode::Enter => {
+                    self.state.confirm_table_name();
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_table_name();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_table_name(c);
+                }
+                _ => {}
             }
-        } else {
-            self.state.navigate_extreme_down();
+            return;
         }
-    }
 
-    fn handle_extreme_left(&mut self) {
-        if self.state.is_modifying {
-            if self.state.move_column_extreme_left() {
-                self.fetch_table_data_preserve_column();
+        // Handle choosing between importing a file and designing the table
+        if self.state.is_creating_table && self.state.table_creation_step == TableCreationStep::ChoosingSource {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_table_creation();
+                }
+                KeyCode::Char('f') => {
+                    self.state.choose_file_source();
+                    self.open_file_browser();
+                }
+                KeyCode::Char('d') => {
+                    self.state.start_table_design();
+                }
+                _ => {}
             }
-        } else {
-            self.state.navigate_extreme_left();
+            return;
         }
-    }
 
-    fn handle_extreme_right(&mut self) {
-        if self.state.is_modifying {
-            if self.state.move_column_extreme_right() {
-                self.fetch_table_data_preserve_column();
+        // Handle the table designer's column editor
+        if self.state.column_editor.is_some() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_column_editor();
+                }
+                KeyCode::Enter if !self.state.save_column_editor() => {
+                    self.state.show_error("Give the column a name first".to_string());
+                }
+                KeyCode::Tab | KeyCode::Down => {
+                    self.state.column_editor_next_field();
+                }
+                KeyCode::BackTab | KeyCode::Up => {
+                    self.state.column_editor_prev_field();
+                }
+                KeyCode::Left => {
+                    self.state.column_editor_cycle(false);
+                }
+                KeyCode::Right => {
+                    self.state.column_editor_cycle(true);
+                }
+                KeyCode::Backspace => {
+                    self.state.remove_char_from_column_editor();
+                }
+                KeyCode::Char(c) => {
+                    self.state.add_char_to_column_editor(c);
+                }
+                _ => {}
             }
-        } else {
-            self.state.navigate_extreme_right();
-        }
-    }
-
-    fn handle_modify_mode(&mut self) {
-        if ((self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some()) ||
-           (self.state.inspect_mode && matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema)))
-           && !self.state.is_modifying {
-            self.state.start_modifying();
-            // No popup - will show in status bar instead
+            return;
         }
-    }
 
-    fn get_current_tables(&self) -> Vec<String> {
-        if let Some(current_db) = self.database_manager.get_current_database() {
-            if let Some(db_info) = self.database_manager.get_database_info(current_db) {
-                return db_info.tables.iter().map(|t| t.name.clone()).collect();
+        // Handle the table designer's column list
+        if self.state.is_creating_table && self.state.table_creation_step == TableCreationStep::DesigningColumns {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.cancel_table_creation();
+                }
+                KeyCode::Char('a') => {
+                    self.state.start_column_editor(None);
+                }
+                KeyCode::Char('e') | KeyCode::Enter if !self.state.table_design.is_empty() => {
+                    self.state.start_column_editor(Some(self.state.table_design_selected));
+                }
+                KeyCode::Char('d') => {
+                    self.state.remove_designed_column();
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.state.move_design_selection(true);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.state.move_design_selection(false);
+                }
+                KeyCode::Char('p') => {
+                    match crate::db::design::create_table_sql(&self.state.new_table_name, &self.state.table_design) {
+                        Ok(_) => self.state.table_creation_step = TableCreationStep::PreviewingDdl,
+                        Err(e) => self.state.show_error(e.to_string()),
+                    }
+                }
+                _ => {}
             }
+            return;
         }
-        Vec::new()
-    }
 
-    fn get_current_table_infos(&self) -> Vec<&crate::db::TableInfo> {
-        if let Some(current_db) = self.database_manager.get_current_database() {
-            if let Some(db_info) = self.database_manager.get_database_info(current_db) {
-                return db_info.tables.iter().collect();
+        // Handle the generated DDL preview
+        if self.state.is_creating_table && self.state.table_creation_step == TableCreationStep::PreviewingDdl {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.table_creation_step = TableCreationStep::DesigningColumns;
+                }
+                KeyCode::Enter => {
+                    self.create_designed_table();
+                }
+                _ => {}
             }
+            return;
         }
-        Vec::new()
-    }
 
-    fn sync_selected_db_index(&mut self) {
-        if let Some(current_db) = self.database_manager.get_current_database() {
-            let databases = self.database_manager.get_databases();
-            if let Some(index) = databases.iter().position(|db| db.name == current_db) {
-                self.selected_db_index = index;
-            }
-        }
-    }
 
-    fn sync_selected_table_index(&mut self) {
-        if let Some(current_table) = &self.state.selected_table {
-            let current_tables = self.get_current_tables();
-            if let Some(index) = current_tables.iter().position(|table| table == current_table) {
-                self.selected_table_index = index;
+        // Normal key handling
+        match key.code {
+            KeyCode::Tab => {
+                if self.state.inspect_mode {
+                    // In inspect mode: cycle between schema and statistics sections
+                    self.state.inspect_cycle_section();
+                } else if self.state.database_dropdown_expanded {
+                    // Close dropdown without making changes when Tab is pressed
+                    self.state.collapse_database_dropdown();
+                    self.state.set_dropdown_to_current_database(self.selected_db_index);
+                } else {
+                    self.state.next_panel();
+                }
             }
-        }
-    }
-
-    fn refresh_current_database(&mut self) {
-        if let Some(current_db) = self.database_manager.get_current_database() {
-            let current_db = current_db.to_string();
-            if let Err(e) = self.database_manager.refresh_database(&current_db) {
-                self.state.show_error(format!("Failed to refresh database: {e}"));
+            KeyCode::BackTab => self.state.prev_panel(),
+            KeyCode::Esc => {
+                if self.state.is_modifying {
+                    // Cancel modifying mode
+                    self.state.cancel_modifying();
+                    self.fetch_table_data_preserve_column();
+                    // No popup - will show in status bar instead
+                } else if self.state.inspect_mode {
+                    // Exit inspect mode
+                    self.state.exit_inspect_mode();
+                } else if self.state.visual_row_anchor.is_some() {
+                    // Leave visual row selection
+                    self.state.toggle_visual_row_selection();
+                } else if self.state.database_dropdown_expanded {
+                    // Close dropdown without making changes when Escape is pressed
+                    self.state.collapse_database_dropdown();
+                    self.state.set_dropdown_to_current_database(self.selected_db_index);
+                }
+                // Note: Could add other escape behaviors here in the future
             }
-        }
-    }
-
-    fn render_table_widget(&self, f: &mut Frame, area: Rect, data: &crate::db::query::QueryResult, title: &str) {
-        let border_style = self.get_panel_border_style(NavigationPanel::MainContent);
-
-        // Create mapping from data column indices to original virtual column indices
-        // Since hidden columns are filtered out in SQL, data.columns only contains visible columns
-        let visible_columns = self.state.get_visible_columns();
-        
-        // Calculate visible rows and columns
-        let available_height = area.height.saturating_sub(3) as usize; // Subtract borders and header
-        let start_row = self.state.scroll_y;
-        let end_row = (start_row + available_height).min(data.rows.len());
-        
-        // Calculate column constraints and visible columns
-        let mut constraints = Vec::new();
-        let mut visible_cols = Vec::new();
-        let available_width = area.width.saturating_sub(2) as usize; // Subtract borders
-        let start_col = self.state.scroll_x;
-        let mut used_width = 0;
-        
-        // Calculate individual column widths based on content
-        let min_col_width = 8;
-        let mut column_widths = Vec::new();
-        
-        for (data_col_idx, col_name) in data.columns.iter().enumerate() {
-            // Map data column index to original virtual column index
-            let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
-            
-            // Calculate header width using the final header text (including sort indicators)
-            let final_header_text = self.get_final_header_text(virtual_col_idx, col_name);
-            let header_width = final_header_text.chars().count();
-            let mut max_data_width = 0;
-            
-            // Calculate max data width for visible rows
-            if self.state.is_column_expanded(virtual_col_idx) {
-                // For expanded columns, check all visible rows for more accurate width
-                for row in data.rows[start_row..end_row].iter() {
-                    if let Some(cell) = row.get(data_col_idx) {
-                        max_data_width = max_data_width.max(cell.chars().count());
+            KeyCode::Up => self.handle_vim_up(),
+            KeyCode::Down => self.handle_vim_down(),
+            KeyCode::Left => self.handle_vim_left(),
+            KeyCode::Right => self.handle_vim_right(),
+            KeyCode::Enter => self.handle_enter(),
+            KeyCode::Char('?') => self.show_help(),
+            KeyCode::Char('k') => self.handle_vim_up(),
+            KeyCode::Char('j') => self.handle_vim_down(),
+            KeyCode::Char('h') => self.handle_vim_left(),
+            KeyCode::Char('l') => self.handle_vim_right(),
+            KeyCode::Char('K') => self.handle_extreme_up(),
+            KeyCode::Char('J') => self.handle_extreme_down(),
+            KeyCode::Char('H') => self.handle_extreme_left(),
+            KeyCode::Char('L') => self.handle_extreme_right(),
+            KeyCode::Char('m') => self.handle_modify_mode(),
+            KeyCode::Char('i') => {
+                match self.state.active_panel {
+                    NavigationPanel::TableList => {
+                        // In table list: start import/table creation
+                        self.start_table_creation();
                     }
-                }
-            } else {
-                // For normal columns, sample first 10 rows for performance
-                for row in data.rows.iter().take(10) {
-                    if let Some(cell) = row.get(data_col_idx) {
-                        max_data_width = max_data_width.max(cell.chars().count());
+                    NavigationPanel::MainContent => {
+                        // In table viewer: enter inspect mode
+                        if self.state.table_data.is_some() {
+                            self.state.enter_inspect_mode();
+                            self.load_inspect_data();
+                        }
                     }
+                    _ => {}
                 }
             }
-            
-            let col_width = if self.state.is_column_expanded(virtual_col_idx) {
-                // Expanded column: fit content up to max of 50 characters
-                header_width.max(max_data_width).max(min_col_width).min(50)
-            } else {
-                // Normal column: limit to 25 characters
-                header_width.max(max_data_width).max(min_col_width).min(25)
+            KeyCode::Char('o') => {
+                if self.state.is_modifying {
+                    // In modifying mode: toggle column visibility
+                    self.state.toggle_column_visibility();
+                    self.fetch_table_data_preserve_column();
+                } else {
+                    // Normal mode: open file browser
+                    self.open_file_browser();
+                }
+            }
+            KeyCode::Char('n') => {
+                // Start database name input
+                self.state.start_database_name_input();
+            }
+            KeyCode::Char('s') => {
+                // Start save database to file
+                if self.database_manager.get_current_database().is_some() {
+                    self.state.start_save_filename_input();
+                } else {
+                    self.state.show_error("No database selected to save".to_string());
+                }
+            }
+            KeyCode::Char('v') => {
+                // Start save view (only in table viewer or inspector mode)
+                if (self.state.active_panel == NavigationPanel::MainContent || self.state.inspect_mode) && 
+                   self.state.table_data.is_some() && self.state.selected_table.is_some() {
+                    self.state.start_view_name_input();
+                } else {
+                    self.state.show_error("No table selected to create view from".to_string());
+                }
+            }
+            KeyCode::Char('b') => {
+                // Open saved layout picker (only in table viewer)
+                if self.state.active_panel == NavigationPanel::MainContent &&
+                   self.state.table_data.is_some() && self.state.selected_table.is_some() {
+                    self.state.start_layout_picker();
+                } else {
+                    self.state.show_error("No table selected to manage layouts for".to_string());
+                }
+            }
+            KeyCode::Char('S') => {
+                // Configure sampling (only in table viewer or inspector mode)
+                if (self.state.active_panel == NavigationPanel::MainContent || self.state.inspect_mode) &&
+                   self.state.table_data.is_some() && self.state.selected_table.is_some() {
+                    self.state.start_sample_spec_input();
+                } else {
+                    self.state.show_error("No table selected to sample".to_string());
+                }
+            }
+            KeyCode::Char('d') => {
+                // Start delete confirmation for current selection
+                self.start_delete_confirmation();
+            }
+            KeyCode::Char('D') => {
+                // Immediate delete without confirmation
+                self.immediate_delete();
+            }
+            KeyCode::Char('q') => {
+                // Quit handled by main loop
+            }
+            KeyCode::Char('O') => self.open_attach_browser(),
+            KeyCode::Char('x') if self.state.active_panel == NavigationPanel::TableList
+                && self.get_selected_attached_alias().is_some() => self.detach_selected_database(),
+            KeyCode::Char('x') => {
+                // Disconnect current database (moved from 'd')
+                if self.database_manager.get_current_database().is_some() {
+                    let mut workflows = DatabaseWorkflows::new(
+                        &mut self.database_manager,
+                        &mut self.action_logger,
+                        &mut self.state,
+                    );
+                    let _ = workflows.disconnect_current_database();
+                    self.selected_table_index = 0;
+                    self.selected_sidebar_node = None;
+                    let database = self.state.selected_database.clone();
+                    self.activate_tab_database(database);
+                }
+            }
+            KeyCode::Char('a') if self.state.inspect_mode
+                && matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema)
+                && !self.state.is_modifying => {
+                // Alter the selected column from the schema section
+                self.start_column_change();
+            }
+            KeyCode::Char('a') => {
+                // Toggle column in sort chain as ascending (only in table viewer)
+                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
+                    self.state.begin_view_change();
+                    self.state.toggle_in_sort_chain(true);
+                    self.state.end_view_change();
+                    self.fetch_table_data_preserve_column();
+                }
+            }
+            KeyCode::Char('A') => {
+                // Toggle column in sort chain as descending (only in table viewer)
+                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
+                    self.state.begin_view_change();
+                    self.state.toggle_in_sort_chain(false);
+                    self.state.end_view_change();
+                    self.fetch_table_data_preserve_column();
+                }
+            }
+            KeyCode::Char('c') => {
+                // Clear all sorting (only in table viewer)
+                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
+                    self.state.begin_view_change();
+                    self.state.clear_sort();
+                    self.state.end_view_change();
+                    self.fetch_table_data_preserve_column();
+                }
+            }
+            KeyCode::Char('f') => {
+                // Start column filter mode (only in table viewer)
+                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
+                    if let Some(selected_idx) = self.state.get_selected_column_index() {
+                        self.state.start_column_search(selected_idx);
+                    }
+                }
+            }
+            KeyCode::Char('F') => {
+                // Clear filter on selected column (only in table viewer)
+                if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() {
+                    if let Some(selected_col) = self.state.selected_column.clone() {
+                        self.state.begin_view_change();
+                        self.state.clear_column_filter(&selected_col);
+                        self.state.end_view_change();
+                        self.fetch_table_data_preserve_column_with_limit(false);
+                    }
+                }
+            }
+            KeyCode::Char('t') => self.open_selected_table_in_new_tab(),
+            KeyCode::Char(']') if !self.state.is_modifying => {
+                let database = self.state.next_tab();
+                self.activate_tab_database(database);
+            }
+            KeyCode::Char('[') if !self.state.is_modifying => {
+                let database = self.state.prev_tab();
+                self.activate_tab_database(database);
+            }
+            KeyCode::Char('w') if !self.state.is_modifying => {
+                let database = self.state.close_active_tab();
+                self.activate_tab_database(database);
+            }
+            KeyCode::Char('|') => {
+                let toggled = self.state.toggle_split();
+                if !toggled {
+                    self.state.show_error("Open a second tab to compare side by side".to_string());
+                }
+            }
+            KeyCode::Char('\\') if !self.state.is_modifying => {
+                // Move focus to the other pane of the split
+                if let Some(partner) = self.state.split_tab {
+                    let database = self.state.switch_to_tab(partner);
+                    self.activate_tab_database(database);
+                }
+            }
+            KeyCode::Char('y') if self.state.split_tab.is_some() => {
+                self.state.cycle_split_sync();
+                match self.state.get_split_sync_display() {
+                    Some(sync) => self.state.show_info(format!("Split view: {sync}")),
+                    None => self.state.show_info("Split view: sync off".to_string()),
+                }
+            }
+            KeyCode::Char('C') => {
+                // Compare two relations row by row
+                if self.database_manager.get_current_database().is_some() {
+                    self.state.start_compare_input();
+                } else {
+                    self.state.show_error("No database selected to compare tables in".to_string());
+                }
+            }
+            KeyCode::Char('e') => {
+                // Export the current view (only in table viewer)
+                if self.state.active_panel == NavigationPanel::MainContent &&
+                   self.state.table_data.is_some() && self.state.selected_table.is_some() {
+                    self.state.start_export_path_input();
+                } else {
+                    self.state.show_error("No table selected to export".to_string());
+                }
+            }
+            KeyCode::Char('E') if self.state.active_panel == NavigationPanel::MainContent && !self.state.is_modifying => {
+                // Edit the selected cell (only in table viewer)
+                self.start_cell_edit();
+            }
+            KeyCode::Char('I') if self.state.active_panel == NavigationPanel::MainContent && !self.state.is_modifying => {
+                // Insert a new row through a form
+                self.start_insert_row(false);
+            }
+            KeyCode::Char('Y') if self.state.active_panel == NavigationPanel::MainContent && !self.state.is_modifying => {
+                // Insert a copy of the selected row
+                self.start_insert_row(true);
+            }
+            KeyCode::Char('U') if self.state.active_panel == NavigationPanel::MainContent && !self.state.is_modifying => {
+                // Set the selected column on every row matching the filters
+                self.start_bulk_update();
+            }
+            KeyCode::Char('N') => self.open_index_manager(),
+            KeyCode::Char('B') => self.open_storage_breakdown(),
+            KeyCode::Char('Q') => self.open_definition_view(),
+            KeyCode::Char('W') => {
+                // Compare the schemas of two databases
+                if self.database_manager.get_databases().is_empty() {
+                    self.state.show_error("No databases open to compare".to_string());
+                } else {
+                    let databases: Vec<String> = self.database_manager.get_databases().iter()
+                        .map(|info| info.name.clone())
+                        .collect();
+                    self.state.start_schema_diff_input(&databases, self.database_manager.get_current_database());
+                }
+            }
+            KeyCode::Char('R') => self.open_er_diagram(),
+            KeyCode::Char('g') if self.state.active_panel == NavigationPanel::MainContent && !self.state.is_modifying => {
+                self.follow_foreign_key();
+            }
+            KeyCode::Char('G') if self.state.active_panel == NavigationPanel::MainContent && !self.state.is_modifying => {
+                self.show_reverse_references();
+            }
+            KeyCode::Backspace if self.state.active_panel == NavigationPanel::MainContent => {
+                self.relation_back();
+            }
+            KeyCode::Char('X') if self.state.active_panel == NavigationPanel::TableList => {
+                self.start_truncate_confirmation();
+            }
+            KeyCode::Char('X') if self.state.active_panel == NavigationPanel::MainContent && !self.state.is_modifying => {
+                // Delete every row matching the filters
+                self.start_bulk_delete();
+            }
+            KeyCode::Char('T') => {
+                // Start transaction mode, or review the staged changes of the open transaction
+                if self.state.transaction.is_some() {
+                    self.state.show_pending_changes = true;
+                } else {
+                    self.begin_transaction();
+                }
+            }
+            KeyCode::Char('V') if self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some() => {
+                // Select a range of rows, e.g. to delete them together
+                self.state.toggle_visual_row_selection();
+            }
+            KeyCode::Char(':') => {
+                // Run an ad-hoc query into a new tab
+                if self.database_manager.get_current_database().is_some() {
+                    self.state.start_query_input();
+                } else {
+                    self.state.show_error("No database selected to query".to_string());
+                }
+            }
+            KeyCode::Char('u') => self.undo_view_change(),
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => self.redo_view_change(),
+            KeyCode::Char('r') if self.state.active_panel == NavigationPanel::TableList => {
+                self.start_table_operation(crate::app::state::TableOperationKind::Rename);
+            }
+            KeyCode::Char('p') if self.state.active_panel == NavigationPanel::TableList => {
+                self.start_table_operation(crate::app::state::TableOperationKind::Duplicate { with_data: false });
+            }
+            KeyCode::Char('P') if self.state.active_panel == NavigationPanel::TableList => {
+                self.start_table_operation(crate::app::state::TableOperationKind::Duplicate { with_data: true });
+            }
+            KeyCode::Char('#') if self.state.active_panel == NavigationPanel::TableList => {
+                self.start_table_operation(crate::app::state::TableOperationKind::Comment);
+            }
+            KeyCode::Char(' ') if self.state.active_panel == NavigationPanel::TableList => {
+                self.toggle_selected_sidebar_node();
+            }
+            KeyCode::Char('M') if self.state.active_panel == NavigationPanel::TableList => {
+                if self.database_manager.get_current_database().is_some() {
+                    self.state.start_schema_name_input();
+                } else {
+                    self.state.show_error("Please connect to a database first".to_string());
+                }
+            }
+            KeyCode::Char('1') => self.state.set_left_panel(NavigationPanel::DatabaseList),
+            KeyCode::Char('2') => self.state.set_left_panel(NavigationPanel::TableList),
+            KeyCode::Char('3') => self.state.set_active_panel(NavigationPanel::MainContent),
+            _ => {}
+        }
+    }
+
+
+    // Vim navigation keys for UI navigation and modal modification
+    fn handle_vim_up(&mut self) {
+        // Handle modifying mode for inspect view
+        if self.state.is_modifying && self.state.inspect_mode {
+            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
+                let selected_row = self.state.inspect_selected_row;
+                if selected_row > 0 {
+                    let target_row = selected_row - 1;
+                    if self.state.reorder_column(selected_row, target_row) {
+                        self.fetch_table_data_preserve_column();
+                        self.state.inspect_selected_row = target_row;
+                    }
+                }
+            }
+            return;
+        }
+        
+        // Handle inspect mode navigation
+        if self.state.inspect_mode {
+            // Normal navigation in inspect mode
+            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
+                // Move selection up in columns view
+                self.state.inspect_move_selection_up();
+            } else {
+                // Scroll up in statistics view
+                self.state.inspect_scroll_up();
+            }
+            return;
+        }
+
+        match self.state.active_panel {
+            NavigationPanel::DatabaseList => {
+                let databases = self.database_manager.get_databases();
+                if !self.state.database_dropdown_expanded {
+                    // Expand dropdown when first pressing up/down
+                    self.state.expand_database_dropdown(databases.len());
+                    self.state.set_dropdown_to_current_database(self.selected_db_index);
+                } else {
+                    // Navigate within dropdown
+                    self.state.dropdown_move_up();
+                }
+            }
+            NavigationPanel::TableList => self.move_sidebar_selection(false),
+            NavigationPanel::MainContent => {
+                // Move selected row up
+                if let Some(ref _data) = self.state.table_data {
+                    let _visible_rows = 10; // Approximate - could be calculated from area
+                    self.state.move_selected_up();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_vim_down(&mut self) {
+        // Handle modifying mode for inspect view
+        if self.state.is_modifying && self.state.inspect_mode {
+            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
+                let total_cols = self.state.get_virtual_column_order().len();
+                let selected_row = self.state.inspect_selected_row;
+                
+                if selected_row + 1 < total_cols {
+                    let target_row = selected_row + 1;
+                    if self.state.reorder_column(selected_row, target_row) {
+                        self.fetch_table_data_preserve_column();
+                        self.state.inspect_selected_row = target_row;
+                    }
+                }
+            }
+            return;
+        }
+        
+        // Handle inspect mode navigation
+        if self.state.inspect_mode {
+            // Normal navigation in inspect mode
+            if matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema) {
+                // Move selection down in columns view (use total columns, not just visible)
+                let total_columns = self.state.get_virtual_column_order().len();
+                let (_max_rows, visible_rows) = self.calculate_inspect_scroll_bounds();
+                self.state.inspect_move_selection_down(total_columns, visible_rows);
+            } else {
+                // Scroll down in statistics view
+                let (max_rows, visible_rows) = self.calculate_inspect_scroll_bounds();
+                self.state.inspect_scroll_down(max_rows, visible_rows);
+            }
+            return;
+        }
+
+        let databases = self.database_manager.get_databases();
+
+        match self.state.active_panel {
+            NavigationPanel::DatabaseList => {
+                if !self.state.database_dropdown_expanded {
+                    // Expand dropdown when first pressing up/down
+                    self.state.expand_database_dropdown(databases.len());
+                    self.state.set_dropdown_to_current_database(self.selected_db_index);
+                } else {
+                    // Navigate within dropdown
+                    self.state.dropdown_move_down(databases.len());
+                }
+            }
+            NavigationPanel::TableList => self.move_sidebar_selection(true),
+            NavigationPanel::MainContent => {
+                // Move selected row down
+                if let Some(ref data) = self.state.table_data {
+                    // Calculate visible rows accounting for word wrapping using cached area height
+                    let area_height = self.state.last_table_area_height;
+                    let visible_rows = self.calculate_visible_data_rows_from_height(area_height, data);
+                    self.state.move_selected_down(data.rows.len(), visible_rows);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_vim_left(&mut self) {
+        // Handle modifying mode for table viewer - move selected column left
+        if self.state.is_modifying && matches!(self.state.active_panel, NavigationPanel::MainContent) && !self.state.inspect_mode {
+            if let Some(selected_column) = &self.state.selected_column {
+                if let Some(current_index) = self.state.get_column_index_by_name(selected_column) {
+                    if current_index > 0 {
+                        let target_index = current_index - 1;
+                        if self.state.reorder_column(current_index, target_index) {
+                            self.fetch_table_data_preserve_column();
+                        }
+                    }
+                }
+            }
+            return;
+        }
+        
+        // Handle inspect mode navigation - treat left as page up
+        if self.state.inspect_mode {
+            // Page up: scroll up by multiple rows
+            for _ in 0..5 {
+                self.state.inspect_scroll_up();
+            }
+            return;
+        }
+
+        match self.state.active_panel {
+            NavigationPanel::MainContent => {
+                // Move selected column left
+                if self.state.table_data.is_some() {
+                    self.state.move_selected_left();
+                }
+            }
+            NavigationPanel::TableList => {
+                // From table list, go to database widget  
+                self.state.set_left_panel(NavigationPanel::DatabaseList);
+                self.state.collapse_database_dropdown(); // Close dropdown if open
+            }
+            NavigationPanel::DatabaseList => {
+                // From database list, underflow to table list
+                self.state.set_left_panel(NavigationPanel::TableList);
+                self.state.collapse_database_dropdown(); // Close dropdown if open
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_vim_right(&mut self) {
+        // Handle modifying mode for table viewer - move selected column right
+        if self.state.is_modifying && matches!(self.state.active_panel, NavigationPanel::MainContent) && !self.state.inspect_mode {
+            if let Some(selected_column) = &self.state.selected_column {
+                if let Some(current_index) = self.state.get_column_index_by_name(selected_column) {
+                    let virtual_order = self.state.get_virtual_column_order();
+                    if current_index < virtual_order.len() - 1 {
+                        let target_index = current_index + 1;
+                        if self.state.reorder_column(current_index, target_index) {
+                            self.fetch_table_data_preserve_column();
+                        }
+                    }
+                }
+            }
+            return;
+        }
+        
+        // Handle inspect mode navigation - treat right as page down
+        if self.state.inspect_mode {
+            // Page down: scroll down by multiple rows
+            let (max_rows, visible_rows) = self.calculate_inspect_scroll_bounds();
+            for _ in 0..5 {
+                self.state.inspect_scroll_down(max_rows, visible_rows);
+            }
+            return;
+        }
+
+        match self.state.active_panel {
+            NavigationPanel::DatabaseList => {
+                // From database widget, go to tables widget
+                self.state.set_left_panel(NavigationPanel::TableList);
+                self.state.collapse_database_dropdown(); // Close dropdown if open
+            }
+            NavigationPanel::MainContent => {
+                // Move selected column right
+                if let Some(ref data) = self.state.table_data {
+                    // Calculate visible columns to pass to method
+                    let visible_cols = 5; // Approximate - could be calculated from area
+                    self.state.move_selected_right(data.columns.len(), visible_cols);
+                }
+            }
+            NavigationPanel::TableList => {
+                // From table list, overflow to database list
+                self.state.set_left_panel(NavigationPanel::DatabaseList);
+            }
+            _ => {}
+        }
+    }
+
+    // Extreme movement handlers (capital letters)
+    fn handle_extreme_up(&mut self) {
+        if self.state.is_modifying {
+            if self.state.move_column_extreme_up() {
+                self.fetch_table_data_preserve_column();
+            }
+        } else {
+            self.state.navigate_extreme_up();
+        }
+    }
+
+    fn handle_extreme_down(&mut self) {
+        if self.state.is_modifying {
+            if self.state.move_column_extreme_down() {
+                self.fetch_table_data_preserve_column();
+            }
+        } else {
+            self.state.navigate_extreme_down();
+        }
+    }
+
+    fn handle_extreme_left(&mut self) {
+        if self.state.is_modifying {
+            if self.state.move_column_extreme_left() {
+                self.fetch_table_data_preserve_column();
+            }
+        } else {
+            self.state.navigate_extreme_left();
+        }
+    }
+
+    fn handle_extreme_right(&mut self) {
+        if self.state.is_modifying {
+            if self.state.move_column_extreme_right() {
+                self.fetch_table_data_preserve_column();
+            }
+        } else {
+            self.state.navigate_extreme_right();
+        }
+    }
+
+    fn handle_modify_mode(&mut self) {
+        if ((self.state.active_panel == NavigationPanel::MainContent && self.state.table_data.is_some()) ||
+           (self.state.inspect_mode && matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema)))
+           && !self.state.is_modifying {
+            self.state.start_modifying();
+            // No popup - will show in status bar instead
+        }
+    }
+
+    fn can_change_view(&self) -> bool {
+        (self.state.active_panel == NavigationPanel::MainContent || self.state.inspect_mode) &&
+            self.state.table_data.is_some() && !self.state.is_modifying
+    }
+
+    fn undo_view_change(&mut self) {
+        if !self.can_change_view() {
+            return;
+        }
+
+        if self.state.undo_view_change() {
+            let include_limit = self.state.column_filters.is_empty();
+            self.fetch_table_data_preserve_column_with_limit(include_limit);
+            self.state.show_info("Undid view change".to_string());
+        } else {
+            self.state.show_info("Nothing to undo".to_string());
+        }
+    }
+
+    fn redo_view_change(&mut self) {
+        if !self.can_change_view() {
+            return;
+        }
+
+        if self.state.redo_view_change() {
+            let include_limit = self.state.column_filters.is_empty();
+            self.fetch_table_data_preserve_column_with_limit(include_limit);
+            self.state.show_info("Redid view change".to_string());
+        } else {
+            self.state.show_info("Nothing to redo".to_string());
+        }
+    }
+
+    fn get_current_tables(&self) -> Vec<String> {
+        if let Some(current_db) = self.database_manager.get_current_database() {
+            if let Some(db_info) = self.database_manager.get_database_info(current_db) {
+                return db_info.tables.iter().map(|t| t.name.clone()).collect();
+            }
+        }
+        Vec::new()
+    }
+
+    fn get_current_table_infos(&self) -> Vec<&crate::db::TableInfo> {
+        if let Some(current_db) = self.database_manager.get_current_database() {
+            if let Some(db_info) = self.database_manager.get_database_info(current_db) {
+                return db_info.tables.iter().collect();
+            }
+        }
+        Vec::new()
+    }
+
+    fn sync_selected_db_index(&mut self) {
+        if let Some(current_db) = self.database_manager.get_current_database() {
+            let databases = self.database_manager.get_databases();
+            if let Some(index) = databases.iter().position(|db| db.name == current_db) {
+                self.selected_db_index = index;
+            }
+        }
+    }
+
+    fn sync_selected_table_index(&mut self) {
+        if let Some(current_table) = &self.state.selected_table {
+            let current_tables = self.get_current_table_infos();
+            if let Some(index) = current_tables.iter().position(|table| &table.name == current_table) {
+                // Keep the selected table visible in the sidebar; its ancestors are expanded by default
+                let table = current_tables[index];
+                let alias = crate::db::schema::split_schema_name(&table.schema).0.map(|alias| alias.into_owned());
+                let ancestors = [
+                    database_node_key(alias.as_deref()),
+                    schema_node_key(&table.schema),
+                    group_node_key(&table.schema, if table.is_view() { "Views" } else { "Tables" }),
+                ];
+                for key in ancestors {
+                    self.toggled_sidebar_nodes.remove(&key);
+                }
+                self.selected_table_index = index;
+                self.selected_sidebar_node = None;
+            }
+        }
+    }
+
+    fn get_current_database_info(&self) -> Option<&crate::db::DatabaseInfo> {
+        self.database_manager.get_current_database()
+            .and_then(|current_db| self.database_manager.get_database_info(current_db))
+    }
+
+    fn is_sidebar_node_expanded(&self, key: &str, expanded_by_default: bool) -> bool {
+        expanded_by_default != self.toggled_sidebar_nodes.contains(key)
+    }
+
+    /// Flattens the visible part of the catalog tree of the current database
+    fn sidebar_rows(&self) -> Vec<SidebarRow> {
+        let Some(db_info) = self.get_current_database_info() else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+
+        let mut catalogs = vec![(None, db_info.name.clone())];
+        catalogs.extend(db_info.attached.iter().map(|attached| {
+            let mode = if attached.read_only { " [attached, ro]" } else { " [attached]" };
+            (Some(attached.alias.clone()), format!("{}{mode}", attached.alias))
+        }));
+
+        for (alias, label) in catalogs {
+            let key = database_node_key(alias.as_deref());
+            let expanded = self.is_sidebar_node_expanded(&key, true);
+            rows.push(SidebarRow { key, depth: 0, node: SidebarNode::Database, label, expanded: Some(expanded) });
+            if !expanded {
+                continue;
+            }
+
+            let schemas = db_info.schemas.iter()
+                .filter(|schema| crate::db::schema::split_schema_name(schema).0.as_deref() == alias.as_deref());
+            for schema in schemas {
+                self.push_schema_rows(db_info, schema, &mut rows);
+            }
+        }
+        rows
+    }
+
+    fn push_schema_rows(&self, db_info: &crate::db::DatabaseInfo, schema: &str, rows: &mut Vec<SidebarRow>) {
+        let tables: Vec<(usize, &crate::db::TableInfo)> = db_info.tables.iter().enumerate()
+            .filter(|(_, table)| table.schema == schema)
+            .collect();
+        let objects: Vec<&CatalogObject> = db_info.objects.iter()
+            .filter(|object| object.schema == schema)
+            .collect();
+
+        let key = schema_node_key(schema);
+        let expanded = self.is_sidebar_node_expanded(&key, true);
+        rows.push(SidebarRow {
+            key,
+            depth: 1,
+            node: SidebarNode::Schema,
+            label: format!("{} ({})", crate::db::schema::split_schema_name(schema).1, tables.len()),
+            expanded: Some(expanded),
+        });
+        if !expanded {
+            return;
+        }
+
+        for (label, is_view) in [("Tables", false), ("Views", true)] {
+            let members: Vec<_> = tables.iter().filter(|(_, table)| table.is_view() == is_view).collect();
+            if members.is_empty() {
+                continue;
+            }
+            let key = group_node_key(schema, label);
+            let expanded = self.is_sidebar_node_expanded(&key, true);
+            rows.push(SidebarRow { key, depth: 2, node: SidebarNode::Group, label: format!("{label} ({})", members.len()), expanded: Some(expanded) });
+            if !expanded {
+                continue;
+            }
+            for (index, table) in members {
+                let key = format!("table:{}", table.name);
+                let expanded = self.is_sidebar_node_expanded(&key, false);
+                rows.push(SidebarRow {
+                    key: key.clone(),
+                    depth: 3,
+                    node: SidebarNode::Table(*index),
+                    label: match table.get_storage_summary() {
+                        Some(summary) => format!("{} {} · {summary}", table.icon(), table.table_name()),
+                        None => format!("{} {}", table.icon(), table.table_name()),
+                    },
+                    expanded: Some(expanded),
+                });
+                if expanded {
+                    let columns = db_info.columns.get(&table.name).map(Vec::as_slice).unwrap_or_default();
+                    rows.extend(detail_rows(&key, 4, columns));
+                }
+            }
+        }
+
+        for kind in CatalogObjectKind::ALL {
+            let members: Vec<_> = objects.iter().filter(|object| object.kind == kind).collect();
+            if members.is_empty() {
+                continue;
+            }
+            let key = group_node_key(schema, kind.label());
+            let expanded = self.is_sidebar_node_expanded(&key, false);
+            rows.push(SidebarRow { key, depth: 2, node: SidebarNode::Group, label: format!("{} ({})", kind.label(), members.len()), expanded: Some(expanded) });
+            if !expanded {
+                continue;
+            }
+            for object in members {
+                let key = object_node_key(object);
+                let expanded = self.is_sidebar_node_expanded(&key, false);
+                rows.push(SidebarRow {
+                    key: key.clone(),
+                    depth: 3,
+                    node: SidebarNode::Object,
+                    label: object.get_display_name(),
+                    expanded: (!object.children.is_empty()).then_some(expanded),
+                });
+                if expanded {
+                    rows.extend(detail_rows(&key, 4, &object.children));
+                }
+            }
+        }
+    }
+
+    /// Position of the sidebar cursor within `rows`
+    fn sidebar_cursor(&self, rows: &[SidebarRow]) -> usize {
+        rows.iter()
+            .position(|row| match (&row.node, &self.selected_sidebar_node) {
+                (SidebarNode::Table(index), None) => *index == self.selected_table_index,
+                (_, Some(selected)) => &row.key == selected,
+                _ => false,
+            })
+            .unwrap_or(0)
+    }
+
+    fn select_sidebar_row(&mut self, row: &SidebarRow) {
+        match row.node {
+            SidebarNode::Table(index) => {
+                self.selected_table_index = index;
+                self.selected_sidebar_node = None;
+            }
+            _ => self.selected_sidebar_node = Some(row.key.clone()),
+        }
+    }
+
+    fn move_sidebar_selection(&mut self, down: bool) {
+        let rows = self.sidebar_rows();
+        let cursor = self.sidebar_cursor(&rows);
+        let target = if down {
+            (cursor + 1).min(rows.len().saturating_sub(1))
+        } else {
+            cursor.saturating_sub(1)
+        };
+        if let Some(row) = rows.get(target) {
+            self.select_sidebar_row(row);
+        }
+    }
+
+    /// Expands or collapses the sidebar node under the cursor
+    fn toggle_selected_sidebar_node(&mut self) {
+        let rows = self.sidebar_rows();
+        let Some(row) = rows.get(self.sidebar_cursor(&rows)) else {
+            return;
+        };
+        if row.expanded.is_some() && !self.toggled_sidebar_nodes.remove(&row.key) {
+            self.toggled_sidebar_nodes.insert(row.key.clone());
+        }
+    }
+
+    /// The sequence, macro, type or function whose node is under the sidebar cursor
+    fn get_selected_catalog_object(&self) -> Option<&CatalogObject> {
+        let key = self.selected_sidebar_node.as_deref()?;
+        self.get_current_database_info()?
+            .objects.iter()
+            .find(|object| object_node_key(object) == key)
+    }
+
+    /// The schema whose node is under the sidebar cursor
+    fn get_selected_schema_node(&self) -> Option<String> {
+        self.selected_sidebar_node.as_deref()?.strip_prefix("schema:").map(str::to_string)
+    }
+
+    /// The table under the sidebar cursor, None on any other node
+    fn get_sidebar_table_name(&self) -> Option<String> {
+        if self.selected_sidebar_node.is_some() {
+            return None;
+        }
+        self.get_current_tables().get(self.selected_table_index).cloned()
+    }
+
+    fn refresh_current_database(&mut self) {
+        if let Some(current_db) = self.database_manager.get_current_database() {
+            let current_db = current_db.to_string();
+            if let Err(e) = self.database_manager.refresh_database(&current_db) {
+                self.state.show_error(format!("Failed to refresh database: {e}"));
+            }
+        }
+    }
+
+    fn render_table_widget(&self, f: &mut Frame, area: Rect, data: &crate::db::query::QueryResult, title: &str, border_style: Style) -> TableGridGeometry {
+        // Create mapping from data column indices to original virtual column indices
+        // Since hidden columns are filtered out in SQL, data.columns only contains visible columns
+        let visible_columns = self.state.get_visible_columns();
+        
+        // Calculate visible rows and columns
+        let available_height = area.height.saturating_sub(3) as usize; // Subtract borders and header
+        let start_row = self.state.scroll_y;
+        let end_row = (start_row + available_height).min(data.rows.len());
+        
+        // Calculate column constraints and visible columns
+        let mut constraints = Vec::new();
+        let mut visible_cols = Vec::new();
+        let available_width = area.width.saturating_sub(2) as usize; // Subtract borders
+        let start_col = self.state.scroll_x;
+        let mut used_width = 0;
+        
+        // Calculate individual column widths based on content
+        let min_col_width = 8;
+        let mut column_widths = Vec::new();
+        
+        for (data_col_idx, col_name) in data.columns.iter().enumerate() {
+            // Map data column index to original virtual column index
+            let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
+            
+            // Calculate header width using the final header text (including sort indicators)
+            let final_header_text = self.get_final_header_text(virtual_col_idx, col_name);
+            let header_width = final_header_text.chars().count();
+            let mut max_data_width = 0;
+            
+            // Calculate max data width for visible rows
+            if self.state.is_column_expanded(virtual_col_idx) {
+                // For expanded columns, check all visible rows for more accurate width
+                for row in data.rows[start_row..end_row].iter() {
+                    if let Some(cell) = row.get(data_col_idx) {
+                        max_data_width = max_data_width.max(cell.chars().count());
+                    }
+                }
+            } else {
+                // For normal columns, sample first 10 rows for performance
+                for row in data.rows.iter().take(10) {
+                    if let Some(cell) = row.get(data_col_idx) {
+                        max_data_width = max_data_width.max(cell.chars().count());
+                    }
+                }
+            }
+            
+            let col_width = if let Some(width) = self.state.get_column_width_override(col_name) {
+                // Width set by dragging the column border
+                width
+            } else if self.state.is_column_expanded(virtual_col_idx) {
+                // Expanded column: fit content up to max of 50 characters
+                header_width.max(max_data_width).max(min_col_width).min(50)
+            } else {
+                // Normal column: limit to 25 characters
+                header_width.max(max_data_width).max(min_col_width).min(25)
+            };
+            
+            column_widths.push(col_width);
+        }
+
+        // Determine visible columns
+        for i in start_col..data.columns.len() {
+            let col_width = column_widths.get(i).unwrap_or(&min_col_width);
+            if used_width + col_width <= available_width {
+                constraints.push(Constraint::Length(*col_width as u16));
+                visible_cols.push(i);
+                used_width += col_width;
+            } else {
+                break;
+            }
+        }
+
+        // Ensure we show at least one column
+        if visible_cols.is_empty() && start_col < data.columns.len() {
+            visible_cols.push(start_col);
+            constraints.push(Constraint::Length(min_col_width as u16));
+        }
+
+        // Create header with styling for selected column and sort indicators
+        let header_cells: Vec<Cell> = visible_cols.iter().map(|&data_col_idx| {
+            let cell_content = &data.columns[data_col_idx];
+            let col_width = column_widths.get(data_col_idx).unwrap_or(&min_col_width);
+            
+            // Map data column index to virtual column index
+            let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
+            
+            // Get the complete header text with all indicators
+            let header_with_sort = self.get_final_header_text(virtual_col_idx, cell_content);
+            
+            let text = if self.state.is_column_expanded(virtual_col_idx) {
+                // For expanded columns, show full header without truncation
+                header_with_sort
+            } else {
+                truncate_text(&header_with_sort, *col_width)
+            };
+            
+            if Some(virtual_col_idx) == self.state.get_selected_column_index() {
+                Cell::from(text).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            } else {
+                Cell::from(text)
+            }
+        }).collect();
+
+        // Create data rows with text wrapping support for expanded columns
+        let mut rows: Vec<Row> = Vec::new();
+        let mut row_lines: Vec<usize> = Vec::new();
+        
+        // Diff results color rows by status and highlight changed cells
+        let is_diff_result = self.state.selected_table.as_ref()
+            .is_some_and(|table| self.state.diff_results.contains_key(table));
+        
+        for (display_idx, row) in data.rows[start_row..end_row].iter().enumerate() {
+            let actual_row_idx = start_row + display_idx;
+            let is_selected_row = actual_row_idx == self.state.selected_row;
+            
+            // First, prepare wrapped content for all cells in this row
+            let mut cell_lines: Vec<Vec<String>> = Vec::new();
+            let mut max_lines = 1;
+            
+            for &data_col_idx in &visible_cols {
+                let empty_string = String::new();
+                let cell_content = row.get(data_col_idx).unwrap_or(&empty_string);
+                let col_width = column_widths.get(data_col_idx).unwrap_or(&min_col_width);
+                
+                // Map data column index to virtual column index
+                let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
+                
+                let lines = if self.state.is_column_expanded(virtual_col_idx) {
+                    wrap_text(cell_content, *col_width)
+                } else {
+                    vec![truncate_text(cell_content, *col_width)]
+                };
+                
+                max_lines = max_lines.max(lines.len());
+                cell_lines.push(lines);
+            }
+            
+            // Create multiple rows if any cell has wrapped content
+            for line_idx in 0..max_lines {
+                let cells: Vec<Cell> = visible_cols.iter().enumerate().map(|(visible_idx, &data_col_idx)| {
+                    let line_text = cell_lines.get(visible_idx)
+                        .and_then(|lines| lines.get(line_idx))
+                        .unwrap_or(&String::new())
+                        .clone();
+                    
+                    // Map data column index to virtual column index
+                    let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
+                    
+                    // Check if this is the current cell (intersection of selected row and column)
+                    let is_current_cell = is_selected_row && Some(virtual_col_idx) == self.state.get_selected_column_index();
+                    
+                    let diff_style = if is_diff_result {
+                        diff_cell_style(data, row, &data.columns[data_col_idx])
+                    } else if self.state.is_cell_modified(actual_row_idx, &data.columns[data_col_idx]) {
+                        // Staged but uncommitted changes
+                        Style::default().fg(Color::Magenta).add_modifier(Modifier::ITALIC)
+                    } else {
+                        Style::default()
+                    };
+                    
+                    if is_current_cell {
+                        // Highlight current cell with light gray background and inverted text for readability
+                        Cell::from(line_text).style(Style::default().bg(Color::Gray).fg(Color::Black).add_modifier(Modifier::BOLD))
+                    } else if is_selected_row {
+                        // Bold selected row
+                        Cell::from(line_text).style(Style::default().add_modifier(Modifier::BOLD).patch(diff_style))
+                    } else if self.state.is_row_visually_selected(actual_row_idx) {
+                        // Rows in the visual selection
+                        Cell::from(line_text).style(Style::default().bg(Color::DarkGray).patch(diff_style))
+                    } else if Some(virtual_col_idx) == self.state.get_selected_column_index() {
+                        // Subtle highlight for selected column
+                        Cell::from(line_text).style(Style::default().fg(Color::Gray).patch(diff_style))
+                    } else {
+                        Cell::from(line_text).style(diff_style)
+                    }
+                }).collect();
+                
+                rows.push(Row::new(cells));
+                row_lines.push(actual_row_idx);
+            }
+        }
+
+        // Record where columns and rows land on screen so mouse clicks can be mapped back.
+        // This mirrors how the Table widget lays out its columns inside the block.
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let column_rects = Layout::horizontal(constraints.clone())
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(inner);
+        let geometry = TableGridGeometry {
+            header_y: inner.y,
+            columns: visible_cols.iter().zip(column_rects.iter())
+                .map(|(&data_col_idx, rect)| GridColumn {
+                    name: data.columns[data_col_idx].clone(),
+                    x: rect.x,
+                    width: rect.width,
+                })
+                .collect(),
+            row_lines: row_lines.into_iter()
+                .enumerate()
+                .map(|(line, row_idx)| (inner.y + 1 + line as u16, row_idx))
+                .take_while(|(y, _)| *y < inner.y + inner.height)
+                .collect(),
+        };
+
+        let table = Table::new(rows, constraints)
+            .header(Row::new(header_cells).height(1))
+            .block(Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">>")
+            .column_spacing(1);
+
+        f.render_widget(table, area);
+        geometry
+    }
+
+    // Calculate how many complete data rows actually fit in the available height
+    // accounting for word wrapping in expanded columns
+    fn calculate_visible_data_rows(&self, area: Rect, data: &crate::db::query::QueryResult) -> usize {
+        let available_height = area.height.saturating_sub(3) as usize; // Subtract borders and header
+        let start_row = self.state.scroll_y;
+        
+        if data.rows.is_empty() {
+            return 0;
+        }
+
+        // Calculate column widths (simplified version of what's in render_table_widget)
+        let min_col_width = 8;
+        let mut column_widths = Vec::new();
+        for (i, col_name) in data.columns.iter().enumerate() {
+            let final_header_text = self.get_final_header_text(i, col_name);
+            let header_width = final_header_text.chars().count();
+            
+            let col_width = if let Some(width) = self.state.get_column_width_override(col_name) {
+                width
+            } else if self.state.is_column_expanded(i) {
+                header_width.max(min_col_width).min(50)
+            } else {
+                header_width.max(min_col_width).min(25)
+            };
+            column_widths.push(col_width);
+        }
+
+        // Calculate visible columns (simplified)
+        let available_width = area.width.saturating_sub(2) as usize;
+        let start_col = self.state.scroll_x;
+        let mut visible_cols = Vec::new();
+        let mut used_width = 0;
+        
+        for i in start_col..data.columns.len() {
+            let col_width = column_widths.get(i).unwrap_or(&min_col_width);
+            if used_width + col_width <= available_width {
+                visible_cols.push(i);
+                used_width += col_width;
+            } else {
+                break;
+            }
+        }
+
+        // Ensure we show at least one column
+        if visible_cols.is_empty() && start_col < data.columns.len() {
+            visible_cols.push(start_col);
+        }
+
+        // Count how many complete data rows fit
+        let mut used_height = 0;
+        let mut visible_data_rows = 0;
+        
+        for row_idx in start_row..data.rows.len() {
+            if row_idx >= data.rows.len() {
+                break;
+            }
+            
+            let row = &data.rows[row_idx];
+            
+            // Calculate max lines for this row (same logic as render_table_widget)
+            let mut max_lines = 1;
+            for &col_idx in &visible_cols {
+                let empty_string = String::new();
+                let cell_content = row.get(col_idx).unwrap_or(&empty_string);
+                let col_width = column_widths.get(col_idx).unwrap_or(&min_col_width);
+                
+                let lines = if self.state.is_column_expanded(col_idx) {
+                    wrap_text(cell_content, *col_width)
+                } else {
+                    vec![truncate_text(cell_content, *col_width)]
+                };
+                
+                max_lines = max_lines.max(lines.len());
+            }
+            
+            // Check if this row fits in remaining height
+            if used_height + max_lines <= available_height {
+                used_height += max_lines;
+                visible_data_rows += 1;
+            } else {
+                break;
+            }
+        }
+        
+        visible_data_rows.max(1) // Always return at least 1
+    }
+
+    // Simplified version that only needs cached height (for navigation)
+    fn calculate_visible_data_rows_from_height(&self, area_height: u16, data: &crate::db::query::QueryResult) -> usize {
+        let available_height = area_height.saturating_sub(3) as usize; // Subtract borders and header
+        
+        if data.rows.is_empty() {
+            return 0;
+        }
+        
+        // If no expanded columns, each row is height 1
+        if self.state.expanded_columns.is_empty() {
+            return available_height.max(1);
+        }
+        
+        // Calculate column widths similar to render_table_widget logic
+        let min_col_width = 8;
+        let mut column_widths = Vec::new();
+        for (i, col_name) in data.columns.iter().enumerate() {
+            let final_header_text = self.get_final_header_text(i, col_name);
+            let header_width = final_header_text.chars().count();
+            
+            let col_width = if let Some(width) = self.state.get_column_width_override(col_name) {
+                width
+            } else if self.state.is_column_expanded(i) {
+                header_width.max(min_col_width).min(50)
+            } else {
+                header_width.max(min_col_width).min(25)
+            };
+            column_widths.push(col_width);
+        }
+
+        // Calculate visible columns (simplified, assume all columns fit for navigation)
+        let available_width = 100; // Reasonable assumption for navigation calculations
+        let start_col = self.state.scroll_x;
+        let mut visible_cols = Vec::new();
+        let mut used_width = 0;
+        
+        for i in start_col..data.columns.len() {
+            let col_width = column_widths.get(i).unwrap_or(&min_col_width);
+            if used_width + col_width <= available_width {
+                visible_cols.push(i);
+                used_width += col_width;
+            } else {
+                break;
+            }
+        }
+        
+        // Ensure we show at least one column
+        if visible_cols.is_empty() && start_col < data.columns.len() {
+            visible_cols.push(start_col);
+        }
+
+        // Count how many complete data rows fit (same logic as calculate_visible_data_rows)
+        let mut used_height = 0;
+        let mut visible_data_rows = 0;
+        let start_row = self.state.scroll_y;
+        
+        for row_idx in start_row..data.rows.len() {
+            if row_idx >= data.rows.len() {
+                break;
+            }
+            
+            let row = &data.rows[row_idx];
+            
+            // Calculate max lines for this row
+            let mut max_lines = 1;
+            for &col_idx in &visible_cols {
+                let empty_string = String::new();
+                let cell_content = row.get(col_idx).unwrap_or(&empty_string);
+                let col_width = column_widths.get(col_idx).unwrap_or(&min_col_width);
+                
+                let lines = if self.state.is_column_expanded(col_idx) {
+                    wrap_text(cell_content, *col_width)
+                } else {
+                    vec![truncate_text(cell_content, *col_width)]
+                };
+                
+                max_lines = max_lines.max(lines.len());
+            }
+            
+            // Check if this row fits in remaining height
+            if used_height + max_lines <= available_height {
+                used_height += max_lines;
+                visible_data_rows += 1;
+            } else {
+                break;
+            }
+        }
+        
+        visible_data_rows.max(1) // Always return at least 1
+    }
+
+    // Helper method to generate the complete header text for a column including all indicators
+    // This ensures width calculations match the actual rendered text
+    fn get_final_header_text(&self, _column_index: usize, column_name: &str) -> String {
+        let mut header_text = column_name.to_string();
+        
+        // Add sort indicator if this column is in the sort chain
+        if let Some((position, sort_spec)) = self.state.sort_columns
+            .iter()
+            .enumerate()
+            .find(|(_, spec)| spec.column_name == column_name) {
+            
+            let sort_indicator = match sort_spec.direction {
+                crate::app::state::SortDirection::Ascending => "↑",
+                crate::app::state::SortDirection::Descending => "↓",
+            };
+            
+            // Show order number for multi-column sorts (1-indexed for user readability)
+            if self.state.sort_columns.len() > 1 {
+                header_text = format!("{} {}^{}", header_text, sort_indicator, position + 1);
+            } else {
+                header_text = format!("{header_text} {sort_indicator}");
+            }
+        }
+        
+        // Add filter indicator if this column is filtered
+        if self.state.is_column_filtered(column_name) {
+            header_text = format!("{header_text} *");
+        }
+        
+        header_text
+    }
+
+    fn fetch_table_data(&mut self) {
+        if let (Some(_db), Some(table)) = (self.state.selected_database.clone(), self.state.selected_table.clone()) {
+            if let Some(connection) = self.database_manager.get_current_connection() {
+                // First get column names to enable sorting and virtual ordering
+                let column_names = match self.get_table_column_names(connection, &table) {
+                    Ok(names) => names,
+                    Err(e) => {
+                        self.state.show_error(format!("Failed to get column names: {e}"));
+                        return;
+                    }
+                };
+
+                // Initialize column order if needed
+                self.state.initialize_column_order(column_names.clone());
+                
+                // Get visible columns in virtual order
+                let visible_column_names = self.state.get_visible_column_names();
+                
+                // Use visible columns directly (they are already in virtual order)
+                let ordered_columns = visible_column_names;
+
+                // Build base SQL query with virtual column ordering
+                let columns_sql = ordered_columns.iter()
+                    .map(|column| crate::db::schema::quote_identifier(column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let source = self.state.get_source_sql(&table);
+                // Editable tables also load each row's key so it can be addressed later
+                let row_key = self.editable_row_key(connection, &table);
+                let key_sql = row_key.as_ref().map(RowKey::select_sql).unwrap_or_default();
+                let mut sql = format!("SELECT {columns_sql}{key_sql} FROM {source}");
+                
+                // Read from a table sample instead of storage order when sampling is enabled
+                if let Some(sample_clause) = self.state.get_sample_sql_clause() {
+                    sql.push(' ');
+                    sql.push_str(&sample_clause);
+                }
+                
+                // Add sorting if active (use original column names for sorting)
+                if let Some(sort_clause) = self.state.get_sort_sql_clause(&column_names) {
+                    sql.push(' ');
+                    sql.push_str(&sort_clause);
+                }
+                
+                // Add limit
+                sql.push_str(" LIMIT 1000");
+
+                match self.execute_query_direct(connection, &sql) {
+                    Ok(mut data) => {
+                        self.state.row_key_values = row_key.as_ref()
+                            .map(|key| key.split_from(&mut data))
+                            .unwrap_or_default();
+                        self.state.row_key = row_key;
+                        self.state.set_table_data(data);
+                    }
+                    Err(e) => {
+                        self.state.show_error(format!("Failed to load table data: {e}"));
+                    }
+                }
+            } else {
+                self.state.show_error("No database connection available".to_string());
+            }
+        } else {
+            self.state.show_error("No table selected for data loading".to_string());
+        }
+    }
+
+    fn fetch_table_data_preserve_column(&mut self) {
+        self.fetch_table_data_preserve_column_with_limit(true);
+    }
+
+    fn fetch_table_data_preserve_column_with_limit(&mut self, include_limit: bool) {
+        if let (Some(_db), Some(table)) = (self.state.selected_database.clone(), self.state.selected_table.clone()) {
+            if let Some(connection) = self.database_manager.get_current_connection() {
+                // First get column names to enable sorting, filtering, and virtual ordering
+                let column_names = match self.get_table_column_names(connection, &table) {
+                    Ok(names) => names,
+                    Err(e) => {
+                        self.state.show_error(format!("Failed to get column names: {e}"));
+                        return;
+                    }
+                };
+
+                // Initialize column order if needed
+                self.state.initialize_column_order(column_names.clone());
+                
+                // Get visible columns in virtual order
+                let visible_column_names = self.state.get_visible_column_names();
+                
+                // Use visible columns directly (they are already in virtual order)
+                let ordered_columns = visible_column_names;
+
+                // Build base SQL query with virtual column ordering
+                let columns_sql = ordered_columns.iter()
+                    .map(|column| crate::db::schema::quote_identifier(column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let source = self.state.get_source_sql(&table);
+                // Editable tables also load each row's key so it can be addressed later
+                let row_key = self.editable_row_key(connection, &table);
+                let key_sql = row_key.as_ref().map(RowKey::select_sql).unwrap_or_default();
+                let mut sql = format!("SELECT {columns_sql}{key_sql} FROM {source}");
+                
+                // Read from a table sample instead of storage order when sampling is enabled
+                if let Some(sample_clause) = self.state.get_sample_sql_clause() {
+                    sql.push(' ');
+                    sql.push_str(&sample_clause);
+                }
+                
+                // Add filtering if active (use original column names for filtering)
+                if let Some(filter_clause) = self.state.get_filter_sql_clause(&column_names) {
+                    sql.push(' ');
+                    sql.push_str(&filter_clause);
+                }
+                
+                // Add sorting if active (use original column names for sorting)
+                if let Some(sort_clause) = self.state.get_sort_sql_clause(&column_names) {
+                    sql.push(' ');
+                    sql.push_str(&sort_clause);
+                }
+                
+                // Add limit conditionally
+                if include_limit {
+                    sql.push_str(" LIMIT 1000");
+                }
+
+                match self.execute_query_direct(connection, &sql) {
+                    Ok(mut data) => {
+                        self.state.row_key_values = row_key.as_ref()
+                            .map(|key| key.split_from(&mut data))
+                            .unwrap_or_default();
+                        self.state.row_key = row_key;
+                        self.state.update_table_data_preserve_column(data);
+                    }
+                    Err(e) => {
+                        self.state.show_error(format!("Failed to load table data: {e}"));
+                    }
+                }
+            } else {
+                self.state.show_error("No database connection available".to_string());
+            }
+        } else {
+            self.state.show_error("No table selected for data loading".to_string());
+        }
+    }
+
+    /// Row key for a base table, or None for views and query results which can't be edited
+    fn editable_row_key(&self, connection: &duckdb::Connection, table_name: &str) -> Option<RowKey> {
+        let is_base_table = self.get_current_table_infos().iter()
+            .any(|info| info.name == table_name && info.table_type != "VIEW");
+        if !is_base_table || self.state.is_query_result(table_name) {
+            return None;
+        }
+        RowKey::for_table(connection, table_name).ok()
+    }
+
+    fn get_table_column_names(&self, connection: &duckdb::Connection, table_name: &str) -> anyhow::Result<Vec<String>> {
+        let source = self.state.get_source_sql(table_name);
+        let sql = format!("SELECT * FROM {source} LIMIT 0");
+        let mut stmt = connection.prepare(&sql)?;
+        let _rows = stmt.query([])?;
+        
+        let column_count = stmt.column_count();
+        let mut column_names = Vec::new();
+        
+        for i in 0..column_count {
+            let column_name = stmt.column_name(i)
+                .unwrap_or(&format!("column_{i}"))
+                .to_string();
+            column_names.push(column_name);
+        }
+        
+        Ok(column_names)
+    }
+
+    fn get_table_schema(&self, connection: &duckdb::Connection, table_name: &str) -> anyhow::Result<crate::db::query::QueryResult> {
+        let sql = if self.state.is_query_result(table_name) {
+            format!("DESCRIBE SELECT * FROM {}", self.state.get_source_sql(table_name))
+        } else {
+            format!("DESCRIBE {}", crate::db::schema::quote_table_name(table_name))
+        };
+        self.execute_query_direct(connection, &sql)
+    }
+
+    fn get_table_statistics(&self, connection: &duckdb::Connection, table_name: &str) -> anyhow::Result<crate::db::query::QueryResult> {
+        // Summarize the sample when sampling is enabled so huge tables stay fast, seeded so
+        // the statistics don't change between loads
+        let sql = match self.state.get_sample_spec().map(|sample| sample.get_repeatable_sql_clause()) {
+            Some(sample_clause) => format!("SUMMARIZE SELECT * FROM {} {sample_clause}", self.state.get_source_sql(table_name)),
+            None => format!("SUMMARIZE SELECT * FROM {}", self.state.get_source_sql(table_name)),
+        };
+        self.execute_query_direct(connection, &sql)
+    }
+
+    fn execute_query_direct(&self, connection: &duckdb::Connection, sql: &str) -> anyhow::Result<crate::db::query::QueryResult> {
+        let start_time = std::time::Instant::now();
+        
+        // Prepare statement and execute query
+        let mut stmt = connection.prepare(sql)?;
+        let mut rows = stmt.query([])?;
+        
+        // Get column count from the rows result
+        let column_count = rows.as_ref().unwrap().column_count();
+        
+        // Get column names from the statement
+        let mut columns = Vec::new();
+        for i in 0..column_count {
+            let column_name = rows.as_ref().unwrap().column_name(i)
+                .unwrap_or(&format!("column_{i}"))
+                .to_string();
+            columns.push(column_name);
+        }
+        
+        // Collect all rows
+        let mut result_rows = Vec::new();
+        let mut null_cells = std::collections::HashSet::new();
+        while let Some(row) = rows.next()? {
+            let mut row_data = Vec::new();
+            for i in 0..column_count {
+                // The text below spells NULL as "Null"; the real NULLs are recorded separately
+                if matches!(row.get_ref(i), Ok(duckdb::types::ValueRef::Null)) {
+                    null_cells.insert((result_rows.len(), i));
+                }
+                // Convert each column value to string with safer error handling
+                // Try f64 first to handle NaN values, then other types
+                let value = match row.get::<_, f64>(i) {
+                    Ok(v) => {
+                        if v.is_nan() {
+                            "NaN".to_string()
+                        } else if v.is_infinite() {
+                            if v.is_sign_positive() { "Infinity".to_string() } else { "-Infinity".to_string() }
+                        } else {
+                            v.to_string()
+                        }
+                    },
+                    Err(_) => match row.get::<_, String>(i) {
+                        Ok(v) => v,
+                        Err(_) => match row.get::<_, i64>(i) {
+                            Ok(v) => v.to_string(),
+                            Err(_) => match row.get::<_, bool>(i) {
+                                Ok(v) => v.to_string(),
+                                Err(_) => {
+                                    // Try to get as raw value or default to NULL
+                                    match row.get_ref(i) {
+                                        Ok(value_ref) => format!("{value_ref:?}"),
+                                        Err(_) => "NULL".to_string(),
+                                    }
+                                }
+                            }
+                        }
+                    }
+                };
+                row_data.push(value);
+            }
+            result_rows.push(row_data);
+        }
+        
+        let execution_time = start_time.elapsed();
+        
+        Ok(crate::db::query::QueryResult {
+            columns,
+            row_count: result_rows.len(),
+            rows: result_rows,
+            execution_time_ms: execution_time.as_millis() as u64,
+            null_cells,
+        })
+    }
+
+    fn handle_enter(&mut self) {
+        // Handle modifying mode confirmation first
+        if self.state.is_modifying {
+            self.state.confirm_modifying();
+            // No popup - will show in status bar instead
+            return;
+        }
+        
+        match self.state.active_panel {
+            NavigationPanel::DatabaseList => {
+                if self.state.database_dropdown_expanded {
+                    // Select database from dropdown
+                    let db_name = {
+                        let databases = self.database_manager.get_databases();
+                        databases
+                            .get(self.state.dropdown_selected_index)
+                            .map(|db| db.name.clone())
+                    };
+
+                    if let Some(db_name) = db_name {
+                        let mut workflows = DatabaseWorkflows::new(
+                            &mut self.database_manager,
+                            &mut self.action_logger,
+                            &mut self.state,
+                        );
+                        let _ = workflows.select_database(db_name);
+                        self.sync_selected_db_index(); // Sync the index after selection
+                        self.selected_table_index = 0; // Reset table selection
+                        self.selected_sidebar_node = None;
+                    }
+                    
+                    // Close dropdown after selection
+                    self.state.collapse_database_dropdown();
+                } else {
+                    // Expand dropdown if not already expanded
+                    let databases = self.database_manager.get_databases();
+                    self.state.expand_database_dropdown(databases.len());
+                    self.state.set_dropdown_to_current_database(self.selected_db_index);
+                }
+            }
+            NavigationPanel::TableList => {
+                if self.selected_sidebar_node.is_some() {
+                    self.toggle_selected_sidebar_node();
+                } else if let Some(table_name) = self.get_sidebar_table_name() {
+                    let mut workflows = DatabaseWorkflows::new(
+                        &mut self.database_manager,
+                        &mut self.action_logger,
+                        &mut self.state,
+                    );
+                    let _ = workflows.select_table(table_name);
+                    self.sync_selected_table_index();
+                    self.fetch_table_data();
+                    // Automatically activate table viewer after selecting table
+                    self.state.set_active_panel(NavigationPanel::MainContent);
+                }
+            }
+            NavigationPanel::MainContent => {
+                // Toggle column expansion when viewing table data (but not in modifying mode)
+                if self.state.table_data.is_some() && !self.state.is_modifying {
+                    self.state.toggle_column_expansion();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn create_designed_table(&mut self) {
+        let table_name = self.state.new_table_name.trim().to_string();
+        let statement = match crate::db::design::create_table_sql(&table_name, &self.state.table_design) {
+            Ok(statement) => statement,
+            Err(e) => {
+                self.state.show_error(e.to_string());
+                return;
+            }
+        };
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.create_table(table_name, statement) {
+            Ok(()) => {
+                self.state.complete_table_creation(true);
+                self.refresh_current_database();
+                self.sync_selected_table_index();
+                self.fetch_table_data();
+            }
+            // Stay in the preview so the design can be fixed
+            Err(e) => self.state.show_error(format!("Create table failed: {e}")),
+        }
+    }
+
+    fn start_table_creation(&mut self) {
+        if self.database_manager.get_current_database().is_some() {
+            self.state.start_table_creation();
+        } else {
+            self.state.show_error("Please connect to a database first".to_string());
+        }
+    }
+
+    pub fn update_notifications(&mut self) {
+        self.state.remove_expired_notifications();
+        self.state.update_flash_timer();
+        self.check_search_debounce();
+        if self.state.is_inspect_data_outdated() {
+            self.load_inspect_data();
+        }
+    }
+
+    fn show_help(&mut self) {
+        self.state.show_info("Keys: Tab=Navigate | hjkl=Select | t/[/]/w=Tabs | |=Split | :=Query | C=Compare | e=Export | E=Edit Cell | I/Y=Insert/Duplicate Row | V=Select Rows | U/X=Bulk Update/Delete | T=Transaction | a=Alter Column (Inspect) | m=Reorder Mode | b=Layouts | u/^R=Undo/Redo | S=Sample | Enter=Confirm | i=Import/Inspect | o=Open | n=New | s=Save | r/p/P/X=Rename/Duplicate/Copy Data/Truncate (Tables) | Space=Expand (Catalog Tree) | #=Comment (Tables) | M=New Schema | O/x=Attach/Detach Database | N=Indexes | B=Storage | Q=Definition (DDL) | W=Schema Diff | R=ER Diagram | g/G=Follow FK/References | Backspace=Back | d=Delete | ?=Help | q=Quit".to_string());
+    }
+
+    fn open_file_browser(&mut self) {
+        match FileBrowser::new() {
+            Ok(browser) => {
+                self.file_browser = Some(browser);
+                self.show_file_browser = true;
+                self.state.show_info("File browser opened".to_string());
+            }
+            Err(e) => {
+                self.state.show_error(format!("Error opening file browser: {e}"));
+            }
+        }
+    }
+
+    fn open_attach_browser(&mut self) {
+        if self.database_manager.get_current_database().is_none() {
+            self.state.show_error("Please connect to a database first".to_string());
+            return;
+        }
+        self.open_file_browser();
+        self.attaching_database = self.show_file_browser;
+        if self.attaching_database {
+            self.state.show_info("Select a database file to attach".to_string());
+        }
+    }
+
+    fn attach_database(&mut self) {
+        let Some(attach) = self.state.pending_attach.clone() else {
+            return;
+        };
+        let alias = attach.alias.trim().to_string();
+        if alias.is_empty() {
+            self.state.show_error("Enter an alias for the attached database".to_string());
+            return;
+        }
+        self.state.cancel_attach();
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        if let Err(e) = workflows.attach_database(attach.path, alias, attach.read_only) {
+            self.state.show_error(format!("Attach failed: {e}"));
+        }
+    }
+
+    /// Alias of the attached database whose database or schema node is under the sidebar cursor
+    fn get_selected_attached_alias(&self) -> Option<String> {
+        let key = self.selected_sidebar_node.as_deref()?;
+        if let Some(alias) = key.strip_prefix("db:").filter(|alias| !alias.is_empty()) {
+            return Some(alias.to_string());
+        }
+        let schema = key.strip_prefix("schema:")?;
+        crate::db::schema::split_schema_name(schema).0.map(|alias| alias.into_owned())
+    }
+
+    fn detach_selected_database(&mut self) {
+        let Some(alias) = self.get_selected_attached_alias() else {
+            return;
+        };
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.detach_database(alias) {
+            Ok(()) => {
+                self.selected_table_index = 0;
+                self.selected_sidebar_node = None;
+                self.sync_selected_table_index();
+            }
+            Err(e) => self.state.show_error(format!("Detach failed: {e}")),
+        }
+    }
+
+    fn create_database_with_name(&mut self) {
+        let db_name = self.state.new_database_name.clone();
+        
+        // Create in-memory database with custom name
+        if let Err(e) = self.database_manager.add_database(db_name.clone(), ":memory:".to_string()) {
+            self.state.show_error(format!("Failed to create database: {e}"));
+        } else {
+            let mut workflows = DatabaseWorkflows::new(
+                &mut self.database_manager,
+                &mut self.action_logger,
+                &mut self.state,
+            );
+            let _ = workflows.select_database(db_name.clone());
+            self.sync_selected_db_index();
+            self.selected_table_index = 0;
+            self.selected_sidebar_node = None;
+            self.state.show_success(format!("Created database '{db_name}'"));
+        }
+    }
+
+    fn start_delete_confirmation(&mut self) {
+        match self.state.active_panel {
+            NavigationPanel::DatabaseList => {
+                let databases = self.database_manager.get_databases();
+                // Use dropdown index if dropdown is expanded, otherwise use selected index
+                let index = if self.state.database_dropdown_expanded {
+                    self.state.dropdown_selected_index
+                } else {
+                    self.selected_db_index
+                };
+                if let Some(db) = databases.get(index) {
+                    self.state.start_database_delete_confirmation(db.name.clone());
+                }
+            }
+            NavigationPanel::TableList => {
+                if let Some(schema) = self.get_selected_schema_node() {
+                    self.start_schema_drop_confirmation(schema);
+                } else if let Some(table) = self.get_sidebar_table_name() {
+                    self.state.start_table_delete_confirmation(table);
+                }
+            }
+            NavigationPanel::MainContent if !self.state.is_modifying => {
+                if let Some(table) = self.check_editable() {
+                    let rows = self.state.get_selected_row_indices();
+                    if !rows.is_empty() {
+                        self.state.start_rows_delete_confirmation(table, rows);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn immediate_delete(&mut self) {
+        match self.state.active_panel {
+            NavigationPanel::DatabaseList => {
+                let db_name = {
+                    let databases = self.database_manager.get_databases();
+                    // Use dropdown index if dropdown is expanded, otherwise use selected index
+                    let index = if self.state.database_dropdown_expanded {
+                        self.state.dropdown_selected_index
+                    } else {
+                        self.selected_db_index
+                    };
+                    databases.get(index).map(|db| db.name.clone())
+                };
+                if let Some(name) = db_name {
+                    self.delete_database(&name);
+                }
+            }
+            NavigationPanel::TableList => {
+                if let Some(name) = self.get_sidebar_table_name() {
+                    self.delete_table(&name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The table or view selected in the sidebar, and whether it is a view
+    fn get_sidebar_selected_table(&self) -> Option<(String, bool)> {
+        if self.selected_sidebar_node.is_some() {
+            return None;
+        }
+        self.get_current_table_infos()
+            .get(self.selected_table_index)
+            .map(|info| (info.name.clone(), info.table_type == "VIEW"))
+    }
+
+    fn start_table_operation(&mut self, kind: crate::app::state::TableOperationKind) {
+        match self.get_sidebar_selected_table() {
+            Some((table, is_view)) => {
+                // Comments are edited starting from the current one
+                let comment = (kind == crate::app::state::TableOperationKind::Comment)
+                    .then(|| self.get_table_comment(&table).unwrap_or_default());
+                self.state.start_table_operation(table, is_view, kind);
+                if let Some(comment) = comment {
+                    self.state.table_operation_input = comment;
+                }
+            }
+            None => self.state.show_error("No table selected".to_string()),
+        }
+    }
+
+    fn get_table_comment(&self, table_name: &str) -> Option<String> {
+        self.get_current_table_infos().into_iter()
+            .find(|info| info.name == table_name)
+            .and_then(|info| info.comment.clone())
+    }
+
+    fn open_er_diagram(&mut self) {
+        let tables: Vec<crate::db::TableInfo> = self.get_current_table_infos().into_iter().cloned().collect();
+        let Some(connection) = self.database_manager.get_current_connection() else {
+            self.state.show_error("Please connect to a database first".to_string());
+            return;
+        };
+        match ErDiagram::load(connection, &tables) {
+            Ok(diagram) if diagram.is_empty() => {
+                self.state.show_info("This database has no tables to diagram".to_string());
+            }
+            Ok(diagram) => {
+                self.er_diagram = Some(diagram);
+                self.state.exit_inspect_mode();
+                self.state.set_active_panel(NavigationPanel::MainContent);
+            }
+            Err(e) => self.state.show_error(format!("Failed to build ER diagram: {e}")),
+        }
+    }
+
+    fn handle_er_diagram_key(&mut self, code: KeyCode) {
+        let Some(diagram) = &mut self.er_diagram else {
+            return;
+        };
+        match code {
+            KeyCode::Esc | KeyCode::Char('R') => self.er_diagram = None,
+            KeyCode::Char('h') | KeyCode::Left => diagram.move_selection(-1, 0),
+            KeyCode::Char('l') | KeyCode::Right => diagram.move_selection(1, 0),
+            KeyCode::Char('k') | KeyCode::Up => diagram.move_selection(0, -1),
+            KeyCode::Char('j') | KeyCode::Down => diagram.move_selection(0, 1),
+            KeyCode::Enter => {
+                let Some(table_name) = diagram.selected_table().map(str::to_string) else {
+                    return;
+                };
+                self.er_diagram = None;
+                let mut workflows = DatabaseWorkflows::new(
+                    &mut self.database_manager,
+                    &mut self.action_logger,
+                    &mut self.state,
+                );
+                let _ = workflows.select_table(table_name);
+                self.sync_selected_table_index();
+                self.fetch_table_data();
+            }
+            _ => {}
+        }
+    }
+
+    /// Values of the selected row for the given columns, in order
+    fn get_selected_row_key(&mut self, columns: &[String]) -> Option<Vec<Option<String>>> {
+        let row = self.state.get_selected_row_values()?;
+        let values: Option<Vec<Option<String>>> = columns.iter().map(|column| row.get(column).cloned()).collect();
+        if values.is_none() {
+            self.state.show_error(format!("Show the key column(s) {} to follow this relationship", columns.join(", ")));
+        }
+        values
+    }
+
+    /// The selected table when its rows can be related to others (not a query result)
+    fn get_relation_source(&mut self) -> Option<String> {
+        match self.state.selected_table.clone() {
+            Some(table) if self.state.is_query_result(&table) => {
+                self.state.show_error("Query results have no relationships to follow".to_string());
+                None
+            }
+            Some(table) if self.state.table_data.as_ref().is_some_and(|data| !data.rows.is_empty()) => Some(table),
+            _ => {
+                self.state.show_error("Select a row first".to_string());
+                None
+            }
+        }
+    }
+
+    /// Open the row referenced by the selected foreign-key cell
+    fn follow_foreign_key(&mut self) {
+        let Some(table) = self.get_relation_source() else {
+            return;
+        };
+        let Some(column) = self.state.selected_column.clone() else {
+            self.state.show_error("Select a foreign-key cell to follow".to_string());
+            return;
+        };
+        let Some(schema) = self.load_table_schema(&table) else {
+            self.state.show_error(format!("Failed to load the keys of '{table}'"));
+            return;
+        };
+        let Some(foreign_key) = schema.foreign_keys.into_iter().find(|foreign_key| foreign_key.columns.contains(&column)) else {
+            self.state.show_error(format!("'{column}' is not a foreign key"));
+            return;
+        };
+        let Some(values) = self.get_selected_row_key(&foreign_key.columns) else {
+            return;
+        };
+        let Some(filters) = crate::db::relations::key_filters(&foreign_key.referenced_columns, &values) else {
+            self.state.show_info(format!("'{column}' is NULL and references no row"));
+            return;
+        };
+
+        let key: Vec<String> = foreign_key.columns.iter().zip(&values)
+            .map(|(column, value)| format!("{column}={}", value.as_deref().unwrap_or("NULL")))
+            .collect();
+        self.open_related_rows(format!("{table}({})", key.join(", ")), foreign_key.referenced_table, filters);
+    }
+
+    /// List the tables with rows that reference the selected row
+    fn show_reverse_references(&mut self) {
+        let Some(table) = self.get_relation_source() else {
+            return;
+        };
+        let references = match self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database connection available"))
+            .and_then(|connection| crate::db::relations::incoming_references(connection, &table))
+        {
+            Ok(references) => references,
+            Err(e) => {
+                self.state.show_error(format!("Failed to load references: {e}"));
+                return;
+            }
+        };
+        if references.is_empty() {
+            self.state.show_info(format!("No foreign keys reference '{table}'"));
+            return;
+        }
+
+        let mut entries = Vec::new();
+        for reference in references {
+            let Some(values) = self.get_selected_row_key(&reference.foreign_key.referenced_columns) else {
+                return;
+            };
+            let Some(filters) = crate::db::relations::key_filters(&reference.foreign_key.columns, &values) else {
+                continue;
+            };
+            let row_count = self.database_manager.get_current_connection()
+                .and_then(|connection| crate::db::relations::count_matching_rows(connection, &reference.table_name, &filters).ok())
+                .unwrap_or(0);
+            entries.push(crate::app::state::ReverseReference { reference, filters, row_count });
+        }
+
+        let label = format!("{table} row {}", self.state.selected_row + 1);
+        self.state.reverse_references = Some(crate::app::state::ReverseReferences { label, entries, selected: 0 });
+    }
+
+    fn follow_reverse_reference(&mut self) {
+        let Some(references) = self.state.reverse_references.take() else {
+            return;
+        };
+        let Some(entry) = references.entries.into_iter().nth(references.selected) else {
+            return;
+        };
+        let Some(table) = self.state.selected_table.clone() else {
+            return;
+        };
+        let key: Vec<String> = entry.filters.iter()
+            .map(|(column, filter)| format!("{column} {filter}"))
+            .collect();
+        self.open_related_rows(format!("{table} ← {}", key.join(", ")), entry.reference.table_name, entry.filters);
+    }
+
+    /// Show `table` filtered to related rows, remembering the current view for going back
+    fn open_related_rows(&mut self, label: String, table: String, filters: std::collections::HashMap<String, String>) {
+        self.state.exit_inspect_mode();
+        self.state.push_relation_step(label);
+        let trail = std::mem::take(&mut self.state.relation_trail);
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        let _ = workflows.select_table(table);
+        self.state.relation_trail = trail;
+        self.state.column_filters = filters;
+        self.sync_selected_table_index();
+        self.fetch_table_data();
+    }
+
+    fn relation_back(&mut self) {
+        if self.state.pop_relation_step() {
+            self.sync_selected_table_index();
+        }
+    }
+
+    fn open_index_manager(&mut self) {
+        let selection = if self.state.active_panel == NavigationPanel::TableList {
+            self.get_sidebar_selected_table()
+        } else {
+            self.state.selected_table.clone().map(|table| {
+                let is_view = self.get_current_table_infos().iter()
+                    .any(|info| info.name == table && info.is_view());
+                (table, is_view)
+            })
+        };
+        match selection {
+            Some((table, _)) if self.state.is_query_result(&table) => {
+                self.state.show_error("Query results have no indexes".to_string());
+            }
+            Some((table, true)) => self.state.show_error(format!("'{table}' is a view; views can't be indexed")),
+            Some((table, false)) => self.load_index_manager(table),
+            None => self.state.show_error("No table selected".to_string()),
+        }
+    }
+
+    fn open_storage_breakdown(&mut self) {
+        let selection = if self.state.active_panel == NavigationPanel::TableList {
+            self.get_sidebar_selected_table()
+        } else {
+            self.state.selected_table.clone().map(|table| {
+                let is_view = self.get_current_table_infos().iter()
+                    .any(|info| info.name == table && info.is_view());
+                (table, is_view)
+            })
+        };
+        let table_name = match selection {
+            Some((table, _)) if self.state.is_query_result(&table) => {
+                self.state.show_error("Query results are not stored on disk".to_string());
+                return;
+            }
+            Some((table, true)) => {
+                self.state.show_error(format!("'{table}' is a view; views have no storage of their own"));
+                return;
+            }
+            Some((table, false)) => table,
+            None => {
+                self.state.show_error("No table selected".to_string());
+                return;
+            }
+        };
+        match self.database_manager.load_table_storage(&table_name) {
+            Ok(storage) => {
+                let estimated_rows = self.get_current_table_infos().iter()
+                    .find(|info| info.name == table_name)
+                    .and_then(|info| info.estimated_rows);
+                self.state.storage_breakdown = Some(crate::app::state::StorageBreakdown {
+                    table_name,
+                    estimated_rows,
+                    storage,
+                    scroll: 0,
+                });
+            }
+            Err(e) => self.state.show_error(format!("Failed to load storage info: {e}")),
+        }
+    }
+
+    /// (Re)load the index list of a table, with hints on how the current filters use it
+    fn load_index_manager(&mut self, table_name: String) {
+        let Some(connection) = self.database_manager.get_current_connection() else {
+            self.state.show_error("No database connection available".to_string());
+            return;
+        };
+        let table_info = crate::db::TableInfo::new(table_name.clone());
+        let indexes = match crate::db::TableSchema::load(connection, table_info) {
+            Ok(schema) => schema.indexes,
+            Err(e) => {
+                self.state.show_error(format!("Failed to load indexes: {e}"));
+                return;
+            }
+        };
+
+        let hints = if self.state.selected_table.as_deref() == Some(table_name.as_str()) {
+            let mut filtered_columns: Vec<String> = self.state.column_filters.keys().cloned().collect();
+            filtered_columns.sort();
+            let filter = self.state.get_bulk_filter_clause().unwrap_or_default();
+            let select_sql = format!("SELECT * FROM {} {filter}", crate::db::schema::quote_table_name(&table_name));
+            crate::db::index::index_usage_hints(connection, &select_sql, &filtered_columns, &indexes)
+                .unwrap_or_else(|e| vec![format!("EXPLAIN failed: {e}")])
+        } else {
+            vec!["Open the table and filter it to see whether the filters use an index".to_string()]
+        };
+        self.state.open_index_manager(table_name, indexes, hints);
+    }
+
+    fn handle_index_manager_key(&mut self, code: KeyCode) {
+        let Some(manager) = &self.state.index_manager else {
+            return;
+        };
+        if manager.form.is_some() {
+            match code {
+                KeyCode::Esc => self.state.cancel_index_form(),
+                KeyCode::Enter => self.create_index_from_form(),
+                KeyCode::Tab | KeyCode::Down => self.state.index_form_next_field(),
+                KeyCode::Backspace => self.state.remove_char_from_index_form(),
+                KeyCode::Char(c) => self.state.add_char_to_index_form(c),
+                _ => {}
+            }
+        } else if manager.confirming_drop {
+            match code {
+                KeyCode::Esc => self.state.set_confirming_index_drop(false),
+                KeyCode::Char('y') => self.drop_selected_index(),
+                _ => {}
+            }
+        } else {
+            match code {
+                KeyCode::Esc | KeyCode::Char('N') => self.state.close_index_manager(),
+                KeyCode::Char('j') | KeyCode::Down => self.state.move_index_selection(true),
+                KeyCode::Char('k') | KeyCode::Up => self.state.move_index_selection(false),
+                KeyCode::Char('a') => self.state.start_index_form(),
+                KeyCode::Char('d') => self.state.set_confirming_index_drop(true),
+                _ => {}
+            }
+        }
+    }
+
+    fn create_index_from_form(&mut self) {
+        let Some((table_name, form)) = self.state.index_manager.as_ref()
+            .and_then(|manager| manager.form.clone().map(|form| (manager.table_name.clone(), form))) else {
+            return;
+        };
+        let columns = crate::db::index::parse_index_columns(&form.columns);
+        if form.name.trim().is_empty() || columns.is_empty() {
+            self.state.show_error("An index needs a name and at least one column".to_string());
+            return;
+        }
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.create_index(table_name.clone(), form.name.trim().to_string(), columns, form.unique) {
+            Ok(()) => self.load_index_manager(table_name),
+            // Keep the form open so it can be corrected
+            Err(e) => self.state.show_error(format!("Create index failed: {e}")),
+        }
+    }
+
+    fn drop_selected_index(&mut self) {
+        let (Some(table_name), Some(index_name)) = (
+            self.state.index_manager.as_ref().map(|manager| manager.table_name.clone()),
+            self.state.get_selected_index().map(|index| index.name.clone()),
+        ) else {
+            return;
+        };
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        let result = workflows.drop_index(table_name.clone(), index_name);
+        if let Err(e) = result {
+            self.state.show_error(format!("Drop index failed: {e}"));
+            self.state.set_confirming_index_drop(false);
+            return;
+        }
+        self.load_index_manager(table_name);
+    }
+
+    fn start_schema_drop_confirmation(&mut self, schema: String) {
+        if crate::db::schema::split_schema_name(&schema).1 == crate::db::schema::DEFAULT_SCHEMA {
+            self.state.show_error(format!("The '{schema}' schema can't be dropped"));
+        } else {
+            self.state.start_schema_drop_confirmation(schema);
+        }
+    }
+
+    fn create_schema(&mut self) {
+        let schema_name = self.state.schema_name_input.trim().to_string();
+        if schema_name.is_empty() {
+            self.state.show_error("Enter a schema name".to_string());
+            return;
+        }
+        self.state.cancel_schema_name_input();
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.create_schema(schema_name.clone()) {
+            Ok(()) => self.selected_sidebar_node = Some(schema_node_key(&schema_name)),
+            Err(e) => self.state.show_error(format!("Failed to create schema: {e}")),
+        }
+    }
+
+    fn drop_schema(&mut self, schema_name: String) {
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.drop_schema(schema_name.clone()) {
+            Ok(()) => {
+                self.toggled_sidebar_nodes.remove(&schema_node_key(&schema_name));
+                self.selected_table_index = 0;
+                self.selected_sidebar_node = None;
+                self.sync_selected_table_index();
+            }
+            Err(e) => self.state.show_error(format!("Failed to drop schema: {e}")),
+        }
+    }
+
+    fn start_truncate_confirmation(&mut self) {
+        match self.get_sidebar_selected_table() {
+            Some((table, true)) => self.state.show_error(format!("'{table}' is a view and can't be truncated")),
+            Some((table, false)) => self.state.start_truncate_confirmation(table),
+            None => self.state.show_error("No table selected".to_string()),
+        }
+    }
+
+    fn submit_table_operation(&mut self) {
+        let new_name = self.state.table_operation_input.trim().to_string();
+        let Some(operation) = self.state.table_operation.clone() else {
+            return;
+        };
+        let is_comment = operation.kind == crate::app::state::TableOperationKind::Comment;
+        if !is_comment && (new_name.is_empty() || new_name == operation.table_name) {
+            self.state.show_error("Enter a new name".to_string());
+            return;
+        }
+        self.state.cancel_table_operation();
+
+        let database = self.database_manager.get_current_database().unwrap_or_default().to_string();
+        let is_staging = self.state.is_staging_edits(Some(&database));
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        let result = match operation.kind {
+            crate::app::state::TableOperationKind::Rename => {
+                workflows.rename_table(operation.table_name.clone(), new_name, operation.is_view).map(Some)
+            }
+            crate::app::state::TableOperationKind::Duplicate { with_data } => {
+                workflows.duplicate_table(operation.table_name.clone(), new_name, with_data).map(|()| None)
+            }
+            crate::app::state::TableOperationKind::Comment => {
+                // An empty comment removes it
+                let comment = (!new_name.is_empty()).then_some(new_name);
+                workflows.comment_on_table(operation.table_name.clone(), comment, operation.is_view).map(|()| None)
+            }
+        };
+        match result {
+            Ok(renamed) => {
+                // Layouts are saved to disk, so a rename staged in a transaction moves them on commit
+                if let (Some(new_name), false) = (renamed, is_staging) {
+                    self.migrate_renamed_layouts(&database, &[(operation.table_name.clone(), new_name)]);
+                }
+                self.sync_selected_table_index();
+                if self.state.table_data.is_some() {
+                    self.fetch_table_data_preserve_column();
+                }
+            }
+            Err(e) => self.state.show_error(format!("Failed on '{}': {e}", operation.table_name)),
+        }
+    }
+
+    /// Move the named layouts of renamed tables, given as (old, new), along with them
+    fn migrate_renamed_layouts(&mut self, database: &str, renames: &[(String, String)]) {
+        for (old, new) in renames {
+            if let Err(e) = self.layout_store.rename_table(database, old, new) {
+                self.state.show_error(format!("Failed to move the layouts of '{old}': {e}"));
+            }
+        }
+    }
+
+    fn truncate_table(&mut self, table_name: String) {
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.truncate_table(table_name.clone()) {
+            Ok(()) => {
+                if self.state.selected_table.as_deref() == Some(table_name.as_str()) {
+                    self.fetch_table_data();
+                }
+            }
+            Err(e) => self.state.show_error(format!("Failed to truncate table: {e}")),
+        }
+    }
+
+    fn confirm_delete(&mut self) {
+        let (item_type, item_name) = match &self.state.delete_confirmation {
+            crate::app::state::DeleteConfirmationState::Database(name) => ("database", name.clone()),
+            crate::app::state::DeleteConfirmationState::Table(name) => ("table", name.clone()),
+            crate::app::state::DeleteConfirmationState::Rows(table, rows) => {
+                let (table, rows) = (table.clone(), rows.clone());
+                self.delete_rows(table, &rows);
+                return;
+            }
+            crate::app::state::DeleteConfirmationState::Truncate(table) => {
+                let table = table.clone();
+                self.truncate_table(table);
+                return;
+            }
+            crate::app::state::DeleteConfirmationState::Schema(schema) => {
+                let schema = schema.clone();
+                self.drop_schema(schema);
+                return;
+            }
+            _ => return,
+        };
+        
+        match item_type {
+            "database" => self.delete_database(&item_name),
+            "table" => self.delete_table(&item_name),
+            _ => {}
+        }
+    }
+
+    fn delete_database(&mut self, db_name: &str) {
+        if self.state.transaction.as_ref().is_some_and(|transaction| transaction.database == db_name) {
+            self.state.show_error(format!("Commit or roll back the open transaction on '{db_name}' first"));
+            return;
+        }
+        if let Err(e) = self.database_manager.remove_database(db_name) {
+            self.state.show_error(format!("Failed to delete database: {e}"));
+        } else {
+            self.sync_selected_db_index();
+            self.selected_table_index = 0;
+            self.selected_sidebar_node = None;
+            self.state.show_success(format!("Deleted database '{db_name}'"));
+        }
+    }
+
+    fn delete_table(&mut self, table_name: &str) {
+        if let Err(e) = self.database_manager.remove_table(table_name) {
+            self.state.show_error(format!("Failed to delete table: {e}"));
+        } else {
+            // Clear table data if we deleted the currently viewed table
+            if let Some(current_table) = &self.state.selected_table {
+                if current_table == table_name {
+                    self.state.table_data = None;
+                    self.state.selected_table = None;
+                }
+            }
+            self.sync_selected_table_index();
+            self.state.show_success(format!("Deleted table '{table_name}'"));
+        }
+    }
+
+    fn save_current_database_to_file(&mut self) {
+        if let Some(current_db) = self.database_manager.get_current_database() {
+            let db_name = current_db.to_string();
+            let filename = self.state.save_filename.clone();
+            
+            // Add .db extension if not present
+            let file_path = if filename.ends_with(".db") || filename.ends_with(".duckdb") {
+                filename
+            } else {
+                format!("{filename}.db")
             };
             
-            column_widths.push(col_width);
+            let mut workflows = DatabaseWorkflows::new(
+                &mut self.database_manager,
+                &mut self.action_logger,
+                &mut self.state,
+            );
+            
+            match workflows.save_database_to_file(db_name, std::path::PathBuf::from(file_path)) {
+                Ok(_) => {
+                    // Success message handled by workflow
+                }
+                Err(e) => {
+                    self.state.show_error(format!("Failed to save database: {e}"));
+                }
+            }
+        } else {
+            self.state.show_error("No database selected to save".to_string());
+        }
+    }
+
+    fn create_view_from_current_state(&mut self) {
+        if let (Some(connection), Some(table_name)) = (
+            self.database_manager.get_current_connection(),
+            &self.state.selected_table
+        ) {
+            let view_name = self.state.new_view_name.trim().to_string();
+            
+            // Generate SQL from current view state
+            if let Some(query_sql) = self.state.generate_view_sql(table_name) {
+                let create_view_sql = format!("CREATE VIEW {view_name} AS {query_sql}");
+                
+                // Execute the CREATE VIEW statement
+                match connection.execute(&create_view_sql, []) {
+                    Ok(_) => {
+                        self.state.show_success(format!("View '{view_name}' created successfully"));
+                        
+                        // Refresh the table list to show the new view
+                        if let Some(current_db) = self.database_manager.get_current_database() {
+                            let db_name = current_db.to_string();
+                            if let Err(e) = self.database_manager.refresh_database(&db_name) {
+                                self.action_logger.log_error(&format!("Failed to refresh table list: {e}"));
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        self.state.show_error(format!("Failed to create view: {e}"));
+                        self.action_logger.log_error(&format!("CREATE VIEW failed: {e}"));
+                    }
+                }
+            } else {
+                self.state.show_error("Failed to generate SQL for current view state".to_string());
+            }
+        } else {
+            self.state.show_error("No database connection or table selected".to_string());
+        }
+    }
+
+    fn open_selected_table_in_new_tab(&mut self) {
+        if self.state.active_panel != NavigationPanel::TableList || self.state.is_modifying {
+            self.state.show_error("Select a table in the sidebar to open it in a new tab".to_string());
+            return;
+        }
+
+        if let Some(table_name) = self.get_sidebar_table_name() {
+            self.state.open_new_tab();
+            let mut workflows = DatabaseWorkflows::new(
+                &mut self.database_manager,
+                &mut self.action_logger,
+                &mut self.state,
+            );
+            let _ = workflows.select_table(table_name);
+            self.fetch_table_data();
+            self.state.set_active_panel(NavigationPanel::MainContent);
+        }
+    }
+
+    fn open_query_in_new_tab(&mut self) {
+        let sql = self.state.query_input.trim().to_string();
+        if sql.is_empty() {
+            self.state.cancel_query_input();
+            return;
+        }
+        if crate::db::definition::is_ddl_statement(&sql) {
+            self.execute_definition(sql);
+            return;
+        }
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.open_query_in_new_tab(sql) {
+            Ok(()) => {
+                self.state.cancel_query_input();
+                self.fetch_table_data();
+                self.state.set_active_panel(NavigationPanel::MainContent);
+            }
+            Err(e) => {
+                // Keep the input open so the query can be corrected
+                self.state.show_error(e.to_string());
+            }
+        }
+    }
+
+    /// Run a definition from the query editor and refresh whatever it changed
+    fn execute_definition(&mut self, sql: String) {
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.execute_definition(sql) {
+            Ok(()) => {
+                self.state.cancel_query_input();
+                self.sync_selected_table_index();
+                if self.state.table_data.is_some() {
+                    self.fetch_table_data_preserve_column();
+                }
+            }
+            Err(e) => {
+                // Keep the input open so the statement can be corrected
+                self.state.show_error(format!("Definition failed: {e}"));
+            }
+        }
+    }
+
+    /// The table, view, macro or sequence selected in the sidebar or open in the viewer
+    fn get_definition_target(&self) -> Result<(String, crate::db::definition::DefinitionKind), String> {
+        use crate::db::definition::DefinitionKind;
+
+        if self.state.active_panel == NavigationPanel::TableList {
+            if let Some(object) = self.get_selected_catalog_object() {
+                let name = crate::db::schema::qualified_table_name(&object.schema, &object.name);
+                return DefinitionKind::from_catalog_kind(object.kind)
+                    .map(|kind| (name, kind))
+                    .ok_or_else(|| format!("No stored definition for '{}'", object.name));
+            }
+            return self.get_sidebar_selected_table()
+                .map(|(table, is_view)| (table, if is_view { DefinitionKind::View } else { DefinitionKind::Table }))
+                .ok_or_else(|| "Select a table, view, macro or sequence".to_string());
+        }
+
+        let table = self.state.selected_table.clone().ok_or_else(|| "No table selected".to_string())?;
+        if self.state.is_query_result(&table) {
+            return Err("Query results have no definition".to_string());
+        }
+        let is_view = self.get_current_table_infos().iter().any(|info| info.name == table && info.is_view());
+        Ok((table, if is_view { DefinitionKind::View } else { DefinitionKind::Table }))
+    }
+
+    fn open_definition_view(&mut self) {
+        let (name, kind) = match self.get_definition_target() {
+            Ok(target) => target,
+            Err(message) => {
+                self.state.show_error(message);
+                return;
+            }
+        };
+        let Some(connection) = self.database_manager.get_current_connection() else {
+            self.state.show_error("No database connection available".to_string());
+            return;
+        };
+        match crate::db::definition::load_definition(connection, kind, &name) {
+            Ok(sql) => self.state.open_definition_view(name, kind, sql),
+            Err(e) => self.state.show_error(format!("Failed to load the definition of '{name}': {e}")),
+        }
+    }
+
+    fn handle_definition_view_key(&mut self, code: KeyCode) {
+        let Some(view) = self.state.definition_view.clone() else {
+            return;
+        };
+        if view.save_path.is_some() {
+            match code {
+                KeyCode::Esc => self.state.cancel_definition_save(),
+                KeyCode::Enter => self.save_definition(),
+                KeyCode::Backspace => self.state.remove_char_from_definition_save(),
+                KeyCode::Char(c) => self.state.add_char_to_definition_save(c),
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('Q') => self.state.definition_view = None,
+            KeyCode::Char('j') | KeyCode::Down => self.state.scroll_definition_view(true),
+            KeyCode::Char('k') | KeyCode::Up => self.state.scroll_definition_view(false),
+            KeyCode::Char('y') => match copy_to_clipboard(&view.sql) {
+                Ok(()) => self.state.show_success(format!("Copied the definition of '{}'", view.object_name)),
+                Err(e) => self.state.show_error(format!("Copy failed: {e}")),
+            },
+            KeyCode::Char('w') => self.state.start_definition_save(),
+            KeyCode::Char(':') => self.open_definition_in_query_editor(view.sql),
+            KeyCode::Char('e') if view.kind == crate::db::definition::DefinitionKind::View => {
+                let sql = crate::db::definition::replace_view_sql(&view.object_name, &view.sql);
+                self.open_definition_in_query_editor(sql);
+            }
+            _ => {}
+        }
+    }
+
+    fn open_definition_in_query_editor(&mut self, sql: String) {
+        self.state.definition_view = None;
+        self.state.start_query_input();
+        // The query editor is a single line
+        self.state.query_input = crate::db::definition::single_line_sql(&sql);
+    }
+
+    fn save_definition(&mut self) {
+        let Some(view) = self.state.definition_view.clone() else {
+            return;
+        };
+        let path = view.save_path.as_deref().unwrap_or_default().trim().to_string();
+        if path.is_empty() {
+            self.state.show_error("Enter a file path".to_string());
+            return;
+        }
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.export_definition(view.object_name, &view.sql, std::path::PathBuf::from(path)) {
+            Ok(()) => self.state.cancel_definition_save(),
+            Err(e) => self.state.show_error(e.to_string()),
+        }
+    }
+
+    fn compare_schemas_from_input(&mut self) {
+        let (source, target) = match crate::db::schema_diff::parse_schema_diff_input(&self.state.schema_diff_input) {
+            Ok(sides) => sides,
+            Err(e) => {
+                self.state.show_error(format!("Invalid comparison: {e}"));
+                return;
+            }
+        };
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.compare_schemas(source, target) {
+            Ok(()) => self.state.cancel_schema_diff_input(),
+            Err(e) => {
+                // Keep the input open so the databases can be corrected
+                self.state.show_error(format!("Schema diff failed: {e}"));
+            }
+        }
+    }
+
+    fn handle_schema_diff_key(&mut self, code: KeyCode) {
+        let Some(view) = self.state.schema_diff.clone() else {
+            return;
+        };
+        if view.save_path.is_some() {
+            match code {
+                KeyCode::Esc => self.state.cancel_schema_diff_save(),
+                KeyCode::Enter => self.save_migration_script(),
+                KeyCode::Backspace => self.state.remove_char_from_schema_diff_save(),
+                KeyCode::Char(c) => self.state.add_char_to_schema_diff_save(c),
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('W') => self.state.schema_diff = None,
+            KeyCode::Char('j') | KeyCode::Down => self.state.scroll_schema_diff(true),
+            KeyCode::Char('k') | KeyCode::Up => self.state.scroll_schema_diff(false),
+            KeyCode::Char('y') => match copy_to_clipboard(&view.script) {
+                Ok(()) => self.state.show_success("Copied the migration script".to_string()),
+                Err(e) => self.state.show_error(format!("Copy failed: {e}")),
+            },
+            KeyCode::Char('w') => self.state.start_schema_diff_save(),
+            _ => {}
+        }
+    }
+
+    fn save_migration_script(&mut self) {
+        let Some(view) = self.state.schema_diff.clone() else {
+            return;
+        };
+        let path = view.save_path.as_deref().unwrap_or_default().trim().to_string();
+        if path.is_empty() {
+            self.state.show_error("Enter a file path".to_string());
+            return;
+        }
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.export_migration_script(view.source, &view.script, std::path::PathBuf::from(path)) {
+            Ok(()) => self.state.cancel_schema_diff_save(),
+            Err(e) => self.state.show_error(e.to_string()),
+        }
+    }
+
+    fn compare_relations_from_input(&mut self) {
+        let (left, right, keys) = match crate::db::diff::TableDiff::parse_input(&self.state.compare_input) {
+            Ok(parts) => parts,
+            Err(e) => {
+                self.state.show_error(format!("Invalid comparison: {e}"));
+                return;
+            }
+        };
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.compare_relations(left, right, keys) {
+            Ok(()) => {
+                self.state.cancel_compare_input();
+                self.fetch_table_data();
+                self.state.set_active_panel(NavigationPanel::MainContent);
+            }
+            Err(e) => {
+                // Keep the input open so the comparison can be corrected
+                self.state.show_error(format!("Compare failed: {e}"));
+            }
+        }
+    }
+
+    /// The selected table if its rows can be changed, otherwise shows why not
+    fn check_editable(&mut self) -> Option<String> {
+        let Some(table) = self.state.selected_table.clone() else {
+            self.state.show_error("No table selected to edit".to_string());
+            return None;
+        };
+        if self.state.is_query_result(&table) {
+            self.state.show_error("Query results are read-only".to_string());
+            return None;
+        }
+        let is_view = self.get_current_table_infos().iter()
+            .any(|info| info.name == table && info.table_type == "VIEW");
+        if is_view {
+            self.state.show_error(format!("'{table}' is a view and can't be edited; edit its underlying tables instead"));
+            return None;
+        }
+        let read_only = self.database_manager.get_current_connection()
+            .map(|connection| crate::db::edit::is_read_only(connection).unwrap_or(false))
+            .unwrap_or(true);
+        if read_only {
+            self.state.show_error("Database is opened read-only; editing is disabled".to_string());
+            return None;
+        }
+        if self.state.row_key.is_none() {
+            self.state.show_error(format!("Rows of '{table}' can't be addressed for editing"));
+            return None;
+        }
+        Some(table)
+    }
+
+    fn start_cell_edit(&mut self) {
+        if self.check_editable().is_some() && !self.state.start_cell_edit() {
+            self.state.show_error("Select a cell to edit".to_string());
+        }
+    }
+
+    fn start_insert_row(&mut self, duplicate: bool) {
+        let Some(table) = self.check_editable() else {
+            return;
+        };
+        let seed = if duplicate {
+            match self.state.get_selected_row_values() {
+                Some(values) => Some(values),
+                None => {
+                    self.state.show_error("No row selected to duplicate".to_string());
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        let columns = self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database connection available"))
+            .and_then(|connection| Ok(connection.try_clone()?))
+            .and_then(|connection| crate::db::query::QueryExecutor::new(connection).get_table_columns(&table));
+        match columns {
+            Ok(columns) => self.state.start_row_form(table, columns, seed),
+            Err(e) => self.state.show_error(format!("Failed to read columns of '{table}': {e}")),
+        }
+    }
+
+    fn submit_row_form(&mut self) {
+        let Some(form) = self.state.row_form.clone() else {
+            return;
+        };
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.insert_row(form) {
+            Ok(()) => {
+                self.state.cancel_row_form();
+                self.fetch_table_data_preserve_column();
+            }
+            Err(e) => {
+                // Keep the form open so the values can be corrected
+                self.state.show_error(format!("Insert failed: {e}"));
+            }
+        }
+    }
+
+    fn start_column_change(&mut self) {
+        if self.check_editable().is_some() && self.state.get_inspect_selected_column().is_some() {
+            self.state.start_column_change_input();
+        }
+    }
+
+    fn submit_column_change(&mut self) {
+        let change = match crate::db::alter::ColumnChange::parse(&self.state.column_change_input) {
+            Ok(change) => change,
+            Err(e) => {
+                self.state.show_error(e.to_string());
+                return;
+            }
+        };
+        let (Some(table_name), Some(column_name)) = (self.state.selected_table.clone(), self.state.get_inspect_selected_column()) else {
+            self.state.cancel_column_change_input();
+            return;
+        };
+
+        // Type changes and drops are confirmed first; type changes after a dry run
+        let report = match &change {
+            crate::db::alter::ColumnChange::ChangeType(data_type) => {
+                let failures = self.database_manager.get_current_connection()
+                    .ok_or_else(|| anyhow::anyhow!("No database connection available"))
+                    .and_then(|connection| crate::db::alter::count_cast_failures(connection, &table_name, &column_name, data_type));
+                match failures {
+                    Ok(0) => Some(format!("All values of '{column_name}' cast to {data_type}")),
+                    Ok(failures) => Some(format!("{failures} value(s) of '{column_name}' fail to cast to {data_type}; the change will fail")),
+                    Err(e) => {
+                        self.state.show_error(format!("Dry run failed: {e}"));
+                        return;
+                    }
+                }
+            }
+            crate::db::alter::ColumnChange::Drop => Some(format!("Drop '{column_name}' and all of its data")),
+            _ => None,
+        };
+
+        self.state.cancel_column_change_input();
+        match report {
+            Some(report) => {
+                self.state.pending_column_change = Some(crate::app::state::PendingColumnChange {
+                    table_name,
+                    column_name,
+                    change,
+                    report,
+                });
+            }
+            None => self.alter_column(table_name, column_name, change),
+        }
+    }
+
+    fn alter_column(&mut self, table_name: String, column_name: String, change: crate::db::alter::ColumnChange) {
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.alter_column(table_name, column_name, change) {
+            Ok(()) => {
+                self.fetch_table_data_preserve_column();
+            }
+            Err(e) => self.state.show_error(format!("Alter table failed: {e}")),
+        }
+    }
+
+    /// The selected table if a bulk change can be made to its filtered rows
+    fn check_bulk_change(&mut self) -> Option<String> {
+        let table = self.check_editable()?;
+        if self.state.get_bulk_filter_clause().is_none() {
+            self.state.show_error("Apply a filter first; bulk changes act on the filtered rows".to_string());
+            return None;
+        }
+        Some(table)
+    }
+
+    fn start_bulk_update(&mut self) {
+        if self.check_bulk_change().is_none() {
+            return;
+        }
+        if self.state.selected_column.is_some() {
+            self.state.start_bulk_update_input();
+        } else {
+            self.state.show_error("Select the column to update".to_string());
+        }
+    }
+
+    fn start_bulk_delete(&mut self) {
+        if let Some(table) = self.check_bulk_change() {
+            self.preview_bulk_change(table, crate::app::state::BulkChangeKind::Delete);
+        }
+    }
+
+    fn preview_bulk_update(&mut self) {
+        let expression = self.state.bulk_update_input.trim().to_string();
+        let (Some(table), Some(column_name)) = (self.state.selected_table.clone(), self.state.selected_column.clone()) else {
+            self.state.cancel_bulk_update_input();
+            return;
+        };
+        if expression.is_empty() {
+            self.state.show_error("Please enter an expression".to_string());
+            return;
+        }
+        let kind = crate::app::state::BulkChangeKind::Update { column_name, expression };
+        if self.preview_bulk_change(table, kind) {
+            self.state.cancel_bulk_update_input();
+        }
+    }
+
+    /// Count the affected rows and ask for confirmation, returning whether the preview worked
+    fn preview_bulk_change(&mut self, table: String, kind: crate::app::state::BulkChangeKind) -> bool {
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.preview_bulk_change(table, kind) {
+            Ok(change) => {
+                self.state.pending_bulk_change = Some(change);
+                true
+            }
+            Err(e) => {
+                self.state.show_error(format!("Bulk change not possible: {e}"));
+                false
+            }
+        }
+    }
+
+    fn apply_bulk_change(&mut self) {
+        let Some(change) = self.state.pending_bulk_change.take() else {
+            return;
+        };
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.apply_bulk_change(change) {
+            Ok(_) => self.fetch_table_data_preserve_column(),
+            Err(e) => self.state.show_error(format!("Bulk change failed: {e}")),
+        }
+    }
+
+    fn begin_transaction(&mut self) {
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        if let Err(e) = workflows.begin_transaction() {
+            self.state.show_error(format!("Failed to start transaction: {e}"));
+        }
+    }
+
+    /// Commit or roll back the open transaction, returning whether it was closed
+    fn end_transaction(&mut self, commit: bool) -> bool {
+        let renames = self.state.transaction.as_ref()
+            .map(|transaction| (transaction.database.clone(), transaction.renamed_tables.clone()));
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        let result = if commit {
+            workflows.commit_transaction()
+        } else {
+            workflows.rollback_transaction()
+        };
+        match result {
+            Ok(()) => {
+                // Tables renamed in the transaction take their layouts along once committed
+                if let (true, Some((database, renames))) = (commit, renames) {
+                    self.migrate_renamed_layouts(&database, &renames);
+                }
+                self.sync_selected_table_index();
+                // Rolled back rows disappear and row keys may change, so reload the view
+                if self.state.table_data.is_some() && self.state.selected_table.is_some() {
+                    self.fetch_table_data_preserve_column();
+                }
+                true
+            }
+            Err(e) => {
+                self.state.show_error(format!("Failed to end transaction: {e}"));
+                false
+            }
+        }
+    }
+
+    fn delete_rows(&mut self, table: String, rows: &[usize]) {
+        let selected_row = self.state.selected_row;
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.delete_rows(table, rows) {
+            Ok(_) => {
+                self.fetch_table_data_preserve_column();
+                // Stay near where the deleted rows were
+                let row_count = self.state.table_data.as_ref().map_or(0, |data| data.rows.len());
+                self.state.selected_row = selected_row.min(row_count.saturating_sub(1));
+            }
+            Err(e) => self.state.show_error(format!("Delete failed: {e}")),
+        }
+    }
+
+    fn apply_cell_edit(&mut self) {
+        let Some(column) = self.state.selected_column.clone() else {
+            self.state.cancel_cell_edit();
+            return;
+        };
+        let row_index = self.state.selected_row;
+        let value = crate::db::edit::parse_input_value(&self.state.cell_edit_input);
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.update_cell(row_index, column, value) {
+            Ok(key_values) => {
+                self.state.cancel_cell_edit();
+                self.refresh_row(row_index, key_values);
+            }
+            Err(e) => {
+                // Keep the prompt open so the value can be corrected
+                self.state.show_error(format!("Update failed: {e}"));
+            }
+        }
+    }
+
+    /// Reload one row of the viewer from the database after it was changed
+    fn refresh_row(&mut self, row_index: usize, key_values: Vec<String>) {
+        let (Some(table), Some(key), Some(data)) = (
+            self.state.selected_table.clone(),
+            self.state.row_key.clone(),
+            self.state.table_data.as_ref(),
+        ) else {
+            return;
+        };
+        let Some(connection) = self.database_manager.get_current_connection() else {
+            return;
+        };
+
+        let columns_sql = data.columns.iter()
+            .map(|column| crate::db::schema::quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "SELECT {columns_sql}{} FROM {} WHERE {}",
+            key.select_sql(),
+            crate::db::schema::quote_table_name(&table),
+            key.literal_where_sql(&key_values)
+        );
+        match self.execute_query_direct(connection, &sql) {
+            Ok(mut refreshed) => {
+                let keys = key.split_from(&mut refreshed);
+                if let (Some(row), Some(row_key)) = (refreshed.rows.pop(), keys.into_iter().next()) {
+                    if let Some(data) = self.state.table_data.as_mut() {
+                        if let Some(slot) = data.rows.get_mut(row_index) {
+                            *slot = row;
+                        }
+                    }
+                    if let Some(slot) = self.state.row_key_values.get_mut(row_index) {
+                        *slot = row_key;
+                    }
+                }
+            }
+            Err(e) => self.state.show_error(format!("Failed to refresh row: {e}")),
+        }
+    }
+
+    fn export_current_view(&mut self) {
+        let path = self.state.export_path.trim().to_string();
+        if path.is_empty() {
+            self.state.show_error("Please enter a file name".to_string());
+            return;
+        }
+
+        let mut workflows = DatabaseWorkflows::new(
+            &mut self.database_manager,
+            &mut self.action_logger,
+            &mut self.state,
+        );
+        match workflows.export_current_view(std::path::PathBuf::from(path)) {
+            Ok(()) => self.state.cancel_export_path_input(),
+            Err(e) => self.state.show_error(format!("Export failed: {e}")),
+        }
+    }
+
+    // Make the database of a newly activated tab the current one
+    fn activate_tab_database(&mut self, database: Option<String>) {
+        if let Some(database) = database {
+            if self.database_manager.get_current_database() != Some(database.as_str()) {
+                if let Err(e) = self.database_manager.set_current_database(&database) {
+                    self.state.show_error(format!("Failed to switch to database '{database}': {e}"));
+                }
+            }
+        }
+        self.sync_selected_db_index();
+        self.sync_selected_table_index();
+    }
+
+    fn apply_sample_spec_input(&mut self) {
+        let input = self.state.sample_spec_input.trim().to_string();
+
+        if input.is_empty() {
+            self.state.set_sample_spec(None);
+            self.state.show_info("Sampling disabled".to_string());
+        } else {
+            match crate::app::state::SampleSpec::parse(&input) {
+                Ok(sample) => {
+                    self.state.show_success(format!("Sampling {}", sample.describe()));
+                    self.state.set_sample_spec(Some(sample));
+                }
+                Err(e) => {
+                    // Keep the input open so the spec can be corrected
+                    self.state.show_error(format!("Invalid sample: {e}"));
+                    return;
+                }
+            }
+        }
+
+        self.state.cancel_sample_spec_input();
+        let include_limit = self.state.column_filters.is_empty();
+        self.fetch_table_data_preserve_column_with_limit(include_limit);
+        if self.state.inspect_mode {
+            self.reload_inspect_statistics();
+        }
+    }
+
+    // Key under which layouts for the current table are stored: (database, table)
+    fn current_layout_key(&self) -> Option<(String, String)> {
+        let database = self.database_manager.get_current_database()?.to_string();
+        let table = self.state.selected_table.clone()?;
+        Some((database, table))
+    }
+
+    fn get_current_layout_names(&self) -> Vec<String> {
+        if let Some((database, table)) = self.current_layout_key() {
+            self.layout_store.get_layouts(&database, &table)
+                .iter()
+                .map(|layout| layout.name.clone())
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn save_current_layout(&mut self) {
+        let name = self.state.new_layout_name.trim().to_string();
+        let Some((database, table)) = self.current_layout_key() else {
+            self.state.show_error("No table selected to save layout for".to_string());
+            return;
+        };
+
+        if let Some(layout) = self.state.capture_layout(name.clone()) {
+            match self.layout_store.save_layout(&database, &table, layout) {
+                Ok(_) => {
+                    self.state.show_success(format!("Saved layout '{name}'"));
+                    // Point the picker at the layout we just saved
+                    if let Some(index) = self.get_current_layout_names().iter().position(|n| *n == name) {
+                        self.state.layout_picker_index = index;
+                    }
+                }
+                Err(e) => {
+                    self.state.show_error(format!("Failed to save layout: {e}"));
+                    self.action_logger.log_error(&format!("Saving layout '{name}' failed: {e}"));
+                }
+            }
+        }
+    }
+
+    fn apply_selected_layout(&mut self) {
+        let Some((database, table)) = self.current_layout_key() else {
+            return;
+        };
+
+        let layout = self.layout_store.get_layouts(&database, &table)
+            .get(self.state.layout_picker_index)
+            .cloned();
+
+        if let Some(layout) = layout {
+            self.state.begin_view_change();
+            self.state.apply_layout(&layout);
+            self.state.end_view_change();
+            self.state.close_layout_picker();
+            // Filtered views are loaded without a limit, matching the filter workflow
+            let include_limit = self.state.column_filters.is_empty();
+            self.fetch_table_data_preserve_column_with_limit(include_limit);
+            self.state.show_success(format!("Applied layout '{}'", layout.name));
+        }
+    }
+
+    fn delete_selected_layout(&mut self) {
+        let Some((database, table)) = self.current_layout_key() else {
+            return;
+        };
+
+        let names = self.get_current_layout_names();
+        if let Some(name) = names.get(self.state.layout_picker_index) {
+            match self.layout_store.delete_layout(&database, &table, name) {
+                Ok(_) => {
+                    self.state.show_success(format!("Deleted layout '{name}'"));
+                    let remaining = names.len() - 1;
+                    if self.state.layout_picker_index >= remaining {
+                        self.state.layout_picker_index = remaining.saturating_sub(1);
+                    }
+                }
+                Err(e) => {
+                    self.state.show_error(format!("Failed to delete layout: {e}"));
+                }
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame) {
+        // Regions are recorded again as each panel is drawn
+        self.mouse_regions = MouseRegions::default();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Header
+                Constraint::Min(0),    // Main content
+                Constraint::Length(3), // Status bar
+            ])
+            .split(f.area());
+
+        // Header, showing the open tabs once there are any
+        if self.state.tabs.is_empty() {
+            let header = Paragraph::new("🦆 Ducky - DuckDB TUI")
+                .style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
+            f.render_widget(header, chunks[0]);
+        } else {
+            self.render_tab_bar(f, chunks[0]);
+        }
+
+        // Check for inspect mode - use full area for inspection
+        if self.state.inspect_mode {
+            if let Some(table) = self.state.selected_table.clone() {
+                self.render_inspect_view(f, chunks[1], &table);
+            }
+        } else {
+            // Normal mode: Main content area - New 2-panel layout
+            let main_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(30), // Left sidebar
+                    Constraint::Percentage(70), // Table viewer
+                ])
+                .split(chunks[1]);
+
+            // Left sidebar (combined database + table list)
+            self.render_left_sidebar(f, main_chunks[0]);
+
+            // Table viewer (renamed from main content)
+            self.render_table_viewer(f, main_chunks[1]);
+        }
+
+        // Status bar
+        self.render_status_bar(f, chunks[2]);
+
+        // Render notifications
+        self.render_notifications(f, f.area());
+
+        // Render file browser popup if shown
+        if self.show_file_browser {
+            if let Some(ref browser) = self.file_browser {
+                render_file_browser_popup(f, f.area(), browser);
+            }
+        }
+
+        // Render database dropdown overlay if expanded
+        if self.state.database_dropdown_expanded {
+            self.mouse_regions.database_overlay = self.render_database_dropdown_overlay(f, f.area());
+        }
+
+        // Render database name input popup
+        if self.state.is_entering_database_name {
+            self.render_database_name_input(f, f.area());
+        }
+
+        // Render save filename input popup
+        if self.state.is_entering_save_filename {
+            self.render_save_filename_input(f, f.area());
+        }
+        // Render view name input popup
+        if self.state.is_entering_view_name {
+            self.render_view_name_input(f, f.area());
+        }
+
+        // Render query input popup
+        if self.state.is_entering_query {
+            self.render_query_input(f, f.area());
+        }
+
+        // Render compare and export input popups
+        if self.state.is_entering_compare {
+            self.render_compare_input(f, f.area());
+        }
+        if self.state.is_entering_export_path {
+            self.render_export_path_input(f, f.area());
+        }
+
+        // Render tables referencing the selected row
+        if let Some(references) = &self.state.reverse_references {
+            self.render_reverse_references(f, f.area(), references);
+        }
+
+        // Render schema diff input and migration script
+        if self.state.is_entering_schema_diff {
+            self.render_schema_diff_input(f, f.area());
+        }
+        if let Some(view) = &self.state.schema_diff {
+            self.render_schema_diff(f, f.area(), view);
+        }
+
+        // Render definition popup
+        if let Some(view) = &self.state.definition_view {
+            self.render_definition_view(f, f.area(), view);
+        }
+
+        // Render storage breakdown
+        if let Some(breakdown) = &self.state.storage_breakdown {
+            self.render_storage_breakdown(f, f.area(), breakdown);
+        }
+
+        // Render index manager
+        if let Some(manager) = &self.state.index_manager {
+            self.render_index_manager(f, f.area(), manager);
+        }
+
+        // Render rename/duplicate name input
+        if let Some(operation) = &self.state.table_operation {
+            self.render_table_operation_input(f, f.area(), operation);
+        }
+
+        // Render new schema name input
+        if self.state.is_entering_schema_name {
+            self.render_schema_name_input(f, f.area());
+        }
+
+        // Render the alias prompt of a database being attached
+        if let Some(attach) = &self.state.pending_attach {
+            self.render_attach_input(f, f.area(), attach);
+        }
+
+        // Render column change popups
+        if self.state.is_entering_column_change {
+            self.render_column_change_input(f, f.area());
+        }
+        if let Some(pending) = &self.state.pending_column_change {
+            self.render_column_change_confirmation(f, f.area(), pending);
+        }
+
+        // Render bulk change popups
+        if self.state.is_entering_bulk_update {
+            self.render_bulk_update_input(f, f.area());
+        }
+        if let Some(change) = &self.state.pending_bulk_change {
+            self.render_bulk_change_confirmation(f, f.area(), change);
+        }
+
+        // Render transaction popups
+        if self.state.show_pending_changes {
+            self.render_pending_changes(f, f.area());
+        }
+        if self.state.is_confirming_quit {
+            self.render_quit_confirmation(f, f.area());
+        }
+
+        // Render cell edit popup and insert-row form
+        if self.state.is_editing_cell {
+            self.render_cell_edit_input(f, f.area());
+        }
+        if let Some(form) = &self.state.row_form {
+            self.render_row_form(f, f.area(), form);
+        }
+
+        // Render sample spec input popup
+        if self.state.is_entering_sample_spec {
+            self.render_sample_spec_input(f, f.area());
+        }
+
+        // Render layout picker and layout name input popups
+        if self.state.is_picking_layout {
+            self.render_layout_picker(f, f.area());
+        }
+        if self.state.is_entering_layout_name {
+            self.render_layout_name_input(f, f.area());
+        }
+
+        // Render delete confirmation popup
+        if self.state.is_delete_confirmation_active() {
+            self.render_delete_confirmation(f, f.area());
+        }
+    }
+
+    fn render_tab_bar(&mut self, f: &mut Frame, area: Rect) {
+        let titles = self.state.get_tab_titles();
+
+        // Tabs are drawn as " title " separated by a one-column divider
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let mut x = inner.x;
+        for title in &titles {
+            let width = title.chars().count() as u16 + 2;
+            self.mouse_regions.tab_labels.push(Rect { x, y: inner.y, width, height: 1 });
+            x += width + 1;
+        }
+
+        let tabs = Tabs::new(titles)
+            .select(self.state.active_tab)
+            .style(Style::default().fg(Color::White))
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::REVERSED))
+            .block(
+                Block::default()
+                    .title("🦆 Ducky - DuckDB TUI ([/] switch tab, t open in new tab, w close, : query)")
+                    .borders(Borders::ALL),
+            );
+        f.render_widget(tabs, area);
+    }
+
+    fn render_left_sidebar(&mut self, f: &mut Frame, area: Rect) {
+        // Split sidebar into database dropdown area and table list
+        let sidebar_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Database dropdown area
+                Constraint::Min(0),    // Table list area
+            ])
+            .split(area);
+
+        self.mouse_regions.database_dropdown = Some(sidebar_chunks[0]);
+        self.mouse_regions.table_list = Some(sidebar_chunks[1]);
+
+        // Render database dropdown in top area
+        self.render_database_dropdown(f, sidebar_chunks[0]);
+        
+        // Render table list in bottom area
+        self.render_table_list_compact(f, sidebar_chunks[1]);
+    }
+
+    fn render_database_dropdown(&self, f: &mut Frame, area: Rect) {
+        let current_db = self.database_manager.get_current_database().unwrap_or("none");
+        
+        // Always render collapsed state here - expanded state is handled as overlay
+        let content = format!("DB: [{current_db}]");
+        
+        let border_style = self.get_panel_border_style(NavigationPanel::DatabaseList);
+
+        let dropdown = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Database")
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .style(Style::default().fg(Color::White));
+
+        f.render_widget(dropdown, area);
+    }
+
+    fn render_table_list_compact(&self, f: &mut Frame, area: Rect) {
+        let current_table_infos = self.get_current_table_infos();
+        let rows = self.sidebar_rows();
+        let cursor = self.sidebar_cursor(&rows);
+        
+        let items: Vec<ListItem> = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let is_selected = i == cursor;
+                let is_current = match row.node {
+                    SidebarNode::Table(index) => self.state.selected_table.as_ref() == Some(&current_table_infos[index].name),
+                    _ => false,
+                };
+                
+                let style = if is_selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else if is_current {
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    match row.node {
+                        SidebarNode::Database | SidebarNode::Schema => Style::default().fg(Color::Cyan),
+                        SidebarNode::Group | SidebarNode::Detail => Style::default().fg(Color::Gray),
+                        SidebarNode::Table(_) | SidebarNode::Object => Style::default(),
+                    }
+                };
+                
+                let marker = match row.expanded {
+                    Some(true) => "▾ ",
+                    Some(false) => "▸ ",
+                    None => "  ",
+                };
+                let indent = "  ".repeat(row.depth);
+                let current = if is_current { "● " } else { "" };
+                ListItem::new(format!("{indent}{marker}{current}{}", row.label)).style(style)
+            })
+            .collect();
+
+        let border_style = self.get_panel_border_style(NavigationPanel::TableList);
+
+        // The comment of the table under the cursor shows along the bottom border
+        let mut block = Block::default()
+            .title("Tables")
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        if let Some(comment) = self.get_sidebar_table_name().and_then(|table| self.get_table_comment(&table)) {
+            block = block.title_bottom(Line::from(format!(" {comment} ")).style(Style::default().fg(Color::Gray)));
         }
 
-        // Determine visible columns
-        for i in start_col..data.columns.len() {
-            let col_width = column_widths.get(i).unwrap_or(&min_col_width);
-            if used_width + col_width <= available_width {
-                constraints.push(Constraint::Length(*col_width as u16));
-                visible_cols.push(i);
-                used_width += col_width;
+        let list = List::new(items)
+            .block(block)
+            .style(Style::default().fg(Color::White));
+
+        f.render_widget(list, area);
+    }
+
+    fn get_panel_border_style(&self, panel: NavigationPanel) -> Style {
+        let is_active = self.state.active_panel == panel;
+        if is_active {
+            if self.state.is_panel_flashing() {
+                // Flash effect: bright cyan
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
             } else {
-                break;
+                // Normal active: green
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
             }
+        } else {
+            // Inactive: default
+            Style::default()
         }
+    }
 
-        // Ensure we show at least one column
-        if visible_cols.is_empty() && start_col < data.columns.len() {
-            visible_cols.push(start_col);
-            constraints.push(Constraint::Length(min_col_width as u16));
+    fn render_table_viewer(&mut self, f: &mut Frame, area: Rect) {
+        if let Some(diagram) = &self.er_diagram {
+            Self::render_er_diagram(f, area, diagram);
+            return;
         }
 
-        // Create header with styling for selected column and sort indicators
-        let header_cells: Vec<Cell> = visible_cols.iter().map(|&data_col_idx| {
-            let cell_content = &data.columns[data_col_idx];
-            let col_width = column_widths.get(data_col_idx).unwrap_or(&min_col_width);
-            
-            // Map data column index to virtual column index
-            let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
-            
-            // Get the complete header text with all indicators
-            let header_with_sort = self.get_final_header_text(virtual_col_idx, cell_content);
-            
-            let text = if self.state.is_column_expanded(virtual_col_idx) {
-                // For expanded columns, show full header without truncation
-                header_with_sort
-            } else {
-                truncate_text(&header_with_sort, *col_width)
-            };
-            
-            if Some(virtual_col_idx) == self.state.get_selected_column_index() {
-                Cell::from(text).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
-            } else {
-                Cell::from(text)
+        // Check if we're in inspect mode and have a selected table
+        if self.state.inspect_mode {
+            if let Some(table) = self.state.selected_table.clone() {
+                self.render_inspect_view(f, area, &table);
+                return;
             }
-        }).collect();
+        }
 
-        // Create data rows with text wrapping support for expanded columns
-        let mut rows: Vec<Row> = Vec::new();
-        
-        for (display_idx, row) in data.rows[start_row..end_row].iter().enumerate() {
-            let actual_row_idx = start_row + display_idx;
-            let is_selected_row = actual_row_idx == self.state.selected_row;
-            
-            // First, prepare wrapped content for all cells in this row
-            let mut cell_lines: Vec<Vec<String>> = Vec::new();
-            let mut max_lines = 1;
-            
-            for &data_col_idx in &visible_cols {
-                let empty_string = String::new();
-                let cell_content = row.get(data_col_idx).unwrap_or(&empty_string);
-                let col_width = column_widths.get(data_col_idx).unwrap_or(&min_col_width);
-                
-                // Map data column index to virtual column index
-                let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
-                
-                let lines = if self.state.is_column_expanded(virtual_col_idx) {
-                    wrap_text(cell_content, *col_width)
-                } else {
-                    vec![truncate_text(cell_content, *col_width)]
-                };
-                
-                max_lines = max_lines.max(lines.len());
-                cell_lines.push(lines);
+        // Split view: the active tab on the left, its partner on the right
+        let area = if let Some(partner) = self.state.split_tab {
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(area);
+            self.render_split_partner(f, panes[1], partner);
+            panes[0]
+        } else {
+            area
+        };
+
+        // Cache area height for navigation calculations
+        self.state.last_table_area_height = area.height;
+        self.mouse_regions.table_viewer = Some(area);
+        let border_style = self.get_panel_border_style(NavigationPanel::MainContent);
+
+        let (content, title): (String, String) = if self.state.current_state == AppState::ImportWizard && self.state.is_creating_table {
+            match self.state.table_creation_step {
+                TableCreationStep::EnteringTableName => {
+                    let content = format!(
+                        "Create New Table\n\nTable name: {}\n\nType the table name and press Enter to continue\nPress Esc to cancel",
+                        if self.state.new_table_name.is_empty() { 
+                            "_" 
+                        } else { 
+                            &self.state.new_table_name 
+                        }
+                    );
+                    (content, "Import Wizard - Table Name".to_string())
+                }
+                TableCreationStep::ChoosingSource => {
+                    let content = format!(
+                        "Create New Table: '{}'\n\nf = Import data from a file\nd = Design the columns\n\nPress Esc to cancel",
+                        self.state.new_table_name
+                    );
+                    (content, "Create Table - Source".to_string())
+                }
+                TableCreationStep::DesigningColumns => {
+                    (self.render_table_design(), "Table Designer - Columns".to_string())
+                }
+                TableCreationStep::PreviewingDdl => {
+                    let ddl = crate::db::design::create_table_sql(&self.state.new_table_name, &self.state.table_design)
+                        .unwrap_or_else(|e| format!("-- {e}"));
+                    let content = format!("{ddl}\n\nPress Enter to create the table, Esc to go back to the columns");
+                    (content, "Table Designer - DDL Preview".to_string())
+                }
+                TableCreationStep::SelectingFile => {
+                    let content = format!(
+                        "Create New Table: '{}'\n\nSelect a file to import data from:\n• CSV files (.csv)\n• JSON files (.json)\n• Parquet files (.parquet)\n\nPress Esc to cancel",
+                        self.state.new_table_name
+                    );
+                    (content, "Import Wizard - File Selection".to_string())
+                }
+                TableCreationStep::ImportingData => {
+                    let content = format!(
+                        "Create New Table: '{}'\n\nImporting data...\n\nPlease wait while the data is being imported.",
+                        self.state.new_table_name
+                    );
+                    (content, "Import Wizard - Importing".to_string())
+                }
             }
-            
-            // Create multiple rows if any cell has wrapped content
-            for line_idx in 0..max_lines {
-                let cells: Vec<Cell> = visible_cols.iter().enumerate().map(|(visible_idx, &data_col_idx)| {
-                    let line_text = cell_lines.get(visible_idx)
-                        .and_then(|lines| lines.get(line_idx))
-                        .unwrap_or(&String::new())
-                        .clone();
-                    
-                    // Map data column index to virtual column index
-                    let virtual_col_idx = visible_columns.get(data_col_idx).copied().unwrap_or(data_col_idx);
-                    
-                    // Check if this is the current cell (intersection of selected row and column)
-                    let is_current_cell = is_selected_row && Some(virtual_col_idx) == self.state.get_selected_column_index();
+        } else if let (Some(db), Some(table)) = (&self.state.selected_database, &self.state.selected_table) {
+            if let Some(ref data) = self.state.table_data {
+                // Render table using ratatui Table widget instead of string content
+                // Build table title with sort status
+                let sort_info = if !self.state.sort_columns.is_empty() {
+                    let mut sort_parts = Vec::new();
+                    for sort_spec in &self.state.sort_columns {
+                        let direction = match sort_spec.direction {
+                            crate::app::state::SortDirection::Ascending => "ASC",
+                            crate::app::state::SortDirection::Descending => "DESC",
+                        };
+                        sort_parts.push(format!("{} {}", sort_spec.column_name, direction));
+                    }
                     
-                    if is_current_cell {
-                        // Highlight current cell with light gray background and inverted text for readability
-                        Cell::from(line_text).style(Style::default().bg(Color::Gray).fg(Color::Black).add_modifier(Modifier::BOLD))
-                    } else if is_selected_row {
-                        // Bold selected row
-                        Cell::from(line_text).style(Style::default().add_modifier(Modifier::BOLD))
-                    } else if Some(virtual_col_idx) == self.state.get_selected_column_index() {
-                        // Subtle highlight for selected column
-                        Cell::from(line_text).style(Style::default().fg(Color::Gray))
+                    if !sort_parts.is_empty() {
+                        format!(" - Sorted by: {}", sort_parts.join(", "))
                     } else {
-                        Cell::from(line_text)
+                        String::new()
                     }
-                }).collect();
+                } else {
+                    String::new()
+                };
                 
-                rows.push(Row::new(cells));
+                let sample_info = self.state.get_sample_spec()
+                    .map(|sample| format!(" [SAMPLE {}]", sample.describe()))
+                    .unwrap_or_default();
+                
+                let diff_info = self.state.diff_results.get(table)
+                    .map(|diff| format!(" [{} vs {}: {}]", diff.left, diff.right, diff.summary.describe()))
+                    .unwrap_or_default();
+                
+                let transaction_info = self.state.transaction.as_ref()
+                    .filter(|_| self.state.is_staging_edits(Some(db.as_str())))
+                    .map(|transaction| if transaction.aborted {
+                        " [TX ABORTED: T to roll back]".to_string()
+                    } else {
+                        format!(" [TX: {} pending, T to review]", transaction.statements.len())
+                    })
+                    .unwrap_or_default();
+                
+                let relation_info = self.state.get_relation_breadcrumb()
+                    .map(|breadcrumb| format!(" [{breadcrumb}, Backspace to go back]"))
+                    .unwrap_or_default();
+                
+                let title = format!("Table: {} ({} rows){}{}{}{}{}", table, data.row_count, diff_info, sample_info, sort_info, transaction_info, relation_info);
+                let geometry = self.render_table_widget(f, area, data, &title, border_style);
+                self.mouse_regions.table_grid = Some(geometry);
+                return; // Early return since we handled rendering directly
+            } else {
+                let content = format!(
+                    "Database: {db}\nTable: {table}\n\nLoading table data...\n\nPress Enter on table to load data or wait for auto-load"
+                );
+                (content, "Table Viewer".to_string())
             }
-        }
-
-        let table = Table::new(rows, constraints)
-            .header(Row::new(header_cells).height(1))
-            .block(Block::default()
-                .title(title)
-                .borders(Borders::ALL)
-                .border_style(border_style))
-            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
-            .highlight_symbol(">>")
-            .column_spacing(1);
+        } else {
+            let debug_info = format!(
+                "Database: {:?}\nTable: {:?}", 
+                self.state.selected_database, 
+                self.state.selected_table
+            );
+            let content = format!(
+                "Select a database and table to view data\n\nNavigation:\n• Use Tab/Shift+Tab to switch panels\n• Use ↑↓ to navigate lists\n• Press Enter to select items\n• Press i to import data\n• Press h for help\n• Press q or Esc to quit\n\nDebug:\n{}", 
+                debug_info
+            );
+            (content, "Main Content [3]".to_string())
+        };
 
-        f.render_widget(table, area);
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(Color::White));
+
+        f.render_widget(paragraph, area);
     }
 
-    // Calculate how many complete data rows actually fit in the available height
-    // accounting for word wrapping in expanded columns
-    fn calculate_visible_data_rows(&self, area: Rect, data: &crate::db::query::QueryResult) -> usize {
-        let available_height = area.height.saturating_sub(3) as usize; // Subtract borders and header
-        let start_row = self.state.scroll_y;
-        
-        if data.rows.is_empty() {
-            return 0;
+    fn render_split_partner(&mut self, f: &mut Frame, area: Rect, partner: usize) {
+        self.mouse_regions.split_pane = Some(area);
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        self.state.sync_split_tab(visible_rows);
+
+        let sync_info = match self.state.get_split_sync_display() {
+            Some(sync) if self.state.split_key_missing => format!(" [{sync} - no matching row]"),
+            Some(sync) => format!(" [{sync}]"),
+            None => String::new(),
+        };
+
+        // Render the partner through the normal grid code by swapping its state in temporarily
+        self.state.swap_live_with_tab(partner);
+        if let (Some(table), Some(data)) = (&self.state.selected_table, &self.state.table_data) {
+            let database = self.state.selected_database.as_deref().unwrap_or("?");
+            let title = format!("Split: {database}.{table} ({} rows){sync_info}", data.row_count);
+            self.render_table_widget(f, area, data, &title, Style::default());
+        } else {
+            let paragraph = Paragraph::new("This tab has no data loaded yet")
+                .block(Block::default().title("Split").borders(Borders::ALL))
+                .style(Style::default().fg(Color::White));
+            f.render_widget(paragraph, area);
         }
+        self.state.swap_live_with_tab(partner);
+    }
 
-        // Calculate column widths (simplified version of what's in render_table_widget)
-        let min_col_width = 8;
-        let mut column_widths = Vec::new();
-        for (i, col_name) in data.columns.iter().enumerate() {
-            let final_header_text = self.get_final_header_text(i, col_name);
-            let header_width = final_header_text.chars().count();
-            
-            let col_width = if self.state.is_column_expanded(i) {
-                header_width.max(min_col_width).min(50)
-            } else {
-                header_width.max(min_col_width).min(25)
-            };
-            column_widths.push(col_width);
+    /// Load the columns, keys, indexes and statistics the inspect view shows for the selected table
+    fn load_inspect_data(&mut self) {
+        let Some(table_name) = self.state.selected_table.clone() else {
+            return;
+        };
+        let (columns, statistics) = match self.database_manager.get_current_connection() {
+            Some(connection) => (
+                self.get_table_schema(connection, &table_name).unwrap_or_else(|_| crate::db::query::QueryResult::new()),
+                self.get_table_statistics(connection, &table_name).unwrap_or_else(|_| crate::db::query::QueryResult::new()),
+            ),
+            None => (crate::db::query::QueryResult::new(), crate::db::query::QueryResult::new()),
+        };
+        let table_schema = self.load_table_schema(&table_name);
+        let storage_info = self.get_table_storage_info(&table_name);
+        self.state.inspect_data = Some(crate::app::state::InspectData { table_name, columns, table_schema, statistics, storage_info });
+    }
+
+    /// Recompute the inspect statistics after the sample changed
+    fn reload_inspect_statistics(&mut self) {
+        let Some(data) = self.state.inspect_data.as_ref() else {
+            return;
+        };
+        let statistics = self.database_manager.get_current_connection()
+            .and_then(|connection| self.get_table_statistics(connection, &data.table_name).ok())
+            .unwrap_or_else(crate::db::query::QueryResult::new);
+        if let Some(data) = self.state.inspect_data.as_mut() {
+            data.statistics = statistics;
         }
+    }
 
-        // Calculate visible columns (simplified)
-        let available_width = area.width.saturating_sub(2) as usize;
-        let start_col = self.state.scroll_x;
-        let mut visible_cols = Vec::new();
-        let mut used_width = 0;
+    fn render_inspect_view(&mut self, f: &mut Frame, area: Rect, table_name: &str) {
+        // Split area into two sections: schema on top, statistics on bottom
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(50), // Schema section
+                Constraint::Percentage(50), // Statistics section
+            ])
+            .split(area);
+
+        self.mouse_regions.inspect_schema = Some(chunks[0]);
+        self.mouse_regions.inspect_stats = Some(chunks[1]);
+
+        // Render both sections from the data loaded on entering inspect mode
+        let empty = crate::db::query::QueryResult::new();
+        let inspect_data = self.state.inspect_data.as_ref().filter(|data| data.table_name == table_name);
+        let schema_data = inspect_data.map_or(&empty, |data| &data.columns);
+        let table_schema = inspect_data.and_then(|data| data.table_schema.as_ref());
+        self.render_schema_section(f, chunks[0], table_name, schema_data, table_schema);
         
-        for i in start_col..data.columns.len() {
-            let col_width = column_widths.get(i).unwrap_or(&min_col_width);
-            if used_width + col_width <= available_width {
-                visible_cols.push(i);
-                used_width += col_width;
-            } else {
-                break;
-            }
-        }
+        // Render statistics section
+        let stats_data = inspect_data.map_or(&empty, |data| &data.statistics);
+        self.render_statistics_section(f, chunks[1], table_name, stats_data);
+    }
 
-        // Ensure we show at least one column
-        if visible_cols.is_empty() && start_col < data.columns.len() {
-            visible_cols.push(start_col);
+    /// Keys, constraints and indexes of a table or view (None for query results)
+    fn load_table_schema(&self, table_name: &str) -> Option<crate::db::TableSchema> {
+        if self.state.is_query_result(table_name) {
+            return None;
         }
+        let connection = self.database_manager.get_current_connection()?;
+        let table_info = self.get_current_table_infos().into_iter()
+            .find(|info| info.name == table_name)
+            .cloned()
+            .unwrap_or_else(|| crate::db::TableInfo::new(table_name.to_string()));
+        crate::db::TableSchema::load(connection, table_info).ok()
+    }
 
-        // Count how many complete data rows fit
-        let mut used_height = 0;
-        let mut visible_data_rows = 0;
+    fn render_schema_section(&self, f: &mut Frame, area: Rect, table_name: &str, schema_data: &crate::db::query::QueryResult, table_schema: Option<&crate::db::TableSchema>) {
+        // Determine if this section is active and style accordingly
+        let is_active = matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema);
+        let border_style = if is_active {
+            Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
         
-        for row_idx in start_row..data.rows.len() {
-            if row_idx >= data.rows.len() {
-                break;
-            }
-            
-            let row = &data.rows[row_idx];
-            
-            // Calculate max lines for this row (same logic as render_table_widget)
-            let mut max_lines = 1;
-            for &col_idx in &visible_cols {
-                let empty_string = String::new();
-                let cell_content = row.get(col_idx).unwrap_or(&empty_string);
-                let col_width = column_widths.get(col_idx).unwrap_or(&min_col_width);
-                
-                let lines = if self.state.is_column_expanded(col_idx) {
-                    wrap_text(cell_content, *col_width)
-                } else {
-                    vec![truncate_text(cell_content, *col_width)]
-                };
-                
-                max_lines = max_lines.max(lines.len());
-            }
-            
-            // Check if this row fits in remaining height
-            if used_height + max_lines <= available_height {
-                used_height += max_lines;
-                visible_data_rows += 1;
+        // Create title with active indicator
+        let title = if is_active {
+            if self.state.is_modifying {
+                format!("► Columns: {} (MODIFY MODE - j/k to move, J/K for extremes, o to hide/show, Enter to confirm, Esc to cancel)", table_name)
             } else {
-                break;
+                format!("► Columns: {} (Tab to switch, j/k to scroll, m to modify, a to alter)", table_name)
             }
-        }
-        
-        visible_data_rows.max(1) // Always return at least 1
-    }
+        } else {
+            format!("Columns: {}", table_name)
+        };
 
-    // Simplified version that only needs cached height (for navigation)
-    fn calculate_visible_data_rows_from_height(&self, area_height: u16, data: &crate::db::query::QueryResult) -> usize {
-        let available_height = area_height.saturating_sub(3) as usize; // Subtract borders and header
-        
-        if data.rows.is_empty() {
-            return 0;
-        }
-        
-        // If no expanded columns, each row is height 1
-        if self.state.expanded_columns.is_empty() {
-            return available_height.max(1);
+        if schema_data.rows.is_empty() {
+            // No schema data available
+            let content = "No column information available for this table.";
+            let paragraph = Paragraph::new(content)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(border_style),
+                )
+                .wrap(Wrap { trim: true })
+                .style(Style::default().fg(Color::White));
+            f.render_widget(paragraph, area);
+            return;
         }
-        
-        // Calculate column widths similar to render_table_widget logic
-        let min_col_width = 8;
-        let mut column_widths = Vec::new();
-        for (i, col_name) in data.columns.iter().enumerate() {
-            let final_header_text = self.get_final_header_text(i, col_name);
-            let header_width = final_header_text.chars().count();
-            
-            let col_width = if self.state.is_column_expanded(i) {
-                header_width.max(min_col_width).min(50)
-            } else {
-                header_width.max(min_col_width).min(25)
-            };
-            column_widths.push(col_width);
+
+        // The table comment, foreign keys, table constraints and indexes are listed under the columns
+        let mut details = Vec::new();
+        if let Some(table_schema) = table_schema {
+            details.extend(table_schema.table_info.comment.as_ref().map(|comment| format!("COMMENT {comment}")));
+            details.extend(table_schema.foreign_keys.iter().map(|foreign_key| format!("FOREIGN KEY {}", foreign_key.describe())));
+            details.extend(table_schema.constraints.iter().cloned());
+            details.extend(table_schema.indexes.iter().map(|index| {
+                index.sql.clone().unwrap_or_else(|| format!("INDEX {} ON {}", index.name, index.expressions))
+            }));
         }
+        let area = if details.is_empty() {
+            area
+        } else {
+            let height = (details.len() as u16 + 2).min(area.height / 2);
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Length(height)])
+                .split(area);
+            let paragraph = Paragraph::new(details.join("\n"))
+                .block(
+                    Block::default()
+                        .title("Keys, Constraints & Indexes")
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::DarkGray)),
+                )
+                .style(Style::default().fg(Color::White));
+            f.render_widget(paragraph, parts[1]);
+            parts[0]
+        };
 
-        // Calculate visible columns (simplified, assume all columns fit for navigation)
-        let available_width = 100; // Reasonable assumption for navigation calculations
-        let start_col = self.state.scroll_x;
-        let mut visible_cols = Vec::new();
-        let mut used_width = 0;
+        // Build table rows from schema data, respecting virtual column order
+        let virtual_order = self.state.get_virtual_column_order();
+        let original_columns = self.state.get_original_column_names();
         
-        for i in start_col..data.columns.len() {
-            let col_width = column_widths.get(i).unwrap_or(&min_col_width);
-            if used_width + col_width <= available_width {
-                visible_cols.push(i);
-                used_width += col_width;
-            } else {
-                break;
+        // Reorder schema data to match virtual column order
+        let mut ordered_schema_rows = Vec::new();
+        for virtual_col_name in &virtual_order {
+            // Find the index of this column in the original schema
+            if let Some(original_idx) = original_columns.iter().position(|name| name == virtual_col_name) {
+                if original_idx < schema_data.rows.len() {
+                    ordered_schema_rows.push(schema_data.rows[original_idx].clone());
+                }
             }
         }
         
-        // Ensure we show at least one column
-        if visible_cols.is_empty() && start_col < data.columns.len() {
-            visible_cols.push(start_col);
-        }
-
-        // Count how many complete data rows fit (same logic as calculate_visible_data_rows)
-        let mut used_height = 0;
-        let mut visible_data_rows = 0;
-        let start_row = self.state.scroll_y;
+        // Create header in virtual order (add extra columns for enhanced display)
+        let header = vec![
+            Cell::from("Column Name"),
+            Cell::from("Data Type"),
+            Cell::from("Nullable"),
+            Cell::from("Keys"),
+            Cell::from("Sort Order"),
+            Cell::from("Sort Direction"),
+            Cell::from("Hidden"),
+            Cell::from("Comment"),
+        ];
         
-        for row_idx in start_row..data.rows.len() {
-            if row_idx >= data.rows.len() {
-                break;
-            }
-            
-            let row = &data.rows[row_idx];
-            
-            // Calculate max lines for this row
-            let mut max_lines = 1;
-            for &col_idx in &visible_cols {
-                let empty_string = String::new();
-                let cell_content = row.get(col_idx).unwrap_or(&empty_string);
-                let col_width = column_widths.get(col_idx).unwrap_or(&min_col_width);
+        // Apply scrolling: skip rows based on scroll position
+        let scroll_offset = self.state.inspect_schema_scroll_y;
+        let rows: Vec<Row> = ordered_schema_rows.iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .map(|(row_idx, row)| {
+                // Get the virtual column index
+                let virtual_col_idx = scroll_offset + row_idx;
+                
+                // Get the column name from virtual order
+                let column_name = if virtual_col_idx < virtual_order.len() {
+                    &virtual_order[virtual_col_idx]
+                } else {
+                    return Row::new(vec![Cell::from("ERROR")]);
+                };
+                
+                // Check if this column is hidden by name
+                let is_hidden = self.state.is_column_hidden_by_name(column_name);
+                
+                // Get sort information
+                let sort_info = self.state.sort_columns.iter()
+                    .position(|spec| spec.column_name == *column_name)
+                    .map(|pos| (pos + 1, &self.state.sort_columns[pos].direction));
+                
+                let (sort_order, sort_direction) = match sort_info {
+                    Some((order, direction)) => {
+                        let dir_str = match direction {
+                            crate::app::state::SortDirection::Ascending => "ASC",
+                            crate::app::state::SortDirection::Descending => "DESC",
+                        };
+                        (order.to_string(), dir_str.to_string())
+                    },
+                    None => ("".to_string(), "".to_string()),
+                };
                 
-                let lines = if self.state.is_column_expanded(col_idx) {
-                    wrap_text(cell_content, *col_width)
+                let column_schema = table_schema.and_then(|schema| schema.column(column_name));
+                let badges = column_schema
+                    .map(|column| column.get_badges())
+                    .unwrap_or_default();
+                let comment = column_schema
+                    .and_then(|column| column.comment.clone())
+                    .unwrap_or_default();
+                
+                // Build enhanced row with additional columns
+                let base_style = if is_hidden {
+                    Style::default().fg(Color::DarkGray)
                 } else {
-                    vec![truncate_text(cell_content, *col_width)]
+                    Style::default()
                 };
                 
-                max_lines = max_lines.max(lines.len());
-            }
-            
-            // Check if this row fits in remaining height
-            if used_height + max_lines <= available_height {
-                used_height += max_lines;
-                visible_data_rows += 1;
+                let cells = vec![
+                    Cell::from(row.first().unwrap_or(&"?".to_string()).clone()).style(base_style),  // Column Name
+                    Cell::from(row.get(1).unwrap_or(&"?".to_string()).clone()).style(base_style),  // Data Type
+                    Cell::from(row.get(2).unwrap_or(&"?".to_string()).clone()).style(base_style),  // Nullable
+                    Cell::from(badges).style(if is_hidden { base_style } else { Style::default().fg(Color::Yellow) }), // Keys
+                    Cell::from(sort_order.clone()).style(base_style),                              // Sort Order
+                    Cell::from(sort_direction.clone()).style(base_style),                          // Sort Direction
+                    Cell::from(if is_hidden { "YES" } else { "NO" }).style(base_style),            // Hidden
+                    Cell::from(comment).style(if is_hidden { base_style } else { Style::default().fg(Color::Gray) }), // Comment
+                ];
+                
+                Row::new(cells)
+            }).collect();
+
+        // Use fixed column widths for the enhanced display (8 columns, the comment takes the rest)
+        let constraints: Vec<Constraint> = vec![
+            Constraint::Length(15),  // Column Name
+            Constraint::Length(12),  // Data Type
+            Constraint::Length(8),   // Nullable
+            Constraint::Length(20),  // Keys
+            Constraint::Length(10),  // Sort Order
+            Constraint::Length(12),  // Sort Direction
+            Constraint::Length(6),   // Hidden
+            Constraint::Min(10),     // Comment
+        ];
+
+        let table = Table::new(rows, constraints)
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .highlight_style(if is_active {
+                Style::default().bg(Color::Gray).fg(Color::Black).add_modifier(Modifier::BOLD)
             } else {
-                break;
-            }
+                Style::default().bg(Color::DarkGray)
+            })
+            .style(Style::default().fg(Color::White));
+
+        // Create table state for selection
+        let mut table_state = TableState::default();
+        if is_active {
+            // Show selection only when this section is active
+            let selected_index = self.state.inspect_selected_row.saturating_sub(scroll_offset);
+            table_state.select(Some(selected_index));
         }
-        
-        visible_data_rows.max(1) // Always return at least 1
+
+        f.render_stateful_widget(table, area, &mut table_state);
     }
 
-    // Helper method to generate the complete header text for a column including all indicators
-    // This ensures width calculations match the actual rendered text
-    fn get_final_header_text(&self, _column_index: usize, column_name: &str) -> String {
-        let mut header_text = column_name.to_string();
-        
-        // Add sort indicator if this column is in the sort chain
-        if let Some((position, sort_spec)) = self.state.sort_columns
-            .iter()
-            .enumerate()
-            .find(|(_, spec)| spec.column_name == column_name) {
-            
-            let sort_indicator = match sort_spec.direction {
-                crate::app::state::SortDirection::Ascending => "↑",
-                crate::app::state::SortDirection::Descending => "↓",
-            };
-            
-            // Show order number for multi-column sorts (1-indexed for user readability)
-            if self.state.sort_columns.len() > 1 {
-                header_text = format!("{} {}^{}", header_text, sort_indicator, position + 1);
-            } else {
-                header_text = format!("{header_text} {sort_indicator}");
-            }
-        }
-        
-        // Add filter indicator if this column is filtered
-        if self.state.is_column_filtered(column_name) {
-            header_text = format!("{header_text} *");
+    /// Row estimate, size and compression of a base table, e.g. "~300.0K rows, 512.0 KB, BitPacking, Dictionary"
+    fn get_table_storage_info(&mut self, table_name: &str) -> Option<String> {
+        if self.state.is_query_result(table_name) {
+            return None;
         }
-        
-        header_text
+        // Loading the storage also fills in the size used by the summary
+        let compression = self.database_manager.load_table_storage(table_name).ok()
+            .map(|storage| storage.compression_summary())
+            .filter(|compression| !compression.is_empty());
+        let summary = self.get_current_table_infos().into_iter()
+            .find(|info| info.name == table_name)?
+            .get_storage_summary()?;
+        Some(match compression {
+            Some(compression) => format!("{summary}, {compression}"),
+            None => summary,
+        })
     }
 
-    fn fetch_table_data(&mut self) {
-        if let (Some(_db), Some(table)) = (self.state.selected_database.clone(), self.state.selected_table.clone()) {
-            if let Some(connection) = self.database_manager.get_current_connection() {
-                // First get column names to enable sorting and virtual ordering
-                let column_names = match self.get_table_column_names(connection, &table) {
-                    Ok(names) => names,
-                    Err(e) => {
-                        self.state.show_error(format!("Failed to get column names: {e}"));
-                        return;
-                    }
-                };
+    fn render_statistics_section(&self, f: &mut Frame, area: Rect, table_name: &str, stats_data: &crate::db::query::QueryResult) {
+        // Determine if this section is active and style accordingly
+        let is_active = matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Statistics);
+        let border_style = if is_active {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        
+        // Create title with active indicator
+        let sample_info = self.state.get_sample_spec()
+            .map(|sample| format!(" [APPROXIMATE - SAMPLE {}]", sample.describe()))
+            .unwrap_or_default();
+        let storage_info = self.state.inspect_data.as_ref()
+            .and_then(|data| data.storage_info.as_ref())
+            .map(|info| format!(" [{info}]"))
+            .unwrap_or_default();
+        let title = if is_active {
+            format!("► Statistics: {table_name}{storage_info}{sample_info} (Tab to switch, ↑↓ to scroll, Esc to exit)")
+        } else {
+            format!("Statistics: {table_name}{storage_info}{sample_info} (Press Esc to exit inspect mode)")
+        };
 
-                // Initialize column order if needed
-                self.state.initialize_column_order(column_names.clone());
-                
-                // Get visible columns in virtual order
-                let visible_column_names = self.state.get_visible_column_names();
-                
-                // Use visible columns directly (they are already in virtual order)
-                let ordered_columns = visible_column_names;
+        if stats_data.rows.is_empty() {
+            // No statistics data available
+            let content = "No statistics available for this table.";
+            let paragraph = Paragraph::new(content)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(border_style),
+                )
+                .wrap(Wrap { trim: true })
+                .style(Style::default().fg(Color::White));
+            f.render_widget(paragraph, area);
+            return;
+        }
 
-                // Build base SQL query with virtual column ordering
-                let columns_sql = ordered_columns.join(", ");
-                let mut sql = format!("SELECT {columns_sql} FROM {table}");
-                
-                // Add sorting if active (use original column names for sorting)
-                if let Some(sort_clause) = self.state.get_sort_sql_clause(&column_names) {
-                    sql.push(' ');
-                    sql.push_str(&sort_clause);
-                }
-                
-                // Add limit
-                sql.push_str(" LIMIT 1000");
+        // Build table rows from statistics data
+        let header = stats_data.columns.iter().map(|col| Cell::from(col.as_str())).collect::<Vec<_>>();
+        
+        // Apply scrolling: skip rows based on scroll position
+        let scroll_offset = self.state.inspect_stats_scroll_y;
+        let rows: Vec<Row> = stats_data.rows.iter()
+            .skip(scroll_offset)
+            .map(|row| {
+                let cells: Vec<Cell> = row.iter().map(|cell| Cell::from(cell.as_str())).collect();
+                Row::new(cells)
+            }).collect();
 
-                match self.execute_query_direct(connection, &sql) {
-                    Ok(data) => {
-                        self.state.set_table_data(data);
-                    }
-                    Err(e) => {
-                        self.state.show_error(format!("Failed to load table data: {e}"));
-                    }
+        // Calculate column widths based on content
+        let mut column_widths = vec![0; stats_data.columns.len()];
+        
+        // Check header widths
+        for (i, col) in stats_data.columns.iter().enumerate() {
+            column_widths[i] = col.len().max(column_widths[i]);
+        }
+        
+        // Check data widths
+        for row in &stats_data.rows {
+            for (i, cell) in row.iter().enumerate() {
+                if i < column_widths.len() {
+                    column_widths[i] = cell.len().max(column_widths[i]);
                 }
-            } else {
-                self.state.show_error("No database connection available".to_string());
             }
-        } else {
-            self.state.show_error("No table selected for data loading".to_string());
         }
+
+        // Convert to constraints with minimum and maximum widths
+        let constraints: Vec<Constraint> = column_widths.iter().map(|&width| {
+            let min_width = 8; // Minimum column width
+            let max_width = 20; // Slightly smaller max for statistics to fit more columns
+            let adjusted_width = width.max(min_width).min(max_width);
+            Constraint::Length(adjusted_width as u16)
+        }).collect();
+
+        let table = Table::new(rows, constraints)
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .style(Style::default().fg(Color::White));
+
+        f.render_widget(table, area);
     }
 
-    fn fetch_table_data_preserve_column(&mut self) {
-        self.fetch_table_data_preserve_column_with_limit(true);
+    fn calculate_inspect_scroll_bounds(&self) -> (usize, usize) {
+        // Calculate scrolling bounds for inspect mode
+        // Each section gets 50% of the main content area height
+        let section_height = (self.state.last_table_area_height / 2).saturating_sub(3) as usize; // Subtract for borders
+        let visible_rows = section_height.max(5); // Minimum 5 visible rows
+        
+        // Calculate max rows based on active section
+        let max_rows = match self.state.inspect_active_section {
+            crate::app::state::InspectSection::Schema => {
+                // For schema, estimate based on typical column count
+                // Most tables have 3-20 columns, so max_rows would be that count
+                20 // Conservative estimate - in practice this will be determined by actual data
+            }
+            crate::app::state::InspectSection::Statistics => {
+                // For statistics, DuckDB SUMMARIZE typically returns one row per column
+                // So this is similar to schema but may be longer with statistics per column
+                50 // Conservative estimate for statistics rows
+            }
+        };
+        
+        (max_rows, visible_rows)
     }
 
-    fn fetch_table_data_preserve_column_with_limit(&mut self, include_limit: bool) {
-        if let (Some(_db), Some(table)) = (self.state.selected_database.clone(), self.state.selected_table.clone()) {
-            if let Some(connection) = self.database_manager.get_current_connection() {
-                // First get column names to enable sorting, filtering, and virtual ordering
-                let column_names = match self.get_table_column_names(connection, &table) {
-                    Ok(names) => names,
-                    Err(e) => {
-                        self.state.show_error(format!("Failed to get column names: {e}"));
-                        return;
-                    }
-                };
+    fn render_status_bar(&self, f: &mut Frame, area: Rect) {
+        if self.state.is_searching {
+            // Search mode: use entire status bar for search input
+            self.render_search_input(f, area);
+        } else {
+            // Normal mode: split status bar into left and right sections
+            let status_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(60), // Left section - general status
+                    Constraint::Percentage(40), // Right section - table viewer status
+                ])
+                .split(area);
 
-                // Initialize column order if needed
-                self.state.initialize_column_order(column_names.clone());
-                
-                // Get visible columns in virtual order
-                let visible_column_names = self.state.get_visible_column_names();
-                
-                // Use visible columns directly (they are already in virtual order)
-                let ordered_columns = visible_column_names;
+            // Render left status section (general status)
+            self.render_left_status(f, status_chunks[0]);
+            
+            // Render right status section (table viewer status)
+            self.render_table_status(f, status_chunks[1]);
+        }
+    }
 
-                // Build base SQL query with virtual column ordering
-                let columns_sql = ordered_columns.join(", ");
-                let mut sql = format!("SELECT {columns_sql} FROM {table}");
-                
-                // Add filtering if active (use original column names for filtering)
-                if let Some(filter_clause) = self.state.get_filter_sql_clause(&column_names) {
-                    sql.push(' ');
-                    sql.push_str(&filter_clause);
-                }
-                
-                // Add sorting if active (use original column names for sorting)
-                if let Some(sort_clause) = self.state.get_sort_sql_clause(&column_names) {
-                    sql.push(' ');
-                    sql.push_str(&sort_clause);
-                }
-                
-                // Add limit conditionally
-                if include_limit {
-                    sql.push_str(" LIMIT 1000");
-                }
+    fn render_search_input(&self, f: &mut Frame, area: Rect) {
+        // Determine border color based on syntax validity
+        let border_color = if self.state.search_syntax_valid {
+            Color::Green // Green border for valid syntax
+        } else {
+            Color::Red // Red border for invalid syntax
+        };
 
-                match self.execute_query_direct(connection, &sql) {
-                    Ok(data) => {
-                        self.state.update_table_data_preserve_column(data);
-                    }
-                    Err(e) => {
-                        self.state.show_error(format!("Failed to load table data: {e}"));
-                    }
-                }
+        // Get column name for context
+        let column_context = if let (Some(column_index), Some(ref data)) = (self.state.search_column, &self.state.table_data) {
+            if column_index < data.columns.len() {
+                format!(" [{}]", data.columns[column_index])
             } else {
-                self.state.show_error("No database connection available".to_string());
+                String::new()
             }
         } else {
-            self.state.show_error("No table selected for data loading".to_string());
-        }
-    }
+            String::new()
+        };
 
-    fn get_table_column_names(&self, connection: &duckdb::Connection, table_name: &str) -> anyhow::Result<Vec<String>> {
-        let sql = format!("SELECT * FROM {table_name} LIMIT 0");
-        let mut stmt = connection.prepare(&sql)?;
-        let _rows = stmt.query([])?;
-        
-        let column_count = stmt.column_count();
-        let mut column_names = Vec::new();
-        
-        for i in 0..column_count {
-            let column_name = stmt.column_name(i)
-                .unwrap_or(&format!("column_{i}"))
-                .to_string();
-            column_names.push(column_name);
-        }
+        // Create search input display with cursor
+        let search_display = format!("Filter{}: {}_", column_context, self.state.search_text);
         
-        Ok(column_names)
-    }
+        // Calculate available width (subtract borders and padding)
+        let available_width = area.width.saturating_sub(4) as usize; // 2 for borders + 2 for padding
+        let display_text = truncate_text(&search_display, available_width);
 
-    fn get_table_schema(&self, connection: &duckdb::Connection, table_name: &str) -> anyhow::Result<crate::db::query::QueryResult> {
-        let sql = format!("DESCRIBE {table_name}");
-        self.execute_query_direct(connection, &sql)
-    }
+        let search_input = Paragraph::new(display_text)
+            .block(
+                Block::default()
+                    .title("Search/Filter")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border_color)),
+            )
+            .style(Style::default().fg(Color::White));
 
-    fn get_table_statistics(&self, connection: &duckdb::Connection, table_name: &str) -> anyhow::Result<crate::db::query::QueryResult> {
-        let sql = format!("SUMMARIZE {table_name}");
-        self.execute_query_direct(connection, &sql)
+        f.render_widget(search_input, area);
     }
 
-    fn execute_query_direct(&self, connection: &duckdb::Connection, sql: &str) -> anyhow::Result<crate::db::query::QueryResult> {
-        let start_time = std::time::Instant::now();
-        
-        // Prepare statement and execute query
-        let mut stmt = connection.prepare(sql)?;
-        let mut rows = stmt.query([])?;
-        
-        // Get column count from the rows result
-        let column_count = rows.as_ref().unwrap().column_count();
-        
-        // Get column names from the statement
-        let mut columns = Vec::new();
-        for i in 0..column_count {
-            let column_name = rows.as_ref().unwrap().column_name(i)
-                .unwrap_or(&format!("column_{i}"))
-                .to_string();
-            columns.push(column_name);
-        }
-        
-        // Collect all rows
-        let mut result_rows = Vec::new();
-        while let Some(row) = rows.next()? {
-            let mut row_data = Vec::new();
-            for i in 0..column_count {
-                // Convert each column value to string with safer error handling
-                // Try f64 first to handle NaN values, then other types
-                let value = match row.get::<_, f64>(i) {
-                    Ok(v) => {
-                        if v.is_nan() {
-                            "NaN".to_string()
-                        } else if v.is_infinite() {
-                            if v.is_sign_positive() { "Infinity".to_string() } else { "-Infinity".to_string() }
-                        } else {
-                            v.to_string()
-                        }
-                    },
-                    Err(_) => match row.get::<_, String>(i) {
-                        Ok(v) => v,
-                        Err(_) => match row.get::<_, i64>(i) {
-                            Ok(v) => v.to_string(),
-                            Err(_) => match row.get::<_, bool>(i) {
-                                Ok(v) => v.to_string(),
-                                Err(_) => {
-                                    // Try to get as raw value or default to NULL
-                                    match row.get_ref(i) {
-                                        Ok(value_ref) => format!("{value_ref:?}"),
-                                        Err(_) => "NULL".to_string(),
-                                    }
-                                }
-                            }
-                        }
+    fn check_search_debounce(&mut self) {
+        if self.state.is_searching && self.state.search_syntax_valid && self.state.should_debounce_update() {
+            // Reset debounce timer to prevent multiple updates
+            self.state.search_debounce_timer = None;
+            
+            // Trigger live update if search text is not empty
+            if !self.state.search_text.trim().is_empty() {
+                // Create a temporary filter for live preview
+                if let Some(column_index) = self.state.search_column {
+                    // Store current filters state
+                    let saved_filters = self.state.column_filters.clone();
+                    
+                    // Temporarily add the search text as a filter
+                    if let Some(column_name) = self.state.get_column_name_by_index(column_index) {
+                        self.state.column_filters.insert(column_name, self.state.search_text.trim().to_string());
                     }
-                };
-                row_data.push(value);
+                    
+                    // Update the view with limit for performance during preview
+                    self.fetch_table_data_preserve_column_with_limit(true);
+                    
+                    // Restore the saved filters (don't persist the preview)
+                    self.state.column_filters = saved_filters;
+                }
             }
-            result_rows.push(row_data);
         }
-        
-        let execution_time = start_time.elapsed();
-        
-        Ok(crate::db::query::QueryResult {
-            columns,
-            row_count: result_rows.len(),
-            rows: result_rows,
-            execution_time_ms: execution_time.as_millis() as u64,
-        })
     }
 
-    fn handle_enter(&mut self) {
-        // Handle modifying mode confirmation first
-        if self.state.is_modifying {
-            self.state.confirm_modifying();
-            // No popup - will show in status bar instead
+    fn validate_search_syntax(&mut self) {
+        if !self.state.is_searching {
             return;
         }
-        
-        match self.state.active_panel {
-            NavigationPanel::DatabaseList => {
-                if self.state.database_dropdown_expanded {
-                    // Select database from dropdown
-                    let db_name = {
-                        let databases = self.database_manager.get_databases();
-                        databases
-                            .get(self.state.dropdown_selected_index)
-                            .map(|db| db.name.clone())
-                    };
 
-                    if let Some(db_name) = db_name {
-                        let mut workflows = DatabaseWorkflows::new(
-                            &mut self.database_manager,
-                            &mut self.action_logger,
-                            &mut self.state,
-                        );
-                        let _ = workflows.select_database(db_name);
-                        self.sync_selected_db_index(); // Sync the index after selection
-                        self.selected_table_index = 0; // Reset table selection
+        // If search text is empty, consider it valid
+        if self.state.search_text.trim().is_empty() {
+            self.state.search_syntax_valid = true;
+            return;
+        }
+
+        // Try to validate by building a test query
+        if let (Some(column_index), Some(ref table), Some(ref data)) = 
+            (self.state.search_column, &self.state.selected_table, &self.state.table_data) {
+            
+            if column_index < data.columns.len() {
+                let column_name = &data.columns[column_index];
+                let test_sql = format!(
+                    "SELECT COUNT(*) FROM {} WHERE {} {}",
+                    self.state.get_source_sql(table), column_name, self.state.search_text.trim()
+                );
+
+                // Try to prepare the statement to validate syntax
+                if let Some(connection) = self.database_manager.get_current_connection() {
+                    match connection.prepare(&test_sql) {
+                        Ok(_) => {
+                            self.state.search_syntax_valid = true;
+                        }
+                        Err(_) => {
+                            self.state.search_syntax_valid = false;
+                        }
                     }
-                    
-                    // Close dropdown after selection
-                    self.state.collapse_database_dropdown();
                 } else {
-                    // Expand dropdown if not already expanded
-                    let databases = self.database_manager.get_databases();
-                    self.state.expand_database_dropdown(databases.len());
-                    self.state.set_dropdown_to_current_database(self.selected_db_index);
-                }
-            }
-            NavigationPanel::TableList => {
-                let current_tables = self.get_current_tables();
-                if let Some(table_name) = current_tables.get(self.selected_table_index) {
-                    let mut workflows = DatabaseWorkflows::new(
-                        &mut self.database_manager,
-                        &mut self.action_logger,
-                        &mut self.state,
-                    );
-                    let _ = workflows.select_table(table_name.clone());
-                    self.sync_selected_table_index();
-                    self.fetch_table_data();
-                    // Automatically activate table viewer after selecting table
-                    self.state.set_active_panel(NavigationPanel::MainContent);
-                }
-            }
-            NavigationPanel::MainContent => {
-                // Toggle column expansion when viewing table data (but not in modifying mode)
-                if self.state.table_data.is_some() && !self.state.is_modifying {
-                    self.state.toggle_column_expansion();
+                    // No connection available, assume valid for now
+                    self.state.search_syntax_valid = true;
                 }
             }
-            _ => {}
         }
     }
 
-    fn start_table_creation(&mut self) {
-        if self.database_manager.get_current_database().is_some() {
-            self.state.start_table_creation();
-        } else {
-            self.state.show_error("Please connect to a database first".to_string());
-        }
-    }
+    fn render_left_status(&self, f: &mut Frame, area: Rect) {
+        let border_style = self.get_panel_border_style(NavigationPanel::StatusBar);
 
-    pub fn update_notifications(&mut self) {
-        self.state.remove_expired_notifications();
-        self.state.update_flash_timer();
-        self.check_search_debounce();
+        // Calculate available width (subtract borders and padding)
+        let available_width = area.width.saturating_sub(4) as usize; // 2 for borders + 2 for padding
+        let status_text = truncate_text(&self.state.get_status_display(), available_width);
+
+        let status = Paragraph::new(status_text)
+            .block(
+                Block::default()
+                    .title("Status")
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .style(Style::default().fg(Color::Cyan))
+            .wrap(Wrap { trim: false }); // Disable wrapping since we're truncating
+
+        f.render_widget(status, area);
     }
 
-    fn show_help(&mut self) {
-        self.state.show_info("Keys: Tab=Navigate | hjkl=Select | m=Reorder Mode | Enter=Confirm | i=Import/Inspect | o=Open | n=New | s=Save | d=Delete | ?=Help | q=Quit".to_string());
+    fn render_table_status(&self, f: &mut Frame, area: Rect) {
+        let border_style = self.get_panel_border_style(NavigationPanel::MainContent);
+
+        // Generate table-specific status info
+        let table_status = if let Some(ref data) = self.state.table_data {
+            format!(
+                "{}Row: {} of {} | Col: {} of {} | ←→↑↓ navigate",
+                if self.state.get_sample_spec().is_some() { "SAMPLED | " } else { "" },
+                self.state.selected_row + 1,
+                data.rows.len(),
+                self.state.get_selected_column_index().map(|i| i + 1).unwrap_or(1),
+                data.columns.len()
+            )
+        } else {
+            "No table data".to_string()
+        };
+
+        // Calculate available width (subtract borders and padding)
+        let available_width = area.width.saturating_sub(4) as usize; // 2 for borders + 2 for padding
+        let truncated_status = truncate_text(&table_status, available_width);
+
+        let status = Paragraph::new(truncated_status)
+            .block(
+                Block::default()
+                    .title("Table Info")
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: false }); // Disable wrapping since we're truncating
+
+        f.render_widget(status, area);
     }
 
-    fn open_file_browser(&mut self) {
-        match FileBrowser::new() {
-            Ok(browser) => {
-                self.file_browser = Some(browser);
-                self.show_file_browser = true;
-                self.state.show_info("File browser opened".to_string());
-            }
-            Err(e) => {
-                self.state.show_error(format!("Error opening file browser: {e}"));
-            }
+    fn render_notifications(&self, f: &mut Frame, area: Rect) {
+        if self.state.notifications.is_empty() {
+            return;
         }
-    }
 
-    fn create_database_with_name(&mut self) {
-        let db_name = self.state.new_database_name.clone();
-        
-        // Create in-memory database with custom name
-        if let Err(e) = self.database_manager.add_database(db_name.clone(), ":memory:".to_string()) {
-            self.state.show_error(format!("Failed to create database: {e}"));
-        } else {
-            let mut workflows = DatabaseWorkflows::new(
-                &mut self.database_manager,
-                &mut self.action_logger,
-                &mut self.state,
-            );
-            let _ = workflows.select_database(db_name.clone());
-            self.sync_selected_db_index();
-            self.selected_table_index = 0;
-            self.state.show_success(format!("Created database '{db_name}'"));
+        // Calculate notification area (centered, 60% width, near top)
+        let notification_width = (area.width as f32 * 0.6) as u16;
+        let notification_height = 3;
+        let x = (area.width.saturating_sub(notification_width)) / 2;
+        let y = 5; // Position near top
+
+        // Only show the most recent notification to avoid overlap
+        if let Some(notification) = self.state.notifications.last() {
+            let notification_area = Rect {
+                x,
+                y,
+                width: notification_width,
+                height: notification_height,
+            };
+
+            // Choose colors based on notification type - simple black background with colored borders
+            let (border_color, icon) = match notification.notification_type {
+                crate::app::state::NotificationType::Success => (Color::Green, "✅"),
+                crate::app::state::NotificationType::Error => (Color::Red, "❌"),
+                crate::app::state::NotificationType::Info => (Color::Blue, "ℹ️"),
+            };
+
+            // Truncate notification text to fit
+            let available_width = notification_width.saturating_sub(6) as usize; // Account for borders + icon
+            let display_text = format!("{} {}", icon, truncate_text(&notification.message, available_width));
+
+            let notification_widget = Paragraph::new(display_text)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default()
+                            .fg(border_color)
+                            .add_modifier(Modifier::BOLD))
+                )
+                .style(Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Black)
+                    .add_modifier(Modifier::BOLD))
+                .alignment(Alignment::Center);
+
+            // Render the notification with opaque black background
+            f.render_widget(notification_widget, notification_area);
         }
     }
 
-    fn start_delete_confirmation(&mut self) {
-        match self.state.active_panel {
-            NavigationPanel::DatabaseList => {
-                let databases = self.database_manager.get_databases();
-                // Use dropdown index if dropdown is expanded, otherwise use selected index
-                let index = if self.state.database_dropdown_expanded {
-                    self.state.dropdown_selected_index
-                } else {
-                    self.selected_db_index
-                };
-                if let Some(db) = databases.get(index) {
-                    self.state.start_database_delete_confirmation(db.name.clone());
-                }
-            }
-            NavigationPanel::TableList => {
-                let current_tables = self.get_current_tables();
-                if let Some(table) = current_tables.get(self.selected_table_index) {
-                    self.state.start_table_delete_confirmation(table.clone());
-                }
+    fn render_database_name_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 50;
+        let popup_height = 5;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let content = format!(
+            "Create New Database\n\nName: {}\n\nPress Enter to create, Esc to cancel",
+            if self.state.new_database_name.is_empty() {
+                "_"
+            } else {
+                &self.state.new_database_name
             }
-            _ => {}
-        }
+        );
+
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Database Name")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+
+        f.render_widget(popup, popup_area);
     }
 
-    fn immediate_delete(&mut self) {
-        match self.state.active_panel {
-            NavigationPanel::DatabaseList => {
-                let db_name = {
-                    let databases = self.database_manager.get_databases();
-                    // Use dropdown index if dropdown is expanded, otherwise use selected index
-                    let index = if self.state.database_dropdown_expanded {
-                        self.state.dropdown_selected_index
-                    } else {
-                        self.selected_db_index
-                    };
-                    databases.get(index).map(|db| db.name.clone())
-                };
-                if let Some(name) = db_name {
-                    self.delete_database(&name);
-                }
-            }
-            NavigationPanel::TableList => {
-                let table_name = {
-                    let current_tables = self.get_current_tables();
-                    current_tables.get(self.selected_table_index).cloned()
-                };
-                if let Some(name) = table_name {
-                    self.delete_table(&name);
-                }
-            }
-            _ => {}
-        }
+    fn render_save_filename_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 60;
+        let popup_height = 6;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let current_db = self.database_manager.get_current_database().unwrap_or("none");
+        let display_filename = if self.state.save_filename.is_empty() {
+            "_"
+        } else {
+            &self.state.save_filename
+        };
+
+        let content = format!(
+            "Save Database '{}' to File\n\nFilename: {}\n\n(.db extension will be added if not present)\nPress Enter to save, Esc to cancel",
+            current_db,
+            display_filename
+        );
+
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Save Database")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+
+        f.render_widget(popup, popup_area);
     }
 
-    fn confirm_delete(&mut self) {
+    fn render_delete_confirmation(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 60;
+        let popup_height = 6;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
         let (item_type, item_name) = match &self.state.delete_confirmation {
-            crate::app::state::DeleteConfirmationState::Database(name) => ("database", name.clone()),
-            crate::app::state::DeleteConfirmationState::Table(name) => ("table", name.clone()),
-            _ => return,
+            crate::app::state::DeleteConfirmationState::Database(name) => ("database", name.as_str()),
+            crate::app::state::DeleteConfirmationState::Table(name) => ("table", name.as_str()),
+            _ => ("item", "unknown"),
         };
-        
-        match item_type {
-            "database" => self.delete_database(&item_name),
-            "table" => self.delete_table(&item_name),
-            _ => {}
-        }
-    }
 
-    fn delete_database(&mut self, db_name: &str) {
-        if let Err(e) = self.database_manager.remove_database(db_name) {
-            self.state.show_error(format!("Failed to delete database: {e}"));
-        } else {
-            self.sync_selected_db_index();
-            self.selected_table_index = 0;
-            self.state.show_success(format!("Deleted database '{db_name}'"));
-        }
-    }
+        let content = match &self.state.delete_confirmation {
+            crate::app::state::DeleteConfirmationState::Rows(table, rows) => format!(
+                "⚠️  Delete Confirmation\n\nDelete {} row(s) from '{}'?\nThis action cannot be undone!\n\nPress 'd' to confirm, Esc to cancel",
+                rows.len(), table
+            ),
+            crate::app::state::DeleteConfirmationState::Truncate(table) => format!(
+                "⚠️  Truncate Confirmation\n\nDelete all rows of table '{}'?\nThis action cannot be undone!\n\nPress 'd' to confirm, Esc to cancel",
+                table
+            ),
+            crate::app::state::DeleteConfirmationState::Schema(schema) => format!(
+                "⚠️  Drop Schema Confirmation\n\nDrop schema '{}' and its {} table(s)?\nThis action cannot be undone!\n\nPress 'd' to confirm, Esc to cancel",
+                schema,
+                self.get_current_table_infos().iter().filter(|table| &table.schema == schema).count()
+            ),
+            _ => format!(
+                "⚠️  Delete Confirmation\n\nDelete {} '{}'?\nThis action cannot be undone!\n\nPress 'd' to confirm, Esc to cancel",
+                item_type, item_name
+            ),
+        };
 
-    fn delete_table(&mut self, table_name: &str) {
-        if let Err(e) = self.database_manager.remove_table(table_name) {
-            self.state.show_error(format!("Failed to delete table: {e}"));
-        } else {
-            // Clear table data if we deleted the currently viewed table
-            if let Some(current_table) = &self.state.selected_table {
-                if current_table == table_name {
-                    self.state.table_data = None;
-                    self.state.selected_table = None;
-                }
-            }
-            self.sync_selected_table_index();
-            self.state.show_success(format!("Deleted table '{table_name}'"));
-        }
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Confirm Delete")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+
+        f.render_widget(popup, popup_area);
     }
 
-    fn save_current_database_to_file(&mut self) {
-        if let Some(current_db) = self.database_manager.get_current_database() {
-            let db_name = current_db.to_string();
-            let filename = self.state.save_filename.clone();
-            
-            // Add .db extension if not present
-            let file_path = if filename.ends_with(".db") || filename.ends_with(".duckdb") {
-                filename
-            } else {
-                format!("{filename}.db")
-            };
-            
-            let mut workflows = DatabaseWorkflows::new(
-                &mut self.database_manager,
-                &mut self.action_logger,
-                &mut self.state,
-            );
-            
-            match workflows.save_database_to_file(db_name, std::path::PathBuf::from(file_path)) {
-                Ok(_) => {
-                    // Success message handled by workflow
-                }
-                Err(e) => {
-                    self.state.show_error(format!("Failed to save database: {e}"));
-                }
-            }
+    fn render_view_name_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 60;
+        let popup_height = 8;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let unknown_table = "unknown".to_string();
+        let current_table = self.state.selected_table.as_ref().unwrap_or(&unknown_table);
+        let display_view_name = if self.state.new_view_name.is_empty() {
+            "_"
         } else {
-            self.state.show_error("No database selected to save".to_string());
-        }
-    }
+            &self.state.new_view_name
+        };
 
-    fn create_view_from_current_state(&mut self) {
-        if let (Some(connection), Some(table_name)) = (
-            self.database_manager.get_current_connection(),
-            &self.state.selected_table
-        ) {
-            let view_name = self.state.new_view_name.trim().to_string();
-            
-            // Generate SQL from current view state
-            if let Some(query_sql) = self.state.generate_view_sql(table_name) {
-                let create_view_sql = format!("CREATE VIEW {view_name} AS {query_sql}");
-                
-                // Execute the CREATE VIEW statement
-                match connection.execute(&create_view_sql, []) {
-                    Ok(_) => {
-                        self.state.show_success(format!("View '{view_name}' created successfully"));
-                        
-                        // Refresh the table list to show the new view
-                        if let Some(current_db) = self.database_manager.get_current_database() {
-                            let db_name = current_db.to_string();
-                            if let Err(e) = self.database_manager.refresh_database(&db_name) {
-                                self.action_logger.log_error(&format!("Failed to refresh table list: {e}"));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        self.state.show_error(format!("Failed to create view: {e}"));
-                        self.action_logger.log_error(&format!("CREATE VIEW failed: {e}"));
-                    }
-                }
+        // Generate a preview of the SQL
+        let sql_preview = if let Some(sql) = self.state.generate_view_sql(current_table) {
+            let truncated = if sql.len() > 100 {
+                format!("{}...", &sql[..97])
             } else {
-                self.state.show_error("Failed to generate SQL for current view state".to_string());
-            }
+                sql
+            };
+            format!("\nSQL: {truncated}")
         } else {
-            self.state.show_error("No database connection or table selected".to_string());
-        }
-    }
+            "\nSQL: SELECT * FROM table".to_string()
+        };
 
-    pub fn render(&mut self, f: &mut Frame) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Min(0),    // Main content
-                Constraint::Length(3), // Status bar
-            ])
-            .split(f.area());
+        let content = format!(
+            "Save View from Table '{}'\n\nView name: {}{}\n\nPress Enter to create view, Esc to cancel",
+            current_table,
+            display_view_name,
+            sql_preview
+        );
 
-        // Header
-        let header = Paragraph::new("🦆 Ducky - DuckDB TUI")
-            .style(
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Create View")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)),
             )
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(header, chunks[0]);
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
 
-        // Check for inspect mode - use full area for inspection
-        if self.state.inspect_mode {
-            if let Some(table) = self.state.selected_table.clone() {
-                self.render_inspect_view(f, chunks[1], &table);
-            }
-        } else {
-            // Normal mode: Main content area - New 2-panel layout
-            let main_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Percentage(30), // Left sidebar
-                    Constraint::Percentage(70), // Table viewer
-                ])
-                .split(chunks[1]);
+        f.render_widget(popup, popup_area);
+    }
 
-            // Left sidebar (combined database + table list)
-            self.render_left_sidebar(f, main_chunks[0]);
+    fn render_query_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup, wide enough for a typical query
+        let popup_width = area.width.saturating_sub(10).min(100);
+        let popup_height = 9;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-            // Table viewer (renamed from main content)
-            self.render_table_viewer(f, main_chunks[1]);
-        }
+        let current_db = self.database_manager.get_current_database().unwrap_or("none");
+        let display_query = if self.state.query_input.is_empty() {
+            "_"
+        } else {
+            &self.state.query_input
+        };
 
-        // Status bar
-        self.render_status_bar(f, chunks[2]);
+        let content = format!(
+            "Query database '{}'\n\n{}\n\nThe result opens in a new tab\nEnter to run, Esc to cancel",
+            current_db,
+            display_query
+        );
 
-        // Render notifications
-        self.render_notifications(f, f.area());
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Run Query")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
 
-        // Render file browser popup if shown
-        if self.show_file_browser {
-            if let Some(ref browser) = self.file_browser {
-                render_file_browser_popup(f, f.area(), browser);
-            }
-        }
+        f.render_widget(popup, popup_area);
+    }
 
-        // Render database dropdown overlay if expanded
-        if self.state.database_dropdown_expanded {
-            self.render_database_dropdown_overlay(f, f.area());
-        }
+    fn render_compare_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 70;
+        let popup_height = 9;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-        // Render database name input popup
-        if self.state.is_entering_database_name {
-            self.render_database_name_input(f, f.area());
-        }
+        let display_input = if self.state.compare_input.is_empty() {
+            "_"
+        } else {
+            &self.state.compare_input
+        };
 
-        // Render save filename input popup
-        if self.state.is_entering_save_filename {
-            self.render_save_filename_input(f, f.area());
-        }
-        // Render view name input popup
-        if self.state.is_entering_view_name {
-            self.render_view_name_input(f, f.area());
-        }
+        let content = format!(
+            "Compare rows of two tables, views or query results\n\n{}\n\n<left> <right> <key columns, comma separated>\nEnter to compare, Esc to cancel",
+            display_input
+        );
+
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Compare Tables")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
 
-        // Render delete confirmation popup
-        if self.state.is_delete_confirmation_active() {
-            self.render_delete_confirmation(f, f.area());
-        }
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_left_sidebar(&self, f: &mut Frame, area: Rect) {
-        // Split sidebar into database dropdown area and table list
-        let sidebar_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Database dropdown area
-                Constraint::Min(0),    // Table list area
-            ])
-            .split(area);
+    fn render_export_path_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 70;
+        let popup_height = 8;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-        // Render database dropdown in top area
-        self.render_database_dropdown(f, sidebar_chunks[0]);
-        
-        // Render table list in bottom area
-        self.render_table_list_compact(f, sidebar_chunks[1]);
-    }
+        let unknown_table = "unknown".to_string();
+        let current_table = self.state.selected_table.as_ref().unwrap_or(&unknown_table);
+        let display_path = if self.state.export_path.is_empty() {
+            "_"
+        } else {
+            &self.state.export_path
+        };
 
-    fn render_database_dropdown(&self, f: &mut Frame, area: Rect) {
-        let current_db = self.database_manager.get_current_database().unwrap_or("none");
-        
-        // Always render collapsed state here - expanded state is handled as overlay
-        let content = format!("DB: [{current_db}]");
-        
-        let border_style = self.get_panel_border_style(NavigationPanel::DatabaseList);
+        let content = format!(
+            "Export '{}' with current columns, filters and sort\n\nFile: {}\n\nFormat from extension: .csv, .parquet or .json\nEnter to export, Esc to cancel",
+            current_table,
+            display_path
+        );
 
-        let dropdown = Paragraph::new(content)
+        let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Database")
+                    .title("Export")
                     .borders(Borders::ALL)
-                    .border_style(border_style),
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             )
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
 
-        f.render_widget(dropdown, area);
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_table_list_compact(&self, f: &mut Frame, area: Rect) {
-        let current_table_infos = self.get_current_table_infos();
-        
-        let items: Vec<ListItem> = current_table_infos
-            .iter()
-            .enumerate()
-            .map(|(i, table_info)| {
-                let is_selected = i == self.selected_table_index;
-                let is_current = self.state.selected_table.as_ref() == Some(&table_info.name);
-                
-                // Choose icon based on table type
-                let icon = if table_info.table_type == "VIEW" {
-                    "[v]"  // View indicator
-                } else {
-                    "[t]"  // Table indicator
-                };
-                
-                let style = if is_selected {
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
-                } else if is_current {
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default()
-                };
-                
-                let selection_indicator = if is_current { "● " } else { "  " };
-                let display_name = format!("{}{} {}", selection_indicator, icon, table_info.name);
-                ListItem::new(display_name).style(style)
-            })
-            .collect();
+    fn render_cell_edit_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 70;
+        let popup_height = 8;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-        let border_style = self.get_panel_border_style(NavigationPanel::TableList);
+        let unknown = "unknown".to_string();
+        let current_table = self.state.selected_table.as_ref().unwrap_or(&unknown);
+        let current_column = self.state.selected_column.as_ref().unwrap_or(&unknown);
+        let display_value = if self.state.cell_edit_input.is_empty() {
+            "_"
+        } else {
+            &self.state.cell_edit_input
+        };
 
-        let list = List::new(items)
+        let content = format!(
+            "Edit '{}' in '{}'\n\nValue: {}\n\nType NULL to clear the cell\nEnter to save, Esc to cancel",
+            current_column,
+            current_table,
+            display_value
+        );
+
+        let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Tables")
+                    .title("Edit Cell")
                     .borders(Borders::ALL)
-                    .border_style(border_style),
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             )
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
 
-        f.render_widget(list, area);
+        f.render_widget(popup, popup_area);
     }
 
-    fn get_panel_border_style(&self, panel: NavigationPanel) -> Style {
-        let is_active = self.state.active_panel == panel;
-        if is_active {
-            if self.state.is_panel_flashing() {
-                // Flash effect: bright cyan
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                // Normal active: green
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD)
+    /// Column list of the table designer, with the column editor below it when open
+    fn render_table_design(&self) -> String {
+        let mut lines = vec![format!("Create New Table: '{}'", self.state.new_table_name), String::new()];
+        if self.state.table_design.is_empty() {
+            lines.push("No columns yet".to_string());
+        }
+        for (i, column) in self.state.table_design.iter().enumerate() {
+            let marker = if i == self.state.table_design_selected { "►" } else { " " };
+            let data_type = column.data_type().unwrap_or_else(|_| format!("{} (incomplete)", column.type_name()));
+            let mut flags = Vec::new();
+            if column.primary_key {
+                flags.push("PK".to_string());
             }
+            if column.not_null {
+                flags.push("NOT NULL".to_string());
+            }
+            if column.unique {
+                flags.push("UNIQUE".to_string());
+            }
+            if !column.default.is_empty() {
+                flags.push(format!("DEFAULT {}", column.default));
+            }
+            lines.push(format!("{} {} {} {}", marker, column.name, data_type, flags.join(" ")).trim_end().to_string());
+        }
+        lines.push(String::new());
+
+        if let Some(editor) = &self.state.column_editor {
+            let title = if editor.index.is_some() { "Edit column" } else { "New column" };
+            lines.push(format!("── {title} ──"));
+            let check = |value: bool| if value { "[x]" } else { "[ ]" };
+            let argument = match editor.column.type_argument_hint() {
+                Some(hint) if editor.column.type_argument.is_empty() => format!("({hint})"),
+                Some(_) => editor.column.type_argument.clone(),
+                None => "-".to_string(),
+            };
+            let values = [
+                editor.column.name.clone(),
+                format!("◄ {} ►", editor.column.type_name()),
+                argument,
+                check(editor.column.not_null).to_string(),
+                editor.column.default.clone(),
+                check(editor.column.primary_key).to_string(),
+                check(editor.column.unique).to_string(),
+            ];
+            for (field, (label, value)) in crate::app::state::COLUMN_EDITOR_FIELDS.iter().zip(values).enumerate() {
+                let marker = if field == editor.field { "►" } else { " " };
+                lines.push(format!("{marker} {label}: {value}"));
+            }
+            lines.push(String::new());
+            lines.push("Tab/↑↓ = Field | ←→ = Type | Space = Toggle | Enter = Save column | Esc = Discard".to_string());
         } else {
-            // Inactive: default
-            Style::default()
+            lines.push("a = Add column | e/Enter = Edit | d = Remove | j/k = Select | p = Preview DDL | Esc = Cancel".to_string());
         }
+        lines.join("\n")
     }
 
-    fn render_table_viewer(&mut self, f: &mut Frame, area: Rect) {
-        // Check if we're in inspect mode and have a selected table
-        if self.state.inspect_mode {
-            if let Some(table) = self.state.selected_table.clone() {
-                self.render_inspect_view(f, area, &table);
-                return;
-            }
+    fn render_er_diagram(f: &mut Frame, area: Rect, diagram: &ErDiagram) {
+        let footer: Vec<String> = diagram.other_relationships.iter()
+            .map(|relationship| format!("Also: {relationship}"))
+            .collect();
+        let inner_height = (area.height as usize).saturating_sub(2 + footer.len());
+        let inner_width = (area.width as usize).saturating_sub(2);
+
+        let highlight = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let mut lines: Vec<Line> = diagram.viewport(inner_width, inner_height).into_iter()
+            .map(|(line, selected)| match selected {
+                Some((start, end)) => {
+                    let chars: Vec<char> = line.chars().collect();
+                    let end = end.min(chars.len());
+                    let start = start.min(end);
+                    Line::from(vec![
+                        Span::raw(chars[..start].iter().collect::<String>()),
+                        Span::styled(chars[start..end].iter().collect::<String>(), highlight),
+                        Span::raw(chars[end..].iter().collect::<String>()),
+                    ])
+                }
+                None => Line::from(line),
+            })
+            .collect();
+        lines.extend(footer.into_iter().map(|line| Line::from(Span::styled(line, Style::default().fg(Color::DarkGray)))));
+
+        let title = format!(
+            "ER Diagram: {} (hjkl to move, Enter to open, Esc to close)",
+            diagram.selected_table().unwrap_or("")
+        );
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White));
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_reverse_references(&self, f: &mut Frame, area: Rect, references: &crate::app::state::ReverseReferences) {
+        // Create centered popup
+        let popup_width = 80.min(area.width);
+        let popup_height = (references.entries.len() as u16 + 6).min(area.height);
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let mut lines = Vec::new();
+        if references.entries.is_empty() {
+            lines.push("The key of this row is NULL; nothing can reference it".to_string());
+        }
+        for (i, entry) in references.entries.iter().enumerate() {
+            let marker = if i == references.selected { "►" } else { " " };
+            lines.push(format!(
+                "{} {} ({}) — {} row(s)",
+                marker, entry.reference.table_name, entry.reference.foreign_key.columns.join(", "), entry.row_count
+            ));
         }
+        lines.push(String::new());
+        lines.push("j/k = Select | Enter = Open referencing rows | Esc = Close".to_string());
 
-        // Cache area height for navigation calculations
-        self.state.last_table_area_height = area.height;
-        let border_style = self.get_panel_border_style(NavigationPanel::MainContent);
+        let popup = Paragraph::new(lines.join("\n"))
+            .block(
+                Block::default()
+                    .title(format!("Referencing {}", references.label))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD));
 
-        let (content, title): (String, String) = if self.state.current_state == AppState::ImportWizard && self.state.is_creating_table {
-            match self.state.table_creation_step {
-                TableCreationStep::EnteringTableName => {
-                    let content = format!(
-                        "Create New Table\n\nTable name: {}\n\nType the table name and press Enter to continue\nPress Esc to cancel",
-                        if self.state.new_table_name.is_empty() { 
-                            "_" 
-                        } else { 
-                            &self.state.new_table_name 
-                        }
-                    );
-                    (content, "Import Wizard - Table Name".to_string())
-                }
-                TableCreationStep::SelectingFile => {
-                    let content = format!(
-                        "Create New Table: '{}'\n\nSelect a file to import data from:\n• CSV files (.csv)\n• JSON files (.json)\n• Parquet files (.parquet)\n\nPress Esc to cancel",
-                        self.state.new_table_name
-                    );
-                    (content, "Import Wizard - File Selection".to_string())
-                }
-                TableCreationStep::ImportingData => {
-                    let content = format!(
-                        "Create New Table: '{}'\n\nImporting data...\n\nPlease wait while the data is being imported.",
-                        self.state.new_table_name
-                    );
-                    (content, "Import Wizard - Importing".to_string())
-                }
-            }
-        } else if let (Some(db), Some(table)) = (&self.state.selected_database, &self.state.selected_table) {
-            if let Some(ref data) = self.state.table_data {
-                // Render table using ratatui Table widget instead of string content
-                // Build table title with sort status
-                let sort_info = if !self.state.sort_columns.is_empty() {
-                    let mut sort_parts = Vec::new();
-                    for sort_spec in &self.state.sort_columns {
-                        let direction = match sort_spec.direction {
-                            crate::app::state::SortDirection::Ascending => "ASC",
-                            crate::app::state::SortDirection::Descending => "DESC",
-                        };
-                        sort_parts.push(format!("{} {}", sort_spec.column_name, direction));
-                    }
-                    
-                    if !sort_parts.is_empty() {
-                        format!(" - Sorted by: {}", sort_parts.join(", "))
-                    } else {
-                        String::new()
-                    }
-                } else {
-                    String::new()
-                };
-                
-                let title = format!("Table: {} ({} rows){}", table, data.row_count, sort_info);
-                self.render_table_widget(f, area, data, &title);
-                return; // Early return since we handled rendering directly
-            } else {
-                let content = format!(
-                    "Database: {db}\nTable: {table}\n\nLoading table data...\n\nPress Enter on table to load data or wait for auto-load"
-                );
-                (content, "Table Viewer".to_string())
-            }
-        } else {
-            let debug_info = format!(
-                "Database: {:?}\nTable: {:?}", 
-                self.state.selected_database, 
-                self.state.selected_table
-            );
-            let content = format!(
-                "Select a database and table to view data\n\nNavigation:\n• Use Tab/Shift+Tab to switch panels\n• Use ↑↓ to navigate lists\n• Press Enter to select items\n• Press i to import data\n• Press h for help\n• Press q or Esc to quit\n\nDebug:\n{}", 
-                debug_info
-            );
-            (content, "Main Content [3]".to_string())
+        f.render_widget(popup, popup_area);
+    }
+
+    fn render_index_manager(&self, f: &mut Frame, area: Rect, manager: &crate::app::state::IndexManager) {
+        // Create centered popup
+        let popup_width = 90.min(area.width);
+        let popup_height = (manager.indexes.len() as u16 + manager.hints.len() as u16 + 14).min(area.height);
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
         };
 
-        let paragraph = Paragraph::new(content)
+        let mut lines = Vec::new();
+        if manager.indexes.is_empty() {
+            lines.push("No indexes on this table".to_string());
+        }
+        for (i, index) in manager.indexes.iter().enumerate() {
+            let marker = if i == manager.selected { "►" } else { " " };
+            let unique = if index.is_unique { " UNIQUE" } else { "" };
+            lines.push(format!("{} {} {}{}", marker, index.name, index.expressions, unique));
+        }
+        lines.push(String::new());
+        lines.push("Filter plan:".to_string());
+        lines.extend(manager.hints.iter().map(|hint| format!("  {hint}")));
+        lines.push(String::new());
+
+        let mut border_color = Color::Cyan;
+        if let Some(form) = &manager.form {
+            let marker = |field: usize| if form.field == field { "►" } else { " " };
+            lines.push("── New index ──".to_string());
+            lines.push(format!("{} Name: {}", marker(0), form.name));
+            lines.push(format!("{} Columns: {}", marker(1), if form.columns.is_empty() { "_" } else { &form.columns }));
+            lines.push(format!("{} Unique: {}", marker(2), if form.unique { "[x]" } else { "[ ]" }));
+            lines.push("Tab = Next field | Space = Toggle unique | Enter = Create | Esc = Cancel".to_string());
+        } else if manager.confirming_drop {
+            border_color = Color::Red;
+            let name = manager.indexes.get(manager.selected).map(|index| index.name.as_str()).unwrap_or("?");
+            lines.push(format!("⚠️  Drop index '{name}'? Press 'y' to confirm, Esc to cancel"));
+        } else {
+            lines.push("j/k = Select | a = Create index | d = Drop index | Esc = Close".to_string());
+        }
+
+        let popup = Paragraph::new(lines.join("\n"))
             .block(
                 Block::default()
-                    .title(title)
+                    .title(format!("Indexes: {}", manager.table_name))
                     .borders(Borders::ALL)
-                    .border_style(border_style),
+                    .border_style(Style::default().fg(border_color).add_modifier(Modifier::BOLD)),
             )
-            .wrap(Wrap { trim: true })
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .wrap(Wrap { trim: false });
 
-        f.render_widget(paragraph, area);
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_inspect_view(&mut self, f: &mut Frame, area: Rect, table_name: &str) {
-        // Get schema and statistics data
-        let (schema_data, stats_data) = if let Some(connection) = self.database_manager.get_current_connection() {
-            let schema = self.get_table_schema(connection, table_name)
-                .unwrap_or_else(|_| crate::db::query::QueryResult::new());
-            let stats = self.get_table_statistics(connection, table_name)
-                .unwrap_or_else(|_| crate::db::query::QueryResult::new());
-            (schema, stats)
-        } else {
-            (crate::db::query::QueryResult::new(), crate::db::query::QueryResult::new())
+    fn render_definition_view(&self, f: &mut Frame, area: Rect, view: &crate::app::state::DefinitionView) {
+        // Create centered popup
+        let popup_width = area.width.saturating_sub(10).min(110);
+        let popup_height = (view.sql.lines().count() as u16 + 6).clamp(10, area.height.max(10));
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width.min(area.width),
+            height: popup_height.min(area.height),
         };
 
-        // Split area into two sections: schema on top, statistics on bottom
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(50), // Schema section
-                Constraint::Percentage(50), // Statistics section
-            ])
-            .split(area);
+        let mut lines: Vec<Line> = highlight_sql(&view.sql).into_iter().skip(view.scroll).collect();
+        lines.push(Line::from(""));
+        let footer = match &view.save_path {
+            Some(path) => format!("Save to: {}  (Enter to write, Esc to cancel)", if path.is_empty() { "_" } else { path }),
+            None if view.kind == crate::db::definition::DefinitionKind::View => {
+                "y = Copy | : = Open in query editor | e = Edit as CREATE OR REPLACE VIEW | w = Save .sql | Esc = Close".to_string()
+            }
+            None => "y = Copy | : = Open in query editor | w = Save .sql | Esc = Close".to_string(),
+        };
+        lines.push(Line::from(footer));
 
-        // Render schema section
-        self.render_schema_section(f, chunks[0], table_name, &schema_data);
-        
-        // Render statistics section
-        self.render_statistics_section(f, chunks[1], table_name, &stats_data);
+        let popup = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!("Definition of {} {}", view.kind.label(), view.object_name))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_schema_section(&self, f: &mut Frame, area: Rect, table_name: &str, schema_data: &crate::db::query::QueryResult) {
-        // Determine if this section is active and style accordingly
-        let is_active = matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Schema);
-        let border_style = if is_active {
-            Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::DarkGray)
+    fn render_schema_diff_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 70;
+        let popup_height = 9;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
         };
-        
-        // Create title with active indicator
-        let title = if is_active {
-            if self.state.is_modifying {
-                format!("► Columns: {} (MODIFY MODE - j/k to move, J/K for extremes, o to hide/show, Enter to confirm, Esc to cancel)", table_name)
-            } else {
-                format!("► Columns: {} (Tab to switch, j/k to scroll, m to modify)", table_name)
-            }
+
+        let display_input = if self.state.schema_diff_input.is_empty() {
+            "_"
         } else {
-            format!("Columns: {}", table_name)
+            &self.state.schema_diff_input
         };
 
-        if schema_data.rows.is_empty() {
-            // No schema data available
-            let content = "No column information available for this table.";
-            let paragraph = Paragraph::new(content)
-                .block(
-                    Block::default()
-                        .title(title)
-                        .borders(Borders::ALL)
-                        .border_style(border_style),
-                )
-                .wrap(Wrap { trim: true })
-                .style(Style::default().fg(Color::White));
-            f.render_widget(paragraph, area);
-            return;
-        }
-
-        // Build table rows from schema data, respecting virtual column order
-        let virtual_order = self.state.get_virtual_column_order();
-        let original_columns = self.state.get_original_column_names();
-        
-        // Reorder schema data to match virtual column order
-        let mut ordered_schema_rows = Vec::new();
-        for virtual_col_name in &virtual_order {
-            // Find the index of this column in the original schema
-            if let Some(original_idx) = original_columns.iter().position(|name| name == virtual_col_name) {
-                if original_idx < schema_data.rows.len() {
-                    ordered_schema_rows.push(schema_data.rows[original_idx].clone());
-                }
-            }
-        }
-        
-        // Create header in virtual order (add extra columns for enhanced display)
-        let header = vec![
-            Cell::from("Column Name"),
-            Cell::from("Data Type"),
-            Cell::from("Nullable"),
-            Cell::from("Sort Order"),
-            Cell::from("Sort Direction"),
-            Cell::from("Hidden"),
-        ];
-        
-        // Apply scrolling: skip rows based on scroll position
-        let scroll_offset = self.state.inspect_schema_scroll_y;
-        let rows: Vec<Row> = ordered_schema_rows.iter()
-            .enumerate()
-            .skip(scroll_offset)
-            .map(|(row_idx, row)| {
-                // Get the virtual column index
-                let virtual_col_idx = scroll_offset + row_idx;
-                
-                // Get the column name from virtual order
-                let column_name = if virtual_col_idx < virtual_order.len() {
-                    &virtual_order[virtual_col_idx]
-                } else {
-                    return Row::new(vec![Cell::from("ERROR")]);
-                };
-                
-                // Check if this column is hidden by name
-                let is_hidden = self.state.is_column_hidden_by_name(column_name);
-                
-                // Get sort information
-                let sort_info = self.state.sort_columns.iter()
-                    .position(|spec| spec.column_name == *column_name)
-                    .map(|pos| (pos + 1, &self.state.sort_columns[pos].direction));
-                
-                let (sort_order, sort_direction) = match sort_info {
-                    Some((order, direction)) => {
-                        let dir_str = match direction {
-                            crate::app::state::SortDirection::Ascending => "ASC",
-                            crate::app::state::SortDirection::Descending => "DESC",
-                        };
-                        (order.to_string(), dir_str.to_string())
-                    },
-                    None => ("".to_string(), "".to_string()),
-                };
-                
-                // Build enhanced row with additional columns
-                let base_style = if is_hidden {
-                    Style::default().fg(Color::DarkGray)
-                } else {
-                    Style::default()
-                };
-                
-                let cells = vec![
-                    Cell::from(row.first().unwrap_or(&"?".to_string()).clone()).style(base_style),  // Column Name
-                    Cell::from(row.get(1).unwrap_or(&"?".to_string()).clone()).style(base_style),  // Data Type
-                    Cell::from(row.get(2).unwrap_or(&"?".to_string()).clone()).style(base_style),  // Nullable
-                    Cell::from(sort_order.clone()).style(base_style),                              // Sort Order
-                    Cell::from(sort_direction.clone()).style(base_style),                          // Sort Direction
-                    Cell::from(if is_hidden { "YES" } else { "NO" }).style(base_style),            // Hidden
-                ];
-                
-                Row::new(cells)
-            }).collect();
-
-        // Use fixed column widths for the enhanced display (6 columns)
-        let constraints: Vec<Constraint> = vec![
-            Constraint::Length(15),  // Column Name
-            Constraint::Length(12),  // Data Type
-            Constraint::Length(8),   // Nullable
-            Constraint::Length(10),  // Sort Order
-            Constraint::Length(12),  // Sort Direction
-            Constraint::Length(6),   // Hidden
-        ];
+        let content = format!(
+            "Compare the schemas of two databases\n\n{}\n\n<left> <right>, each a database or database:catalog\nEnter to compare, Esc to cancel",
+            display_input
+        );
 
-        let table = Table::new(rows, constraints)
-            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+        let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title(title)
+                    .title("Schema Diff")
                     .borders(Borders::ALL)
-                    .border_style(border_style),
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             )
-            .highlight_style(if is_active {
-                Style::default().bg(Color::Gray).fg(Color::Black).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().bg(Color::DarkGray)
-            })
-            .style(Style::default().fg(Color::White));
-
-        // Create table state for selection
-        let mut table_state = TableState::default();
-        if is_active {
-            // Show selection only when this section is active
-            let selected_index = self.state.inspect_selected_row.saturating_sub(scroll_offset);
-            table_state.select(Some(selected_index));
-        }
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
 
-        f.render_stateful_widget(table, area, &mut table_state);
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_statistics_section(&self, f: &mut Frame, area: Rect, table_name: &str, stats_data: &crate::db::query::QueryResult) {
-        // Determine if this section is active and style accordingly
-        let is_active = matches!(self.state.inspect_active_section, crate::app::state::InspectSection::Statistics);
-        let border_style = if is_active {
-            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::DarkGray)
+    fn render_schema_diff(&self, f: &mut Frame, area: Rect, view: &crate::app::state::SchemaDiffView) {
+        // Create centered popup
+        let popup_width = area.width.saturating_sub(10).min(110);
+        let popup_height = (view.script.lines().count() as u16 + 6).clamp(10, area.height.max(10));
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width.min(area.width),
+            height: popup_height.min(area.height),
         };
-        
-        // Create title with active indicator
-        let title = if is_active {
-            format!("► Statistics: {table_name} (Tab to switch, ↑↓ to scroll, Esc to exit)")
-        } else {
-            format!("Statistics: {table_name} (Press Esc to exit inspect mode)")
+
+        let mut lines: Vec<Line> = highlight_sql(&view.script).into_iter().skip(view.scroll).collect();
+        lines.push(Line::from(""));
+        let footer = match &view.save_path {
+            Some(path) => format!("Save to: {}  (Enter to write, Esc to cancel)", if path.is_empty() { "_" } else { path }),
+            None => "j/k = Scroll | y = Copy | w = Save .sql | Esc = Close".to_string(),
         };
+        lines.push(Line::from(footer));
 
-        if stats_data.rows.is_empty() {
-            // No statistics data available
-            let content = "No statistics available for this table.";
-            let paragraph = Paragraph::new(content)
-                .block(
-                    Block::default()
-                        .title(title)
-                        .borders(Borders::ALL)
-                        .border_style(border_style),
-                )
-                .wrap(Wrap { trim: true })
-                .style(Style::default().fg(Color::White));
-            f.render_widget(paragraph, area);
-            return;
-        }
+        let popup = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!("Schema diff {} → {} ({} change(s))", view.source, view.target, view.change_count))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .wrap(Wrap { trim: false });
 
-        // Build table rows from statistics data
-        let header = stats_data.columns.iter().map(|col| Cell::from(col.as_str())).collect::<Vec<_>>();
-        
-        // Apply scrolling: skip rows based on scroll position
-        let scroll_offset = self.state.inspect_stats_scroll_y;
-        let rows: Vec<Row> = stats_data.rows.iter()
-            .skip(scroll_offset)
-            .map(|row| {
-                let cells: Vec<Cell> = row.iter().map(|cell| Cell::from(cell.as_str())).collect();
-                Row::new(cells)
-            }).collect();
+        f.render_widget(popup, popup_area);
+    }
+
+    fn render_storage_breakdown(&self, f: &mut Frame, area: Rect, breakdown: &crate::app::state::StorageBreakdown) {
+        // Create centered popup
+        let popup_width = 90.min(area.width);
+        let popup_height = (breakdown.storage.columns.len() as u16 + 8).min(area.height);
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-        // Calculate column widths based on content
-        let mut column_widths = vec![0; stats_data.columns.len()];
-        
-        // Check header widths
-        for (i, col) in stats_data.columns.iter().enumerate() {
-            column_widths[i] = col.len().max(column_widths[i]);
-        }
-        
-        // Check data widths
-        for row in &stats_data.rows {
-            for (i, cell) in row.iter().enumerate() {
-                if i < column_widths.len() {
-                    column_widths[i] = cell.len().max(column_widths[i]);
-                }
-            }
+        let storage = &breakdown.storage;
+        let rows = breakdown.estimated_rows.map(|rows| format!("~{rows} rows in ")).unwrap_or_default();
+        let size = |bytes: Option<i64>| bytes
+            .map(crate::db::schema::format_size)
+            .unwrap_or_else(|| "in memory".to_string());
+        let mut lines = vec![
+            format!(
+                "{rows}{} row groups, {} segments, {} on disk",
+                storage.row_group_count,
+                storage.segment_count,
+                size(storage.estimated_bytes)
+            ),
+            String::new(),
+            format!("{:<24} {:>8} {:>12}  Compression", "Column", "Segments", "Size"),
+        ];
+        for column in storage.columns.iter().skip(breakdown.scroll) {
+            let compressions = column.compressions.iter()
+                .map(|(compression, count)| format!("{compression} ×{count}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!(
+                "{:<24} {:>8} {:>12}  {}",
+                column.column_name,
+                column.segment_count,
+                size(column.estimated_bytes),
+                compressions
+            ));
         }
+        lines.push(String::new());
+        lines.push("Sizes are upper-bound estimates from block offsets | j/k = Scroll | Esc = Close".to_string());
 
-        // Convert to constraints with minimum and maximum widths
-        let constraints: Vec<Constraint> = column_widths.iter().map(|&width| {
-            let min_width = 8; // Minimum column width
-            let max_width = 20; // Slightly smaller max for statistics to fit more columns
-            let adjusted_width = width.max(min_width).min(max_width);
-            Constraint::Length(adjusted_width as u16)
-        }).collect();
-
-        let table = Table::new(rows, constraints)
-            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+        let popup = Paragraph::new(lines.join("\n"))
             .block(
                 Block::default()
-                    .title(title)
+                    .title(format!("Storage: {}", breakdown.table_name))
                     .borders(Borders::ALL)
-                    .border_style(border_style),
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             )
-            .highlight_style(Style::default().bg(Color::DarkGray))
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD));
 
-        f.render_widget(table, area);
+        f.render_widget(popup, popup_area);
     }
 
-    fn calculate_inspect_scroll_bounds(&self) -> (usize, usize) {
-        // Calculate scrolling bounds for inspect mode
-        // Each section gets 50% of the main content area height
-        let section_height = (self.state.last_table_area_height / 2).saturating_sub(3) as usize; // Subtract for borders
-        let visible_rows = section_height.max(5); // Minimum 5 visible rows
-        
-        // Calculate max rows based on active section
-        let max_rows = match self.state.inspect_active_section {
-            crate::app::state::InspectSection::Schema => {
-                // For schema, estimate based on typical column count
-                // Most tables have 3-20 columns, so max_rows would be that count
-                20 // Conservative estimate - in practice this will be determined by actual data
-            }
-            crate::app::state::InspectSection::Statistics => {
-                // For statistics, DuckDB SUMMARIZE typically returns one row per column
-                // So this is similar to schema but may be longer with statistics per column
-                50 // Conservative estimate for statistics rows
-            }
+    fn render_attach_input(&self, f: &mut Frame, area: Rect, attach: &crate::app::state::PendingAttach) {
+        // Create centered popup
+        let popup_width = 70;
+        let popup_height = 10;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
         };
-        
-        (max_rows, visible_rows)
+
+        let display_alias = if attach.alias.is_empty() { "_" } else { &attach.alias };
+        let mode = if attach.read_only { "read-only" } else { "read-write" };
+        let content = format!(
+            "Attach {}\n\nAs: {display_alias}\nMode: {mode} (Tab to toggle)\n\nQuery its tables as {display_alias}.main.<table>\nEnter to attach, Esc to cancel",
+            attach.path.display()
+        );
+
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Attach Database")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_status_bar(&self, f: &mut Frame, area: Rect) {
-        if self.state.is_searching {
-            // Search mode: use entire status bar for search input
-            self.render_search_input(f, area);
+    fn render_schema_name_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 60;
+        let popup_height = 8;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let display_input = if self.state.schema_name_input.is_empty() {
+            "_"
         } else {
-            // Normal mode: split status bar into left and right sections
-            let status_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Percentage(60), // Left section - general status
-                    Constraint::Percentage(40), // Right section - table viewer status
-                ])
-                .split(area);
+            &self.state.schema_name_input
+        };
+        let content = format!("Name of the new schema\n\n{display_input}\n\nEnter to confirm, Esc to cancel");
 
-            // Render left status section (general status)
-            self.render_left_status(f, status_chunks[0]);
-            
-            // Render right status section (table viewer status)
-            self.render_table_status(f, status_chunks[1]);
-        }
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Create Schema")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_search_input(&self, f: &mut Frame, area: Rect) {
-        // Determine border color based on syntax validity
-        let border_color = if self.state.search_syntax_valid {
-            Color::Green // Green border for valid syntax
-        } else {
-            Color::Red // Red border for invalid syntax
+    fn render_table_operation_input(&self, f: &mut Frame, area: Rect, operation: &crate::app::state::TableOperation) {
+        // Create centered popup
+        let popup_width = 60;
+        let popup_height = 8;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
         };
 
-        // Get column name for context
-        let column_context = if let (Some(column_index), Some(ref data)) = (self.state.search_column, &self.state.table_data) {
-            if column_index < data.columns.len() {
-                format!(" [{}]", data.columns[column_index])
-            } else {
-                String::new()
+        let (title, prompt) = match operation.kind {
+            crate::app::state::TableOperationKind::Rename => {
+                let kind = if operation.is_view { "view" } else { "table" };
+                ("Rename", format!("Rename {} '{}' to", kind, operation.table_name))
             }
+            crate::app::state::TableOperationKind::Duplicate { with_data: false } => {
+                ("Duplicate Structure", format!("Copy the columns of '{}' into", operation.table_name))
+            }
+            crate::app::state::TableOperationKind::Duplicate { with_data: true } => {
+                ("Duplicate With Data", format!("Copy '{}' with its rows into", operation.table_name))
+            }
+            crate::app::state::TableOperationKind::Comment => {
+                let kind = if operation.is_view { "view" } else { "table" };
+                ("Comment", format!("Comment on {} '{}' (empty to remove)", kind, operation.table_name))
+            }
+        };
+        let display_input = if self.state.table_operation_input.is_empty() {
+            "_"
         } else {
-            String::new()
+            &self.state.table_operation_input
         };
 
-        // Create search input display with cursor
-        let search_display = format!("Filter{}: {}_", column_context, self.state.search_text);
-        
-        // Calculate available width (subtract borders and padding)
-        let available_width = area.width.saturating_sub(4) as usize; // 2 for borders + 2 for padding
-        let display_text = truncate_text(&search_display, available_width);
+        let content = format!("{prompt}\n\n{display_input}\n\nEnter to confirm, Esc to cancel");
 
-        let search_input = Paragraph::new(display_text)
+        let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Search/Filter")
+                    .title(title)
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(border_color)),
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             )
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
 
-        f.render_widget(search_input, area);
+        f.render_widget(popup, popup_area);
     }
 
-    fn check_search_debounce(&mut self) {
-        if self.state.is_searching && self.state.search_syntax_valid && self.state.should_debounce_update() {
-            // Reset debounce timer to prevent multiple updates
-            self.state.search_debounce_timer = None;
-            
-            // Trigger live update if search text is not empty
-            if !self.state.search_text.trim().is_empty() {
-                // Create a temporary filter for live preview
-                if let Some(column_index) = self.state.search_column {
-                    // Store current filters state
-                    let saved_filters = self.state.column_filters.clone();
-                    
-                    // Temporarily add the search text as a filter
-                    if let Some(column_name) = self.state.get_column_name_by_index(column_index) {
-                        self.state.column_filters.insert(column_name, self.state.search_text.trim().to_string());
-                    }
-                    
-                    // Update the view with limit for performance during preview
-                    self.fetch_table_data_preserve_column_with_limit(true);
-                    
-                    // Restore the saved filters (don't persist the preview)
-                    self.state.column_filters = saved_filters;
-                }
-            }
-        }
-    }
+    fn render_column_change_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 80;
+        let popup_height = 10;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-    fn validate_search_syntax(&mut self) {
-        if !self.state.is_searching {
-            return;
-        }
+        let unknown = "unknown".to_string();
+        let current_table = self.state.selected_table.as_ref().unwrap_or(&unknown);
+        let current_column = self.state.get_inspect_selected_column().unwrap_or_else(|| unknown.clone());
+        let display_input = if self.state.column_change_input.is_empty() {
+            "_"
+        } else {
+            &self.state.column_change_input
+        };
 
-        // If search text is empty, consider it valid
-        if self.state.search_text.trim().is_empty() {
-            self.state.search_syntax_valid = true;
-            return;
-        }
+        let content = format!(
+            "Alter column '{}' of '{}'\n\nChange: {}\n\n{}\nEnter to apply, Esc to cancel",
+            current_column, current_table, display_input, crate::db::alter::ALTER_SYNTAX
+        );
 
-        // Try to validate by building a test query
-        if let (Some(column_index), Some(ref table), Some(ref data)) = 
-            (self.state.search_column, &self.state.selected_table, &self.state.table_data) {
-            
-            if column_index < data.columns.len() {
-                let column_name = &data.columns[column_index];
-                let test_sql = format!(
-                    "SELECT COUNT(*) FROM {} WHERE {} {}",
-                    table, column_name, self.state.search_text.trim()
-                );
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Alter Column")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
 
-                // Try to prepare the statement to validate syntax
-                if let Some(connection) = self.database_manager.get_current_connection() {
-                    match connection.prepare(&test_sql) {
-                        Ok(_) => {
-                            self.state.search_syntax_valid = true;
-                        }
-                        Err(_) => {
-                            self.state.search_syntax_valid = false;
-                        }
-                    }
-                } else {
-                    // No connection available, assume valid for now
-                    self.state.search_syntax_valid = true;
-                }
-            }
-        }
+        f.render_widget(popup, popup_area);
+    }
+
+    fn render_column_change_confirmation(&self, f: &mut Frame, area: Rect, pending: &crate::app::state::PendingColumnChange) {
+        // Create centered popup
+        let popup_width = 80;
+        let popup_height = 9;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let content = format!(
+            "⚠️  Alter Table\n\n{}\n{}\n\nPress 'y' to confirm, Esc to cancel",
+            pending.report,
+            pending.change.alter_sql(&pending.table_name, &pending.column_name)
+        );
+
+        let popup = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title("Confirm Column Change")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_left_status(&self, f: &mut Frame, area: Rect) {
-        let border_style = self.get_panel_border_style(NavigationPanel::StatusBar);
+    fn render_bulk_update_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 80;
+        let popup_height = 9;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-        // Calculate available width (subtract borders and padding)
-        let available_width = area.width.saturating_sub(4) as usize; // 2 for borders + 2 for padding
-        let status_text = truncate_text(&self.state.get_status_display(), available_width);
+        let unknown = "unknown".to_string();
+        let current_column = self.state.selected_column.as_ref().unwrap_or(&unknown);
+        let filter = self.state.get_bulk_filter_clause().unwrap_or_default();
+        let display_expression = if self.state.bulk_update_input.is_empty() {
+            "_"
+        } else {
+            &self.state.bulk_update_input
+        };
 
-        let status = Paragraph::new(status_text)
+        let content = format!(
+            "For all rows {}\n\nSET {} = {}\n\nAny SQL expression, e.g. {} * 1.1, upper({}), NULL\nEnter to preview, Esc to cancel",
+            filter, current_column, display_expression, current_column, current_column
+        );
+
+        let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Status")
+                    .title("Bulk Update")
                     .borders(Borders::ALL)
-                    .border_style(border_style),
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             )
-            .style(Style::default().fg(Color::Cyan))
-            .wrap(Wrap { trim: false }); // Disable wrapping since we're truncating
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
 
-        f.render_widget(status, area);
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_table_status(&self, f: &mut Frame, area: Rect) {
-        let border_style = self.get_panel_border_style(NavigationPanel::MainContent);
+    fn render_bulk_change_confirmation(&self, f: &mut Frame, area: Rect, change: &crate::app::state::BulkChange) {
+        // Create centered popup
+        let popup_width = 80;
+        let popup_height = 10;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-        // Generate table-specific status info
-        let table_status = if let Some(ref data) = self.state.table_data {
-            format!(
-                "Row: {} of {} | Col: {} of {} | ←→↑↓ navigate",
-                self.state.selected_row + 1,
-                data.rows.len(),
-                self.state.get_selected_column_index().map(|i| i + 1).unwrap_or(1),
-                data.columns.len()
-            )
+        let description = match &change.kind {
+            crate::app::state::BulkChangeKind::Update { column_name, expression } => {
+                format!("Set '{}' = {} on {} row(s) of '{}'", column_name, expression, change.row_count, change.table_name)
+            }
+            crate::app::state::BulkChangeKind::Delete => {
+                format!("Delete {} row(s) from '{}'", change.row_count, change.table_name)
+            }
+        };
+        let staged = if self.state.is_staging_edits(self.state.selected_database.as_deref()) {
+            "The change is staged in the open transaction"
         } else {
-            "No table data".to_string()
+            "This action cannot be undone!"
         };
 
-        // Calculate available width (subtract borders and padding)
-        let available_width = area.width.saturating_sub(4) as usize; // 2 for borders + 2 for padding
-        let truncated_status = truncate_text(&table_status, available_width);
+        let content = format!(
+            "⚠️  Bulk Change\n\n{}\n{}\n{}\n\nPress 'y' to confirm, Esc to cancel",
+            description, change.filter_clause, staged
+        );
 
-        let status = Paragraph::new(truncated_status)
+        let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Table Info")
+                    .title("Confirm Bulk Change")
                     .borders(Borders::ALL)
-                    .border_style(border_style),
+                    .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
             )
-            .style(Style::default().fg(Color::Yellow))
-            .wrap(Wrap { trim: false }); // Disable wrapping since we're truncating
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
 
-        f.render_widget(status, area);
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_notifications(&self, f: &mut Frame, area: Rect) {
-        if self.state.notifications.is_empty() {
+    fn render_pending_changes(&self, f: &mut Frame, area: Rect) {
+        let Some(transaction) = &self.state.transaction else {
             return;
-        }
-
-        // Calculate notification area (centered, 60% width, near top)
-        let notification_width = (area.width as f32 * 0.6) as u16;
-        let notification_height = 3;
-        let x = (area.width.saturating_sub(notification_width)) / 2;
-        let y = 5; // Position near top
+        };
 
-        // Only show the most recent notification to avoid overlap
-        if let Some(notification) = self.state.notifications.last() {
-            let notification_area = Rect {
-                x,
-                y,
-                width: notification_width,
-                height: notification_height,
-            };
+        // Create centered popup
+        let popup_width = area.width.saturating_sub(10).min(110);
+        let popup_height = area.height.saturating_sub(6).min(24);
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
 
-            // Choose colors based on notification type - simple black background with colored borders
-            let (border_color, icon) = match notification.notification_type {
-                crate::app::state::NotificationType::Success => (Color::Green, "✅"),
-                crate::app::state::NotificationType::Error => (Color::Red, "❌"),
-                crate::app::state::NotificationType::Info => (Color::Blue, "ℹ️"),
-            };
+        let mut lines = vec![
+            format!("Transaction on '{}': {} pending change(s)", transaction.database, transaction.statements.len()),
+            String::new(),
+        ];
+        if transaction.aborted {
+            lines.push("⚠️  A change failed and DuckDB aborted the transaction; it can only be rolled back".to_string());
+            lines.push(String::new());
+        }
 
-            // Truncate notification text to fit
-            let available_width = notification_width.saturating_sub(6) as usize; // Account for borders + icon
-            let display_text = format!("{} {}", icon, truncate_text(&notification.message, available_width));
+        // Show the most recent statements that fit
+        let visible_statements = popup_height.saturating_sub(lines.len() as u16 + 5) as usize;
+        let skipped = transaction.statements.len().saturating_sub(visible_statements);
+        if skipped > 0 {
+            lines.push(format!("... {skipped} earlier change(s)"));
+        }
+        for (i, statement) in transaction.statements.iter().enumerate().skip(skipped) {
+            lines.push(format!("{:>3}. {statement};", i + 1));
+        }
+        if transaction.statements.is_empty() {
+            lines.push("No changes yet - edits in the viewer (E, I, Y, d) are staged here".to_string());
+        }
+        lines.push(String::new());
+        lines.push("c to commit, r to roll back, Esc to keep editing".to_string());
 
-            let notification_widget = Paragraph::new(display_text)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .border_style(Style::default()
-                            .fg(border_color)
-                            .add_modifier(Modifier::BOLD))
-                )
-                .style(Style::default()
-                    .fg(Color::White)
-                    .bg(Color::Black)
-                    .add_modifier(Modifier::BOLD))
-                .alignment(Alignment::Center);
+        let popup = Paragraph::new(lines.join("\n"))
+            .block(
+                Block::default()
+                    .title("Pending Changes")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD));
 
-            // Render the notification with opaque black background
-            f.render_widget(notification_widget, notification_area);
-        }
+        f.render_widget(popup, popup_area);
     }
 
-    fn render_database_name_input(&self, f: &mut Frame, area: Rect) {
+    fn render_quit_confirmation(&self, f: &mut Frame, area: Rect) {
         // Create centered popup
-        let popup_width = 50;
-        let popup_height = 5;
+        let popup_width = 60;
+        let popup_height = 9;
         let x = (area.width.saturating_sub(popup_width)) / 2;
         let y = (area.height.saturating_sub(popup_height)) / 2;
-
         let popup_area = Rect {
             x,
             y,
@@ -2452,21 +6338,20 @@ impl App {
             height: popup_height,
         };
 
+        let (database, count) = self.state.transaction.as_ref()
+            .map(|transaction| (transaction.database.as_str(), transaction.statements.len()))
+            .unwrap_or(("unknown", 0));
+
         let content = format!(
-            "Create New Database\n\nName: {}\n\nPress Enter to create, Esc to cancel",
-            if self.state.new_database_name.is_empty() {
-                "_"
-            } else {
-                &self.state.new_database_name
-            }
+            "⚠️  Uncommitted Changes\n\n{count} staged change(s) on '{database}' are not committed.\n\nc to commit and quit\nr to roll back and quit\nEsc to keep editing"
         );
 
         let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Database Name")
+                    .title("Quit")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
             )
             .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
             .alignment(Alignment::Center);
@@ -2474,13 +6359,58 @@ impl App {
         f.render_widget(popup, popup_area);
     }
 
-    fn render_save_filename_input(&self, f: &mut Frame, area: Rect) {
-        // Create centered popup
-        let popup_width = 60;
-        let popup_height = 6;
+    fn render_row_form(&self, f: &mut Frame, area: Rect, form: &crate::app::state::RowForm) {
+        // Create centered popup sized to the column list
+        let popup_width = 90.min(area.width);
+        let popup_height = (form.columns.len() as u16 + 6).min(area.height);
         let x = (area.width.saturating_sub(popup_width)) / 2;
         let y = (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        // Keep the selected field in view when there are more columns than lines
+        let visible_fields = popup_height.saturating_sub(6) as usize;
+        let first_field = form.selected.saturating_sub(visible_fields.saturating_sub(1));
+
+        let mut lines = vec![format!("Insert into '{}'", form.table_name), String::new()];
+        for (i, (column, value)) in form.columns.iter().zip(&form.values).enumerate().skip(first_field).take(visible_fields) {
+            let marker = if i == form.selected { ">" } else { " " };
+            let nullability = if column.is_nullable { "NULL" } else { "NOT NULL" };
+            let default = column.default_value.as_ref()
+                .map(|default| format!(" default {default}"))
+                .unwrap_or_default();
+            let shown_value = if value.is_empty() { "(default)" } else { value.as_str() };
+            lines.push(format!(
+                "{marker} {} {} {nullability}{default}: {shown_value}",
+                column.name, column.data_type
+            ));
+        }
+        lines.push(String::new());
+        lines.push("↑/↓ move between fields, empty = column default, NULL = null".to_string());
+        lines.push("Enter to insert, Esc to cancel".to_string());
+
+        let popup = Paragraph::new(lines.join("\n"))
+            .block(
+                Block::default()
+                    .title("Insert Row")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD));
+
+        f.render_widget(popup, popup_area);
+    }
 
+    fn render_sample_spec_input(&self, f: &mut Frame, area: Rect) {
+        // Create centered popup
+        let popup_width = 70;
+        let popup_height = 9;
+        let x = (area.width.saturating_sub(popup_width)) / 2;
+        let y = (area.height.saturating_sub(popup_height)) / 2;
         let popup_area = Rect {
             x,
             y,
@@ -2488,25 +6418,26 @@ impl App {
             height: popup_height,
         };
 
-        let current_db = self.database_manager.get_current_database().unwrap_or("none");
-        let display_filename = if self.state.save_filename.is_empty() {
+        let unknown_table = "unknown".to_string();
+        let current_table = self.state.selected_table.as_ref().unwrap_or(&unknown_table);
+        let display_spec = if self.state.sample_spec_input.is_empty() {
             "_"
         } else {
-            &self.state.save_filename
+            &self.state.sample_spec_input
         };
 
         let content = format!(
-            "Save Database '{}' to File\n\nFilename: {}\n\n(.db extension will be added if not present)\nPress Enter to save, Esc to cancel",
-            current_db,
-            display_filename
+            "Sample Table '{}'\n\nSample: {}\n\nSize as percent or rows, optional method and seed\ne.g. 10%  |  5000 reservoir  |  1% bernoulli 42\nEmpty input disables sampling. Enter to apply, Esc to cancel",
+            current_table,
+            display_spec
         );
 
         let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Save Database")
+                    .title("Sampling Mode")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                    .border_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             )
             .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
             .alignment(Alignment::Center);
@@ -2514,48 +6445,56 @@ impl App {
         f.render_widget(popup, popup_area);
     }
 
-    fn render_delete_confirmation(&self, f: &mut Frame, area: Rect) {
-        // Create centered popup
+    fn render_layout_picker(&self, f: &mut Frame, area: Rect) {
+        let names = self.get_current_layout_names();
+
+        // Create centered popup sized to the number of layouts
         let popup_width = 60;
-        let popup_height = 6;
+        let popup_height = (names.len() as u16 + 4).clamp(6, 16);
         let x = (area.width.saturating_sub(popup_width)) / 2;
         let y = (area.height.saturating_sub(popup_height)) / 2;
-
         let popup_area = Rect {
             x,
             y,
-            width: popup_width,
-            height: popup_height,
+            width: popup_width.min(area.width),
+            height: popup_height.min(area.height),
         };
 
-        let (item_type, item_name) = match &self.state.delete_confirmation {
-            crate::app::state::DeleteConfirmationState::Database(name) => ("database", name.as_str()),
-            crate::app::state::DeleteConfirmationState::Table(name) => ("table", name.as_str()),
-            _ => ("item", "unknown"),
-        };
+        let unknown_table = "unknown".to_string();
+        let current_table = self.state.selected_table.as_ref().unwrap_or(&unknown_table);
 
-        let content = format!(
-            "⚠️  Delete Confirmation\n\nDelete {} '{}'?\nThis action cannot be undone!\n\nPress 'd' to confirm, Esc to cancel",
-            item_type, item_name
-        );
+        let items: Vec<ListItem> = if names.is_empty() {
+            vec![ListItem::new("  No saved layouts for this table").style(Style::default().fg(Color::DarkGray))]
+        } else {
+            names.iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let style = if i == self.state.layout_picker_index {
+                        Style::default().fg(Color::Yellow).bg(Color::Blue).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::White).bg(Color::Black)
+                    };
+                    ListItem::new(format!("  {name}")).style(style)
+                })
+                .collect()
+        };
 
-        let popup = Paragraph::new(content)
+        let list = List::new(items)
             .block(
                 Block::default()
-                    .title("Confirm Delete")
+                    .title(format!("Layouts: {current_table} (Enter=Apply n=Save current d=Delete Esc=Close)"))
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                    .border_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
             )
-            .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
-            .alignment(Alignment::Center);
+            .style(Style::default().fg(Color::White).bg(Color::Black));
 
-        f.render_widget(popup, popup_area);
+        f.render_widget(list, popup_area);
     }
 
-    fn render_view_name_input(&self, f: &mut Frame, area: Rect) {
+    fn render_layout_name_input(&self, f: &mut Frame, area: Rect) {
         // Create centered popup
         let popup_width = 60;
-        let popup_height = 8;
+        let popup_height = 7;
         let x = (area.width.saturating_sub(popup_width)) / 2;
         let y = (area.height.saturating_sub(popup_height)) / 2;
         let popup_area = Rect {
@@ -2567,37 +6506,24 @@ impl App {
 
         let unknown_table = "unknown".to_string();
         let current_table = self.state.selected_table.as_ref().unwrap_or(&unknown_table);
-        let display_view_name = if self.state.new_view_name.is_empty() {
+        let display_layout_name = if self.state.new_layout_name.is_empty() {
             "_"
         } else {
-            &self.state.new_view_name
-        };
-
-        // Generate a preview of the SQL
-        let sql_preview = if let Some(sql) = self.state.generate_view_sql(current_table) {
-            let truncated = if sql.len() > 100 {
-                format!("{}...", &sql[..97])
-            } else {
-                sql
-            };
-            format!("\nSQL: {truncated}")
-        } else {
-            "\nSQL: SELECT * FROM table".to_string()
+            &self.state.new_layout_name
         };
 
         let content = format!(
-            "Save View from Table '{}'\n\nView name: {}{}\n\nPress Enter to create view, Esc to cancel",
+            "Save Layout for Table '{}'\n\nLayout name: {}\n\nPress Enter to save (replaces same name), Esc to cancel",
             current_table,
-            display_view_name,
-            sql_preview
+            display_layout_name
         );
 
         let popup = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title("Create View")
+                    .title("Save Layout")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)),
+                    .border_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
             )
             .style(Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD))
             .alignment(Alignment::Center);
@@ -2605,10 +6531,10 @@ impl App {
         f.render_widget(popup, popup_area);
     }
 
-    fn render_database_dropdown_overlay(&self, f: &mut Frame, area: Rect) {
+    fn render_database_dropdown_overlay(&self, f: &mut Frame, area: Rect) -> Option<Rect> {
         let databases = self.database_manager.get_databases();
         if databases.is_empty() {
-            return;
+            return None;
         }
 
         // Calculate dropdown area - positioned over the database dropdown widget
@@ -2697,6 +6623,30 @@ impl App {
 
         // Render with opaque black background to cover underlying widgets
         f.render_widget(dropdown_list, dropdown_area);
+        Some(dropdown_area)
+    }
+}
+
+// Style for a cell of a diff result: changed cells stand out, missing rows are colored by side
+fn diff_cell_style(data: &crate::db::query::QueryResult, row: &[String], column_name: &str) -> Style {
+    use crate::db::diff::{CHANGED_COLUMNS_COLUMN, DIFF_STATUS_COLUMN, ONLY_LEFT, ONLY_RIGHT};
+
+    let value_of = |name: &str| data.columns.iter()
+        .position(|column| column == name)
+        .and_then(|index| row.get(index));
+
+    let base_name = column_name.strip_suffix("_left")
+        .or_else(|| column_name.strip_suffix("_right"));
+    let is_changed = base_name.zip(value_of(CHANGED_COLUMNS_COLUMN))
+        .is_some_and(|(base, changed)| changed.split(", ").any(|name| name == base));
+    if is_changed {
+        return Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    }
+
+    match value_of(DIFF_STATUS_COLUMN).map(|status| status.as_str()) {
+        Some(ONLY_LEFT) => Style::default().fg(Color::Red),
+        Some(ONLY_RIGHT) => Style::default().fg(Color::Green),
+        _ => Style::default(),
     }
 }
 
@@ -0,0 +1,270 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+
+use super::App;
+use crate::app::state::{InspectSection, NavigationPanel};
+
+// Number of rows moved per scroll wheel notch
+const SCROLL_STEP: usize = 3;
+
+/// Screen areas from the last rendered frame, used to route mouse events
+#[derive(Debug, Default, Clone)]
+pub(super) struct MouseRegions {
+    pub database_dropdown: Option<Rect>,
+    pub database_overlay: Option<Rect>,
+    pub table_list: Option<Rect>,
+    pub table_viewer: Option<Rect>,
+    pub split_pane: Option<Rect>,
+    pub inspect_schema: Option<Rect>,
+    pub inspect_stats: Option<Rect>,
+    pub table_grid: Option<TableGridGeometry>,
+    pub tab_labels: Vec<Rect>,
+}
+
+/// Where the data grid placed its header, columns and rows on screen
+#[derive(Debug, Default, Clone)]
+pub(super) struct TableGridGeometry {
+    pub header_y: u16,
+    pub columns: Vec<GridColumn>,
+    // One entry per screen line: (y, data row index). Wrapped rows span several lines.
+    pub row_lines: Vec<(u16, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct GridColumn {
+    pub name: String,
+    pub x: u16,
+    pub width: u16,
+}
+
+/// An in-progress column resize started by pressing on a column border
+#[derive(Debug, Clone)]
+pub(super) struct ColumnDrag {
+    pub column_name: String,
+    pub start_x: u16,
+    pub start_width: u16,
+}
+
+fn contains(area: Option<Rect>, column: u16, row: u16) -> bool {
+    area.is_some_and(|area| {
+        column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
+    })
+}
+
+impl App {
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        // The file browser popup only supports scrolling its list
+        if self.show_file_browser {
+            let key = match mouse.kind {
+                MouseEventKind::ScrollUp => KeyCode::Up,
+                MouseEventKind::ScrollDown => KeyCode::Down,
+                _ => return,
+            };
+            if let Some(ref mut browser) = self.file_browser {
+                let _ = browser.handle_key(KeyEvent::from(key));
+            }
+            return;
+        }
+
+        // Ignore the mouse while a popup is waiting for keyboard input
+        if self.is_popup_open() {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.handle_mouse_click(mouse.column, mouse.row),
+            MouseEventKind::Drag(MouseButton::Left) => self.handle_mouse_drag(mouse.column),
+            MouseEventKind::Up(MouseButton::Left) => self.finish_column_drag(),
+            MouseEventKind::ScrollUp => self.handle_mouse_scroll(mouse.column, mouse.row, true),
+            MouseEventKind::ScrollDown => self.handle_mouse_scroll(mouse.column, mouse.row, false),
+            _ => {}
+        }
+    }
+
+    fn is_popup_open(&self) -> bool {
+        self.is_text_input_active()
+            || self.state.is_picking_layout
+            || self.state.is_delete_confirmation_active()
+            || self.state.is_creating_table
+            || self.state.show_pending_changes
+            || self.state.is_confirming_quit
+            || self.state.pending_bulk_change.is_some()
+            || self.state.pending_column_change.is_some()
+            || self.state.index_manager.is_some()
+            || self.state.reverse_references.is_some()
+            || self.state.storage_breakdown.is_some()
+            || self.state.definition_view.is_some()
+            || self.state.schema_diff.is_some()
+    }
+
+    fn handle_mouse_click(&mut self, column: u16, row: u16) {
+        let regions = self.mouse_regions.clone();
+
+        // The database dropdown overlay sits on top of everything else
+        if self.state.database_dropdown_expanded {
+            if let Some(overlay) = regions.database_overlay {
+                if contains(Some(overlay), column, row) {
+                    let index = row.saturating_sub(overlay.y + 1) as usize;
+                    if row > overlay.y && index < self.database_manager.get_databases().len() {
+                        self.state.dropdown_selected_index = index;
+                        self.handle_enter();
+                    }
+                    return;
+                }
+            }
+            // Clicking anywhere else closes the dropdown without changes
+            self.state.collapse_database_dropdown();
+            self.state.set_dropdown_to_current_database(self.selected_db_index);
+            if contains(regions.database_dropdown, column, row) {
+                return;
+            }
+        }
+
+        if let Some(index) = regions.tab_labels.iter().position(|area| contains(Some(*area), column, row)) {
+            if !self.state.is_modifying {
+                let database = self.state.switch_to_tab(index);
+                self.activate_tab_database(database);
+            }
+            return;
+        }
+
+        if self.state.inspect_mode {
+            self.handle_inspect_click(&regions, column, row);
+            return;
+        }
+
+        if contains(regions.database_dropdown, column, row) {
+            self.state.set_left_panel(NavigationPanel::DatabaseList);
+            self.handle_enter();
+        } else if let Some(list_area) = regions.table_list.filter(|area| contains(Some(*area), column, row)) {
+            self.state.set_left_panel(NavigationPanel::TableList);
+            let index = row.saturating_sub(list_area.y + 1) as usize;
+            let rows = self.sidebar_rows();
+            if let Some(clicked) = rows.get(index).filter(|_| row > list_area.y) {
+                self.select_sidebar_row(clicked);
+                self.handle_enter();
+            }
+        } else if contains(regions.table_viewer, column, row) {
+            self.state.set_active_panel(NavigationPanel::MainContent);
+            if let Some(grid) = regions.table_grid {
+                self.handle_grid_click(&grid, column, row);
+            }
+        } else if contains(regions.split_pane, column, row) {
+            // Clicking the other pane of a split moves focus to it
+            self.state.set_active_panel(NavigationPanel::MainContent);
+            if let Some(partner) = self.state.split_tab.filter(|_| !self.state.is_modifying) {
+                let database = self.state.switch_to_tab(partner);
+                self.activate_tab_database(database);
+            }
+        }
+    }
+
+    fn handle_grid_click(&mut self, grid: &TableGridGeometry, column: u16, row: u16) {
+        if row == grid.header_y {
+            // Pressing on the gap right after a column starts a resize
+            if let Some(col) = grid.columns.iter().find(|col| column == col.x + col.width) {
+                if !self.state.is_modifying {
+                    self.state.begin_view_change();
+                    self.column_drag = Some(ColumnDrag {
+                        column_name: col.name.clone(),
+                        start_x: column,
+                        start_width: col.width,
+                    });
+                }
+                return;
+            }
+
+            // Clicking a header cycles its sort: none -> ascending -> descending -> none
+            if let Some(col) = grid.columns.iter().find(|col| column >= col.x && column < col.x + col.width) {
+                self.state.selected_column = Some(col.name.clone());
+                if self.can_change_view() {
+                    let ascending = !self.state.is_column_in_sort_chain(&col.name);
+                    self.state.begin_view_change();
+                    self.state.toggle_in_sort_chain(ascending);
+                    self.state.end_view_change();
+                    self.fetch_table_data_preserve_column();
+                }
+            }
+            return;
+        }
+
+        if let Some(&(_, row_index)) = grid.row_lines.iter().find(|(y, _)| *y == row) {
+            self.state.selected_row = row_index;
+        }
+        if let Some(col) = grid.columns.iter().find(|col| column >= col.x && column < col.x + col.width) {
+            self.state.selected_column = Some(col.name.clone());
+        }
+    }
+
+    fn handle_inspect_click(&mut self, regions: &MouseRegions, column: u16, row: u16) {
+        if let Some(schema_area) = regions.inspect_schema.filter(|area| contains(Some(*area), column, row)) {
+            self.state.inspect_active_section = InspectSection::Schema;
+            // Rows start below the top border and the header line
+            if row > schema_area.y + 1 {
+                let index = self.state.inspect_schema_scroll_y + (row - schema_area.y - 2) as usize;
+                if index < self.state.get_virtual_column_order().len() {
+                    self.state.inspect_selected_row = index;
+                }
+            }
+        } else if contains(regions.inspect_stats, column, row) {
+            self.state.inspect_active_section = InspectSection::Statistics;
+        }
+    }
+
+    fn handle_mouse_drag(&mut self, column: u16) {
+        let Some(drag) = self.column_drag.clone() else {
+            return;
+        };
+        let width = (drag.start_width as i32 + column as i32 - drag.start_x as i32).max(0) as usize;
+        self.state.set_column_width(&drag.column_name, width);
+    }
+
+    fn finish_column_drag(&mut self) {
+        if self.column_drag.take().is_some() {
+            self.state.end_view_change();
+        }
+    }
+
+    fn handle_mouse_scroll(&mut self, column: u16, row: u16, up: bool) {
+        let regions = self.mouse_regions.clone();
+
+        if self.state.database_dropdown_expanded && contains(regions.database_overlay, column, row) {
+            for _ in 0..SCROLL_STEP {
+                if up {
+                    self.state.dropdown_move_up();
+                } else {
+                    self.state.dropdown_move_down(self.database_manager.get_databases().len());
+                }
+            }
+            return;
+        }
+
+        if self.state.inspect_mode {
+            if contains(regions.inspect_schema, column, row) {
+                self.state.inspect_active_section = InspectSection::Schema;
+            } else if contains(regions.inspect_stats, column, row) {
+                self.state.inspect_active_section = InspectSection::Statistics;
+            } else {
+                return;
+            }
+        } else if contains(regions.table_list, column, row) {
+            self.state.set_left_panel(NavigationPanel::TableList);
+        } else if contains(regions.table_viewer, column, row) {
+            self.state.set_active_panel(NavigationPanel::MainContent);
+        } else {
+            return;
+        }
+
+        // Scrolling behaves like repeated vim navigation, outside of modify mode
+        if self.state.is_modifying {
+            return;
+        }
+        for _ in 0..SCROLL_STEP {
+            if up {
+                self.handle_vim_up();
+            } else {
+                self.handle_vim_down();
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+const KEYWORDS: &[&str] = &[
+    "ALTER", "AND", "AS", "ASC", "BY", "CASCADE", "CASE", "CHECK", "COLUMN", "COMMENT", "CONSTRAINT",
+    "CREATE", "CROSS", "CYCLE", "DEFAULT", "DELETE", "DESC", "DISTINCT", "DROP", "ELSE", "END", "EXISTS",
+    "FOREIGN", "FROM", "FULL", "GROUP", "HAVING", "IF", "IN", "INCREMENT", "INDEX", "INNER", "INSERT",
+    "INTO", "IS", "JOIN", "KEY", "LEFT", "LIKE", "LIMIT", "MACRO", "MAXVALUE", "MINVALUE", "NO", "NOT",
+    "NULL", "ON", "OR", "ORDER", "OUTER", "PRIMARY", "REFERENCES", "REPLACE", "RIGHT", "SCHEMA", "SELECT",
+    "SEQUENCE", "SET", "START", "TABLE", "THEN", "TYPE", "UNION", "UNIQUE", "UPDATE", "USING", "VALUES",
+    "VIEW", "WHEN", "WHERE", "WITH",
+];
+
+/// Splits SQL into styled spans: keywords, string literals, quoted identifiers, numbers and comments
+pub(super) fn highlight_sql(sql: &str) -> Vec<Line<'static>> {
+    sql.lines().map(highlight_line).collect()
+}
+
+fn highlight_line(line: &str) -> Line<'static> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        let style = if c == '-' && chars.get(i + 1) == Some(&'-') {
+            i = chars.len();
+            Style::default().fg(Color::DarkGray)
+        } else if c == '\'' || c == '"' {
+            // Doubled quotes inside a literal escape the quote
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == c && chars.get(i + 1) == Some(&c) {
+                    i += 2;
+                } else if chars[i] == c {
+                    i += 1;
+                    break;
+                } else {
+                    i += 1;
+                }
+            }
+            Style::default().fg(if c == '\'' { Color::Green } else { Color::Cyan })
+        } else if c.is_alphanumeric() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if word.chars().all(|c| c.is_ascii_digit()) {
+                Style::default().fg(Color::Magenta)
+            } else if KEYWORDS.contains(&word.to_uppercase().as_str()) {
+                Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        } else {
+            i += 1;
+            Style::default().fg(Color::White)
+        };
+        spans.push(Span::styled(chars[start..i].iter().collect::<String>(), style));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_sql() {
+        let lines = highlight_sql("CREATE VIEW \"it's\" AS SELECT 'a''b', 42 -- note\nFROM t");
+        assert_eq!(lines.len(), 2);
+
+        let spans = &lines[0].spans;
+        let style_of = |text: &str| spans.iter().find(|span| span.content == text).unwrap().style;
+        assert_eq!(style_of("CREATE").fg, Some(Color::Blue));
+        assert_eq!(style_of("\"it's\"").fg, Some(Color::Cyan));
+        assert_eq!(style_of("'a''b'").fg, Some(Color::Green));
+        assert_eq!(style_of("42").fg, Some(Color::Magenta));
+        assert_eq!(style_of("-- note").fg, Some(Color::DarkGray));
+
+        // Nothing is lost from the original text
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, "CREATE VIEW \"it's\" AS SELECT 'a''b', 42 -- note");
+    }
+}
@@ -1,8 +1,13 @@
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::actions::{Action, ActionLogger, DatabaseType};
-use crate::app::state::ApplicationState;
+use crate::app::state::{ApplicationState, BulkChange, BulkChangeKind, PendingTransaction, RowForm};
+use crate::db::alter::{self, ColumnChange};
+use crate::db::diff::{DiffResult, TableDiff};
+use crate::db::edit;
+use crate::db::index;
+use crate::db::schema::{qualified_table_name, split_schema_name, split_table_name, DEFAULT_SCHEMA};
 use crate::db::DatabaseManager;
 use crate::import::ImportWorkflows;
 
@@ -104,13 +109,70 @@ impl<'a> DatabaseWorkflows<'a> {
             ));
         }
 
+        if self.state.transaction.as_ref().is_some_and(|transaction| transaction.database == db_name) {
+            return Err(anyhow::anyhow!("Commit or roll back the open transaction on '{db_name}' first"));
+        }
+
         self.database_manager.remove_database(&db_name)?;
         self.state.show_info(format!("Disconnected from: {db_name}"));
-        self.state.selected_database = None;
-        self.state.selected_table = None;
+        self.state.close_tabs_for_database(&db_name);
+        if self.state.tabs.is_empty() {
+            self.state.selected_database = None;
+            self.state.selected_table = None;
+        }
         Ok(())
     }
 
+    /// ATTACH a database file into the current database under an alias
+    pub fn attach_database(&mut self, path: PathBuf, alias: String, read_only: bool) -> Result<()> {
+        let database_name = self.database_manager.get_current_database()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?
+            .to_string();
+        let path_str = path.to_string_lossy().to_string();
+        let action = Action::AttachDatabase {
+            database_name,
+            path: path_str.clone(),
+            alias: alias.clone(),
+            read_only,
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.database_manager.attach_database(&path_str, &alias, read_only);
+        if result.is_ok() {
+            self.state.show_success(format!("Attached '{path_str}' as '{alias}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// DETACH an attached database, closing the views of its tables
+    pub fn detach_database(&mut self, alias: String) -> Result<()> {
+        let database_name = self.database_manager.get_current_database()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?
+            .to_string();
+        if self.state.transaction.as_ref().is_some_and(|transaction| transaction.database == database_name) {
+            return Err(anyhow::anyhow!("Commit or roll back the open transaction on '{database_name}' first"));
+        }
+        let action = Action::DetachDatabase {
+            database_name,
+            alias: alias.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.database_manager.detach_database(&alias);
+        if result.is_ok() {
+            let viewing_detached_table = self.state.selected_table.as_deref()
+                .is_some_and(|table| split_schema_name(split_table_name(table).0).0.as_deref() == Some(alias.as_str()));
+            if viewing_detached_table {
+                self.state.table_data = None;
+                self.state.selected_table = None;
+            }
+            self.state.show_info(format!("Detached '{alias}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
     /// Select a database
     pub fn select_database(&mut self, db_name: String) -> Result<()> {
         let action = Action::SelectDatabase {
@@ -144,10 +206,786 @@ impl<'a> DatabaseWorkflows<'a> {
     }
 
     fn execute_select_table(&mut self, table_name: String) -> Result<()> {
+        // Only the selected table's size is read from storage; views and query results have none
+        if !self.state.is_query_result(&table_name) {
+            let _ = self.database_manager.load_table_storage(&table_name);
+        }
         self.state.select_table(table_name);
         Ok(())
     }
 
+    /// Run a query and show its result in a new tab
+    pub fn open_query_in_new_tab(&mut self, sql: String) -> Result<()> {
+        let action = Action::ExecuteQuery { query: sql.clone() };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_open_query_in_new_tab(sql);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_open_query_in_new_tab(&mut self, sql: String) -> Result<()> {
+        let sql = sql.trim().trim_end_matches(';').to_string();
+        let connection = self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?;
+
+        // Only queries that produce rows can be shown in the viewer
+        connection.prepare(&format!("SELECT * FROM ({sql}) LIMIT 0"))
+            .map_err(|e| anyhow::anyhow!("Invalid query: {e}"))?;
+
+        self.state.open_new_tab();
+        let name = self.state.add_query_source("query", sql);
+        self.state.select_table(name);
+        Ok(())
+    }
+
+    /// Run a CREATE/ALTER/DROP/COMMENT statement from the query editor, e.g. an edited view definition
+    pub fn execute_definition(&mut self, statement: String) -> Result<()> {
+        let action = Action::ExecuteDefinition { statement: statement.clone() };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&statement);
+        if result.is_ok() {
+            self.state.show_success("Definition executed".to_string());
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// Write the CREATE statement of a catalog object to a .sql file
+    pub fn export_definition(&mut self, object_name: String, sql: &str, file_path: PathBuf) -> Result<()> {
+        let result = self.write_sql_file(object_name.clone(), sql, &file_path);
+        if result.is_ok() {
+            self.state.show_success(format!("Saved the definition of '{object_name}' to '{}'", file_path.display()));
+        }
+        result
+    }
+
+    /// Write a schema diff's migration script to a .sql file
+    pub fn export_migration_script(&mut self, source: String, script: &str, file_path: PathBuf) -> Result<()> {
+        let result = self.write_sql_file(source, script, &file_path);
+        if result.is_ok() {
+            self.state.show_success(format!("Saved the migration script to '{}'", file_path.display()));
+        }
+        result
+    }
+
+    fn write_sql_file(&mut self, source_name: String, sql: &str, file_path: &Path) -> Result<()> {
+        let action = Action::ExportData {
+            source_table: source_name,
+            destination_path: file_path.to_string_lossy().to_string(),
+            format: "SQL".to_string(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = std::fs::write(file_path, format!("{sql}\n"))
+            .map_err(|e| anyhow::anyhow!("Failed to write '{}': {e}", file_path.display()));
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// Compare the schemas of two databases ("name" or "name:catalog") and show the migration script
+    pub fn compare_schemas(&mut self, source: String, target: String) -> Result<()> {
+        let action = Action::CompareSchemas {
+            source: source.clone(),
+            target: target.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_compare_schemas(source, target);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_compare_schemas(&mut self, source: String, target: String) -> Result<()> {
+        use crate::db::schema_diff::{diff_schemas, migration_script, split_schema_source, SchemaSnapshot};
+
+        let load = |spec: &str| -> Result<SchemaSnapshot> {
+            let (database, catalog) = split_schema_source(spec);
+            let connection = self.database_manager.get_connection(database)
+                .ok_or_else(|| anyhow::anyhow!("Database '{database}' is not open"))?;
+            SchemaSnapshot::load(connection, catalog)
+                .map_err(|e| anyhow::anyhow!("Failed to read the schema of '{spec}': {e}"))
+        };
+        let source_snapshot = load(&source)?;
+        let target_snapshot = load(&target)?;
+
+        let changes = diff_schemas(&source_snapshot, &target_snapshot);
+        let script = migration_script(&changes, &source, &target);
+        if changes.is_empty() {
+            self.state.show_success(format!("The schemas of '{source}' and '{target}' are identical"));
+        } else {
+            self.state.show_success(format!("{} schema change(s) between '{source}' and '{target}'", changes.len()));
+        }
+        self.state.schema_diff = Some(crate::app::state::SchemaDiffView {
+            source,
+            target,
+            change_count: changes.len(),
+            script,
+            scroll: 0,
+            save_path: None,
+        });
+        Ok(())
+    }
+
+    /// Compare two tables, views or query results and show the differing rows in a new tab
+    pub fn compare_relations(&mut self, left: String, right: String, key_columns: Vec<String>) -> Result<()> {
+        let action = Action::CompareRelations {
+            left: left.clone(),
+            right: right.clone(),
+            key_columns: key_columns.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_compare_relations(left, right, key_columns);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_compare_relations(&mut self, left: String, right: String, key_columns: Vec<String>) -> Result<()> {
+        let connection = self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?;
+
+        // Query results from other tabs can be compared like tables
+        let left_source = self.state.get_source_sql(&left);
+        let right_source = self.state.get_source_sql(&right);
+        let diff = TableDiff::new(connection, &left_source, &right_source, key_columns)?;
+        let summary = diff.summary(connection)?;
+
+        self.state.open_new_tab();
+        let name = self.state.add_query_source("diff", diff.diff_sql());
+        self.state.diff_results.insert(name.clone(), DiffResult { left, right, summary });
+        self.state.select_table(name);
+        self.state.show_success(summary.describe());
+        Ok(())
+    }
+
+    /// Write the current view (visible columns, filters and sort) to a CSV, Parquet or JSON file
+    pub fn export_current_view(&mut self, file_path: PathBuf) -> Result<()> {
+        let source_table = self.state.selected_table.clone()
+            .ok_or_else(|| anyhow::anyhow!("No table selected to export"))?;
+        let format = match file_path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()) {
+            Some(ext) if ext == "csv" => "CSV",
+            Some(ext) if ext == "parquet" => "PARQUET",
+            Some(ext) if ext == "json" => "JSON",
+            _ => return Err(anyhow::anyhow!("Export file must end in .csv, .parquet or .json")),
+        };
+
+        let action = Action::ExportData {
+            source_table: source_table.clone(),
+            destination_path: file_path.to_string_lossy().to_string(),
+            format: format.to_string(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_export_current_view(source_table, file_path, format);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_export_current_view(&mut self, source_table: String, file_path: PathBuf, format: &str) -> Result<()> {
+        let connection = self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?;
+        let sql = self.state.generate_view_sql(&source_table)
+            .ok_or_else(|| anyhow::anyhow!("Failed to generate SQL for current view state"))?;
+
+        let path = file_path.to_string_lossy().replace('\'', "''");
+        let options = match format {
+            "CSV" => "FORMAT CSV, HEADER".to_string(),
+            other => format!("FORMAT {other}"),
+        };
+        connection.execute_batch(&format!("COPY ({sql}) TO '{path}' ({options})"))?;
+
+        self.state.show_success(format!("Exported '{}' to '{}'", source_table, file_path.display()));
+        Ok(())
+    }
+
+    /// Set one cell of a loaded row, returning the row's key afterwards (it changes when a key column is edited)
+    pub fn update_cell(&mut self, row_index: usize, column_name: String, new_value: Option<String>) -> Result<Vec<String>> {
+        let table_name = self.state.selected_table.clone()
+            .ok_or_else(|| anyhow::anyhow!("No table selected"))?;
+        let key = self.state.row_key.clone()
+            .ok_or_else(|| anyhow::anyhow!("'{table_name}' is not an editable table"))?;
+        let key_values = self.state.row_key_values.get(row_index).cloned()
+            .ok_or_else(|| anyhow::anyhow!("No row selected"))?;
+
+        let action = Action::UpdateCell {
+            table_name: table_name.clone(),
+            column_name: column_name.clone(),
+            row_key: key.describe(&key_values),
+            new_value: new_value.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_update_cell(&table_name, &column_name, &key, key_values, new_value);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_update_cell(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        key: &edit::RowKey,
+        mut key_values: Vec<String>,
+        new_value: Option<String>,
+    ) -> Result<Vec<String>> {
+        let connection = self.writable_connection()?;
+        let data_type = edit::column_type(connection, table_name, column_name)?;
+        edit::validate_value(connection, new_value.as_deref(), &data_type)?;
+
+        // Editing a key column moves the row to a new key
+        let new_key_values = match (key.columns.iter().position(|column| column == column_name), &new_value) {
+            (Some(position), Some(value)) => {
+                let mut values = key_values.clone();
+                values[position] = connection.query_row(
+                    &format!("SELECT CAST(CAST(? AS {data_type}) AS VARCHAR)"),
+                    [value],
+                    |row| row.get::<_, String>(0),
+                )?;
+                values
+            }
+            _ => key_values.clone(),
+        };
+
+        let (sql, params) = edit::update_cell_sql(table_name, column_name, &data_type, key, &key_values, new_value.as_deref());
+        let updated = self.run_change(&sql, params)?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("Row no longer exists in '{table_name}'"));
+        }
+        key_values = new_key_values;
+
+        if let Some(transaction) = self.staged_transaction() {
+            transaction.modified_cells.insert((table_name.to_string(), key_values.clone(), column_name.to_string()));
+            self.state.show_success(format!("Staged update of '{column_name}' in '{table_name}'"));
+        } else {
+            self.state.show_success(format!("Updated '{column_name}' in '{table_name}'"));
+        }
+        Ok(key_values)
+    }
+
+    /// Insert the row described by the insert form
+    pub fn insert_row(&mut self, form: RowForm) -> Result<()> {
+        // Empty fields are left out so the column default applies
+        let values: Vec<(&crate::db::query::ColumnInfo, Option<String>)> = form.columns.iter()
+            .zip(&form.values)
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(column, value)| (column, edit::parse_input_value(value)))
+            .collect();
+
+        let action = Action::InsertRow {
+            table_name: form.table_name.clone(),
+            columns: values.iter().map(|(column, _)| column.name.clone()).collect(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_insert_row(&form.table_name, &values);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_insert_row(&mut self, table_name: &str, values: &[(&crate::db::query::ColumnInfo, Option<String>)]) -> Result<()> {
+        let connection = self.writable_connection()?;
+        for (column, value) in values {
+            if value.is_none() && !column.is_nullable {
+                return Err(anyhow::anyhow!("'{}' cannot be NULL", column.name));
+            }
+            edit::validate_value(connection, value.as_deref(), &column.data_type)
+                .map_err(|e| anyhow::anyhow!("{}: {e}", column.name))?;
+        }
+
+        let (sql, params) = edit::insert_row_sql(table_name, values);
+        if !self.is_staging() {
+            connection.execute(&sql, duckdb::params_from_iter(params))?;
+            self.state.show_success(format!("Inserted row into '{table_name}'"));
+            return Ok(());
+        }
+
+        // Inside a transaction, tables with a primary key report the new row's key so it can be marked
+        let key = edit::RowKey::for_table(connection, table_name)?;
+        match key.returning_sql() {
+            Some(returning) => {
+                let row_key = self.run_change_returning(&sql, &returning, params, key.columns.len())?;
+                if let Some(transaction) = self.staged_transaction() {
+                    transaction.inserted_rows.insert((table_name.to_string(), row_key));
+                }
+            }
+            None => {
+                self.run_change(&sql, params)?;
+            }
+        }
+        self.state.show_success(format!("Staged insert into '{table_name}'"));
+        Ok(())
+    }
+
+    /// Delete loaded rows of the selected table, addressing them by their keys
+    pub fn delete_rows(&mut self, table_name: String, row_indices: &[usize]) -> Result<usize> {
+        let key = self.state.row_key.clone()
+            .ok_or_else(|| anyhow::anyhow!("'{table_name}' is not an editable table"))?;
+        let key_values: Vec<Vec<String>> = row_indices.iter()
+            .filter_map(|&row| self.state.row_key_values.get(row).cloned())
+            .collect();
+
+        let action = Action::DeleteRows {
+            table_name: table_name.clone(),
+            row_keys: key_values.iter().map(|values| key.describe(values)).collect(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_delete_rows(&table_name, &key, &key_values);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_delete_rows(&mut self, table_name: &str, key: &edit::RowKey, key_values: &[Vec<String>]) -> Result<usize> {
+        let connection = self.writable_connection()?;
+        let sql = edit::delete_row_sql(table_name, key);
+
+        if self.is_staging() {
+            let mut deleted = 0;
+            for values in key_values {
+                deleted += self.run_change(&sql, values.iter().cloned().map(Some).collect())?;
+            }
+            self.state.show_success(format!("Staged deletion of {deleted} row(s) from '{table_name}'"));
+            return Ok(deleted);
+        }
+
+        // All rows go or none do
+        connection.execute_batch("BEGIN TRANSACTION")?;
+        let mut deleted = 0;
+        for values in key_values {
+            match connection.execute(&sql, duckdb::params_from_iter(values)) {
+                Ok(count) => deleted += count,
+                Err(e) => {
+                    connection.execute_batch("ROLLBACK")?;
+                    return Err(e.into());
+                }
+            }
+        }
+        connection.execute_batch("COMMIT")?;
+
+        self.state.show_success(format!("Deleted {deleted} row(s) from '{table_name}'"));
+        Ok(deleted)
+    }
+
+    /// Count the rows a bulk change would touch, checking the statement before it can run
+    pub fn preview_bulk_change(&mut self, table_name: String, kind: BulkChangeKind) -> Result<BulkChange> {
+        let filter_clause = self.state.get_bulk_filter_clause()
+            .ok_or_else(|| anyhow::anyhow!("Apply a filter first; bulk changes act on the filtered rows"))?;
+        let connection = self.writable_connection()?;
+
+        // EXPLAIN binds the statement, so bad expressions fail here rather than mid-transaction
+        connection.execute_batch(&format!("EXPLAIN {}", Self::bulk_change_sql(&table_name, &kind, &filter_clause)))?;
+        let row_count = connection.query_row(
+            &edit::count_matching_sql(&table_name, &filter_clause),
+            [],
+            |row| row.get::<_, i64>(0),
+        )? as usize;
+
+        Ok(BulkChange {
+            table_name,
+            kind,
+            filter_clause,
+            row_count,
+        })
+    }
+
+    fn bulk_change_sql(table_name: &str, kind: &BulkChangeKind, filter_clause: &str) -> String {
+        match kind {
+            BulkChangeKind::Update { column_name, expression } => {
+                edit::bulk_update_sql(table_name, column_name, expression, filter_clause)
+            }
+            BulkChangeKind::Delete => edit::bulk_delete_sql(table_name, filter_clause),
+        }
+    }
+
+    /// Run a previewed bulk change
+    pub fn apply_bulk_change(&mut self, change: BulkChange) -> Result<usize> {
+        let action = match &change.kind {
+            BulkChangeKind::Update { column_name, expression } => Action::BulkUpdate {
+                table_name: change.table_name.clone(),
+                column_name: column_name.clone(),
+                expression: expression.clone(),
+                filter: change.filter_clause.clone(),
+                row_count: change.row_count,
+            },
+            BulkChangeKind::Delete => Action::BulkDelete {
+                table_name: change.table_name.clone(),
+                filter: change.filter_clause.clone(),
+                row_count: change.row_count,
+            },
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_bulk_change(&change);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_bulk_change(&mut self, change: &BulkChange) -> Result<usize> {
+        self.writable_connection()?;
+        let sql = Self::bulk_change_sql(&change.table_name, &change.kind, &change.filter_clause);
+        let count = self.run_change(&sql, Vec::new())?;
+
+        let verb = match change.kind {
+            BulkChangeKind::Update { .. } => "Updated",
+            BulkChangeKind::Delete => "Deleted",
+        };
+        let staged = if self.is_staging() { " (staged)" } else { "" };
+        self.state.show_success(format!("{verb} {count} row(s) in '{}'{staged}", change.table_name));
+        Ok(count)
+    }
+
+    /// Apply a schema change to one column of a table
+    pub fn alter_column(&mut self, table_name: String, column_name: String, change: ColumnChange) -> Result<()> {
+        let statement = change.alter_sql(&table_name, &column_name);
+
+        let action = match &change {
+            ColumnChange::Comment(comment) => Action::CommentOnColumn {
+                table_name: table_name.clone(),
+                column_name: column_name.clone(),
+                comment: comment.clone(),
+            },
+            _ => Action::AlterTable {
+                table_name: table_name.clone(),
+                statement: statement.clone(),
+            },
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_alter_column(&table_name, &column_name, &change, &statement);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_alter_column(&mut self, table_name: &str, column_name: &str, change: &ColumnChange, statement: &str) -> Result<()> {
+        // Column counts in the sidebar come from DatabaseInfo.tables
+        self.execute_table_statement(statement)?;
+
+        let (old, new) = change.renamed_columns(column_name);
+        self.state.apply_column_rename(table_name, old.as_deref(), new.as_deref());
+        self.state.show_success(format!("Altered '{table_name}': {statement}"));
+        Ok(())
+    }
+
+    /// Create a table from the designer's generated DDL
+    pub fn create_table(&mut self, table_name: String, statement: String) -> Result<()> {
+        let action = Action::CreateTable {
+            table_name: table_name.clone(),
+            statement: statement.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&statement);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// Rename a table or view within its schema, keeping its view state; returns the new name
+    pub fn rename_table(&mut self, table_name: String, new_name: String, is_view: bool) -> Result<String> {
+        let (schema, _) = split_table_name(&table_name);
+        let new_name = match new_name.rsplit_once('.') {
+            Some((new_schema, _)) if new_schema != schema => {
+                return Err(anyhow::anyhow!("'{table_name}' can only be renamed within schema '{schema}'"));
+            }
+            Some(_) => new_name,
+            None => qualified_table_name(schema, &new_name),
+        };
+        let action = Action::RenameTable {
+            table_name: table_name.clone(),
+            new_name: new_name.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&alter::rename_relation_sql(&table_name, &new_name, is_view));
+        if result.is_ok() {
+            // A rename inside a transaction is remembered so a rollback can undo it
+            if let Some(transaction) = self.staged_transaction() {
+                transaction.renamed_tables.push((table_name.clone(), new_name.clone()));
+            }
+            let current_db = self.database_manager.get_current_database().unwrap_or_default().to_string();
+            self.state.apply_table_rename(&current_db, &table_name, &new_name);
+            self.state.show_success(format!("Renamed '{table_name}' to '{new_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result.map(|()| new_name)
+    }
+
+    /// Copy a table or view into a new table, with or without its rows
+    pub fn duplicate_table(&mut self, table_name: String, new_name: String, with_data: bool) -> Result<()> {
+        let action = Action::DuplicateTable {
+            table_name: table_name.clone(),
+            new_name: new_name.clone(),
+            with_data,
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&alter::duplicate_table_sql(&table_name, &new_name, with_data));
+        if result.is_ok() {
+            self.state.show_success(format!("Duplicated '{table_name}' as '{new_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// Delete all rows of a table
+    pub fn truncate_table(&mut self, table_name: String) -> Result<()> {
+        let action = Action::TruncateTable {
+            table_name: table_name.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&alter::truncate_table_sql(&table_name));
+        if result.is_ok() {
+            self.state.show_success(format!("Truncated '{table_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// Set or, with None, remove the comment of a table or view
+    pub fn comment_on_table(&mut self, table_name: String, comment: Option<String>, is_view: bool) -> Result<()> {
+        let action = Action::CommentOnTable {
+            table_name: table_name.clone(),
+            comment: comment.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&alter::comment_on_table_sql(&table_name, is_view, comment.as_deref()));
+        if result.is_ok() {
+            self.state.show_success(format!("Updated the comment on '{table_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    pub fn create_schema(&mut self, schema_name: String) -> Result<()> {
+        let action = Action::CreateSchema {
+            schema_name: schema_name.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&alter::create_schema_sql(&schema_name));
+        if result.is_ok() {
+            self.state.show_success(format!("Created schema '{schema_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// Drop a schema with its tables, closing the view of any of them
+    pub fn drop_schema(&mut self, schema_name: String) -> Result<()> {
+        if split_schema_name(&schema_name).1 == DEFAULT_SCHEMA {
+            return Err(anyhow::anyhow!("The '{DEFAULT_SCHEMA}' schema can't be dropped"));
+        }
+        let action = Action::DropSchema {
+            schema_name: schema_name.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&alter::drop_schema_sql(&schema_name));
+        if result.is_ok() {
+            let viewing_dropped_table = self.state.selected_table.as_deref()
+                .is_some_and(|table| split_table_name(table).0 == schema_name);
+            if viewing_dropped_table {
+                self.state.table_data = None;
+                self.state.selected_table = None;
+            }
+            self.state.show_success(format!("Dropped schema '{schema_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    pub fn create_index(&mut self, table_name: String, index_name: String, columns: Vec<String>, unique: bool) -> Result<()> {
+        let statement = index::create_index_sql(&index_name, &table_name, &columns, unique);
+        let action = Action::CreateIndex {
+            table_name,
+            index_name: index_name.clone(),
+            columns,
+            unique,
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&statement);
+        if result.is_ok() {
+            self.state.show_success(format!("Created index '{index_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    pub fn drop_index(&mut self, table_name: String, index_name: String) -> Result<()> {
+        let statement = index::drop_index_sql(&index_name, &table_name);
+        let action = Action::DropIndex {
+            table_name,
+            index_name: index_name.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_table_statement(&statement);
+        if result.is_ok() {
+            self.state.show_success(format!("Dropped index '{index_name}'"));
+        }
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_table_statement(&mut self, statement: &str) -> Result<()> {
+        self.writable_connection()?;
+        self.run_change(statement, Vec::new())?;
+
+        let current_db = self.database_manager.get_current_database()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?
+            .to_string();
+        self.database_manager.refresh_database(&current_db)?;
+        // The inspect view reloads the changed schema on the next update
+        self.state.inspect_data = None;
+        Ok(())
+    }
+
+    fn writable_connection(&self) -> Result<&duckdb::Connection> {
+        let connection = self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?;
+        if edit::is_read_only(connection)? {
+            return Err(anyhow::anyhow!("Database is opened read-only"));
+        }
+        Ok(connection)
+    }
+
+    fn is_staging(&self) -> bool {
+        self.state.is_staging_edits(self.database_manager.get_current_database())
+    }
+
+    /// The open transaction, if edits to the current database are being staged
+    fn staged_transaction(&mut self) -> Option<&mut PendingTransaction> {
+        if !self.is_staging() {
+            return None;
+        }
+        self.state.transaction.as_mut()
+    }
+
+    /// Execute a data change, recording it when it is staged in a transaction
+    fn run_change(&mut self, sql: &str, params: Vec<Option<String>>) -> Result<usize> {
+        let connection = self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?;
+        let result = connection.execute(sql, duckdb::params_from_iter(&params));
+        self.record_change(sql, &params, result.is_ok())?;
+        Ok(result?)
+    }
+
+    fn run_change_returning(&mut self, sql: &str, returning: &str, params: Vec<Option<String>>, columns: usize) -> Result<Vec<String>> {
+        let connection = self.database_manager.get_current_connection()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?;
+        let result = connection.query_row(&format!("{sql}{returning}"), duckdb::params_from_iter(&params), |row| {
+            (0..columns).map(|i| row.get::<_, String>(i)).collect::<std::result::Result<Vec<_>, _>>()
+        });
+        self.record_change(sql, &params, result.is_ok())?;
+        Ok(result?)
+    }
+
+    fn record_change(&mut self, sql: &str, params: &[Option<String>], succeeded: bool) -> Result<()> {
+        let Some(transaction) = self.staged_transaction() else {
+            return Ok(());
+        };
+        if succeeded {
+            transaction.statements.push(edit::inline_params(sql, params));
+            Ok(())
+        } else {
+            transaction.aborted = true;
+            Err(anyhow::anyhow!("the transaction was aborted and can only be rolled back"))
+        }
+    }
+
+    /// Start staging viewer edits to the current database in a transaction
+    pub fn begin_transaction(&mut self) -> Result<()> {
+        let database_name = self.database_manager.get_current_database()
+            .ok_or_else(|| anyhow::anyhow!("No database currently selected"))?
+            .to_string();
+
+        let action = Action::BeginTransaction {
+            database_name: database_name.clone(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_begin_transaction(database_name);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_begin_transaction(&mut self, database_name: String) -> Result<()> {
+        if let Some(transaction) = &self.state.transaction {
+            return Err(anyhow::anyhow!("A transaction is already open on '{}'", transaction.database));
+        }
+        let connection = self.writable_connection()?;
+        connection.execute_batch("BEGIN TRANSACTION")?;
+        self.state.show_info(format!("Transaction mode on '{database_name}': edits are staged until committed"));
+        self.state.transaction = Some(PendingTransaction::new(database_name));
+        Ok(())
+    }
+
+    /// Commit the staged edits
+    pub fn commit_transaction(&mut self) -> Result<()> {
+        let transaction = self.state.transaction.clone()
+            .ok_or_else(|| anyhow::anyhow!("No transaction is open"))?;
+
+        let action = Action::CommitTransaction {
+            database_name: transaction.database.clone(),
+            statement_count: transaction.statements.len(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_end_transaction(&transaction, true);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    /// Discard the staged edits
+    pub fn rollback_transaction(&mut self) -> Result<()> {
+        let transaction = self.state.transaction.clone()
+            .ok_or_else(|| anyhow::anyhow!("No transaction is open"))?;
+
+        let action = Action::RollbackTransaction {
+            database_name: transaction.database.clone(),
+            statement_count: transaction.statements.len(),
+        };
+
+        let tracker = self.action_logger.start_action(action);
+        let result = self.execute_end_transaction(&transaction, false);
+        self.action_logger.complete_action(tracker, &result);
+        result
+    }
+
+    fn execute_end_transaction(&mut self, transaction: &PendingTransaction, commit: bool) -> Result<()> {
+        // Committing an aborted DuckDB transaction silently rolls it back
+        if commit && transaction.aborted {
+            return Err(anyhow::anyhow!("The transaction was aborted by a failed change; roll it back instead"));
+        }
+        let connection = self.database_manager.get_connection(&transaction.database)
+            .ok_or_else(|| anyhow::anyhow!("Database '{}' is no longer connected", transaction.database))?;
+        connection.execute_batch(if commit { "COMMIT" } else { "ROLLBACK" })?;
+
+        self.state.transaction = None;
+        self.state.show_pending_changes = false;
+        if !commit {
+            // A rollback can undo schema changes too
+            for (old, new) in transaction.renamed_tables.iter().rev() {
+                self.state.apply_table_rename(&transaction.database, new, old);
+            }
+            if let Err(e) = self.database_manager.refresh_database(&transaction.database) {
+                self.state.show_error(format!("Failed to refresh database: {e}"));
+            }
+            self.state.inspect_data = None;
+        }
+        let count = transaction.statements.len();
+        if commit {
+            self.state.show_success(format!("Committed {count} change(s) to '{}'", transaction.database));
+        } else {
+            self.state.show_info(format!("Rolled back {count} change(s) on '{}'", transaction.database));
+        }
+        Ok(())
+    }
+
     /// Select a file for database connection
     pub fn select_file(&mut self, path: PathBuf) -> Result<()> {
         self.connect_to_database_file(path)